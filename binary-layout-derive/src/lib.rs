@@ -0,0 +1,238 @@
+//! This crate implements the `#[derive(BinaryLayout)]` macro for the [binary-layout](https://crates.io/crates/binary-layout) crate.
+//! Don't use it directly, enable the `derive` feature of binary-layout instead and use `binary_layout::BinaryLayout`.
+
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitInt, Result,
+    Type,
+};
+
+/// Derive a layout from a struct definition, as an alternative to the `binary_layout!` macro.
+///
+/// The derive generates a `binary_layout!` invocation with the fields of the struct, so the generated
+/// module offers the same `View` type and field accessors. The struct itself is left as it is and isn't used by the layout.
+///
+/// Attributes on the struct:
+/// - `#[layout(endian = ...)]` (required) sets the endianness of the layout, i.e. `BigEndian`, `LittleEndian` or `NativeEndian`.
+/// - `#[layout(module = ...)]` sets the name of the generated module. By default, it is the name of the struct in snake case.
+///
+/// Attributes on fields:
+/// - `#[layout(offset = ...)]` pins the field to the given offset, like `field @offset: T` in `binary_layout!`. The bytes between the
+///   previous field and the pinned field are skipped, and pinning a field before the end of the previous field is a compile time error.
+/// - `#[layout(stored_as = ...)]` stores a custom type implementing `LayoutAs`, like `field: MyType as u32` in `binary_layout!`.
+/// - `#[layout(len = ...)]` on a `[u8]` field makes it a byte array with computed length, like `field: [u8; expr(...)]` in `binary_layout!`.
+///
+/// All other attributes on fields, e.g. doc comments or `#[cfg(...)]`, are forwarded to `binary_layout!`.
+///
+/// The endianness can't be set for single fields, all fields of the struct use the endianness of the layout.
+/// For fields with a different endianness, define them in a separate layout with that endianness and nest its `NestedView`,
+/// or store them as a custom type implementing `LayoutAs` that converts the byte order.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, BinaryLayout};
+///
+/// #[derive(BinaryLayout)]
+/// #[layout(endian = BigEndian)]
+/// #[allow(dead_code)]
+/// struct IcmpPacket {
+///   packet_type: u8,
+///   code: u8,
+///   checksum: u16,
+///   #[layout(offset = 8)]
+///   rest_of_header: [u8; 4],
+///   /// open ended byte array, matches until the end of the packet
+///   data_section: [u8],
+/// }
+///
+/// # fn main() {
+/// let mut storage = [0; 16];
+/// let mut view = icmp_packet::View::new(&mut storage);
+/// view.checksum_mut().write(10);
+/// assert_eq!(10, view.checksum().read());
+/// assert_eq!(8, icmp_packet::rest_of_header::OFFSET);
+/// assert_eq!(4, view.data_section().len());
+/// # }
+/// ```
+#[proc_macro_derive(BinaryLayout, attributes(layout))]
+pub fn derive_binary_layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let options = StructOptions::parse(&input)?;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "#[derive(BinaryLayout)] only supports structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "#[derive(BinaryLayout)] only supports structs",
+            ))
+        }
+    };
+
+    let module = &options.module;
+    let endian = &options.endian;
+    let mut layout_fields = Vec::new();
+    for field in fields {
+        let name = field.ident.as_ref().expect("named fields have an ident");
+        let options = FieldOptions::parse(&field.attrs)?;
+        let vis = &field.vis;
+        let attrs = field.attrs.iter().filter(|attr| !is_layout_attr(attr));
+        let ty = &field.ty;
+        let field_type = match (&options.stored_as, &options.len) {
+            (Some(_), Some(len)) => {
+                return Err(Error::new_spanned(
+                    len,
+                    "`stored_as` and `len` can't be used on the same field",
+                ))
+            }
+            (Some(stored_as), None) => quote!(#ty as #stored_as),
+            (None, Some(len)) => quote!([u8; expr(#len)]),
+            (None, None) => ty.to_token_stream(),
+        };
+        let pin = options.offset.as_ref().map(|offset| quote!(@ #offset));
+        layout_fields.push(quote! {
+            #(#attrs)* #vis #name #pin: #field_type
+        });
+    }
+
+    Ok(quote! {
+        ::binary_layout::binary_layout!(#module, #endian, {
+            #(#layout_fields,)*
+        });
+    })
+}
+
+fn is_layout_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("layout")
+}
+
+struct StructOptions {
+    module: Ident,
+    endian: Ident,
+}
+
+impl StructOptions {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut module = None;
+        let mut endian = None;
+        for attr in input.attrs.iter().filter(|attr| is_layout_attr(attr)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("endian") {
+                    endian = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("module") {
+                    module = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta
+                        .error("unsupported #[layout] attribute, expected `endian` or `module`"))
+                }
+            })?;
+        }
+        let endian = endian.ok_or_else(|| {
+            Error::new_spanned(
+                &input.ident,
+                "#[derive(BinaryLayout)] needs a #[layout(endian = ...)] attribute, e.g. #[layout(endian = BigEndian)]",
+            )
+        })?;
+        let module = module.unwrap_or_else(|| {
+            Ident::new(&to_snake_case(&input.ident.to_string()), input.ident.span())
+        });
+        Ok(Self { module, endian })
+    }
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    offset: Option<LitInt>,
+    stored_as: Option<Type>,
+    len: Option<Expr>,
+}
+
+impl FieldOptions {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| is_layout_attr(attr)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("offset") {
+                    options.offset = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("stored_as") {
+                    options.stored_as = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("len") {
+                    options.len = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("endian") {
+                    Err(meta.error(
+                        "the endianness can only be set for the whole struct, not for single fields. Use a nested layout or a `LayoutAs` type for fields with a different endianness",
+                    ))
+                } else {
+                    Err(meta.error(
+                        "unsupported #[layout] attribute, expected `offset`, `stored_as` or `len`",
+                    ))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            // Start a new word after a lowercase letter or digit, and at the last capital letter of an acronym, e.g. `TCPHeader`
+            let starts_word = match prev {
+                Some(prev) if prev != '_' => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase() && next.map_or(false, |next| next.is_lowercase()))
+                }
+                _ => false,
+            };
+            if starts_word {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case() {
+        assert_eq!("icmp_packet", to_snake_case("IcmpPacket"));
+        assert_eq!("header", to_snake_case("Header"));
+        assert_eq!("ipv4_header", to_snake_case("Ipv4Header"));
+        assert_eq!("tcp_header", to_snake_case("TCPHeader"));
+        assert_eq!("crc", to_snake_case("CRC"));
+        assert_eq!("already_snake", to_snake_case("already_snake"));
+    }
+}
@@ -0,0 +1,101 @@
+#![allow(dead_code)]
+
+use binary_layout::{prelude::*, BinaryLayout, LayoutAs};
+use core::convert::Infallible;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Id(u32);
+impl LayoutAs<u32> for Id {
+    type ReadError = Infallible;
+    type WriteError = Infallible;
+
+    fn try_read(v: u32) -> Result<Id, Infallible> {
+        Ok(Id(v))
+    }
+
+    fn try_write(v: Id) -> Result<u32, Infallible> {
+        Ok(v.0)
+    }
+}
+
+#[derive(BinaryLayout)]
+#[layout(endian = LittleEndian)]
+struct Header {
+    version: u16,
+    #[layout(offset = 2)]
+    flags: [u8; 2],
+    #[layout(stored_as = u32)]
+    id: Id,
+    tail: [u8],
+}
+
+#[derive(BinaryLayout)]
+#[layout(endian = BigEndian, module = message)]
+struct MessageWithLength {
+    len: u8,
+    #[layout(len = |v| v.len().read() as usize)]
+    payload: [u8],
+}
+
+#[derive(BinaryLayout)]
+#[layout(endian = BigEndian)]
+struct WithAttributes {
+    /// A documented field
+    first: u8,
+    #[cfg(any())]
+    disabled: u32,
+    #[layout(offset = 4)]
+    second: u16,
+}
+
+#[derive(BinaryLayout)]
+#[layout(endian = LittleEndian)]
+struct Outer {
+    checksum: u32,
+    inner: header::NestedView,
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(0, header::version::OFFSET);
+    assert_eq!(2, header::flags::OFFSET);
+    assert_eq!(4, header::id::OFFSET);
+    assert_eq!(8, header::tail::OFFSET);
+    assert_eq!(None, header::SIZE);
+    assert_eq!(Some(6), with_attributes::SIZE);
+    assert_eq!(4, with_attributes::second::OFFSET);
+    assert_eq!(&[(1, 4)], with_attributes::GAPS);
+}
+
+#[test]
+fn read_write() {
+    let mut storage = [0; 12];
+    let mut view = header::View::new(&mut storage);
+    view.version_mut().write(3);
+    view.flags_mut().copy_from_slice(&[1, 2]);
+    view.id_mut().write(Id(1000));
+    view.tail_mut().copy_from_slice(&[5, 6, 7, 8]);
+    assert_eq!(3, view.version().read());
+    assert_eq!(&[1, 2], view.flags());
+    assert_eq!(Id(1000), view.id().read());
+    assert_eq!(&[5, 6, 7, 8], view.tail());
+    assert_eq!([3, 0, 1, 2, 0xE8, 0x03, 0, 0, 5, 6, 7, 8], storage);
+}
+
+#[test]
+fn computed_length() {
+    let storage = [3, 1, 2, 3, 4, 5];
+    let view = message::View::new(&storage);
+    assert_eq!(&[1, 2, 3], view.payload());
+    assert_eq!(4, message::byte_len(&storage));
+}
+
+#[test]
+fn nested() {
+    let mut storage = [0; 12];
+    let mut view = outer::View::new(&mut storage[..]);
+    view.inner_mut().version_mut().write(7);
+    view.checksum_mut().write(9);
+    assert_eq!(7, view.inner().version().read());
+    assert_eq!(9, view.checksum().read());
+}
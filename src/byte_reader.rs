@@ -0,0 +1,268 @@
+use crate::LayoutError;
+
+/// A source of bytes that the `read_from` function generated by [binary_layout!](crate::binary_layout!) reads records from,
+/// e.g. a serial port on a microcontroller. It doesn't need `std::io` or `alloc`, so it works in `no_std` environments.
+///
+/// It is implemented for closures returning `Result<u8, E>`, so a blocking read of a serial port can be passed in as
+/// `|| block!(serial.read())` without implementing the trait.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(frame, BigEndian, {
+///   command: u8,
+///   argument: u16,
+/// });
+///
+/// # fn main() {
+/// let mut incoming = [0x10, 0x01, 0x02].into_iter();
+/// let mut serial = || incoming.next().ok_or("no more bytes");
+///
+/// let mut buf = [0; 3];
+/// let view = frame::read_from(&mut serial, &mut buf).unwrap();
+/// assert_eq!(0x10, view.command().read());
+/// assert_eq!(0x0102, view.argument().read());
+/// # }
+/// ```
+pub trait ByteReader {
+    /// The error returned when reading a byte fails
+    type Error;
+
+    /// Read the next byte, blocking until it is available.
+    fn read_byte(&mut self) -> Result<u8, Self::Error>;
+
+    /// Fill `buf` by calling [ByteReader::read_byte] for each byte.
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        for byte in buf {
+            *byte = self.read_byte()?;
+        }
+        Ok(())
+    }
+}
+
+impl<E, F: FnMut() -> Result<u8, E>> ByteReader for F {
+    type Error = E;
+
+    #[inline]
+    fn read_byte(&mut self) -> Result<u8, E> {
+        self()
+    }
+}
+
+/// This error is thrown by the `read_from` function generated by [binary_layout!](crate::binary_layout!).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadFromError<E> {
+    /// Reading a byte from the [ByteReader] failed
+    Read(E),
+    /// The bytes read don't match the layout, e.g. the buffer is too short for the record
+    Layout(LayoutError),
+}
+
+impl<E> From<LayoutError> for ReadFromError<E> {
+    #[inline]
+    fn from(error: LayoutError) -> Self {
+        Self::Layout(error)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for ReadFromError<E> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Read(error) => write!(fmt, "ReadFromError: {}", error),
+            Self::Layout(error) => write!(fmt, "ReadFromError: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadFromError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(error) => Some(error),
+            Self::Layout(error) => Some(error),
+        }
+    }
+}
+
+/// Internal function, don't use!
+/// Reads a record into `storage` for the `read_from` function generated by [binary_layout!](crate::binary_layout!).
+/// First reads the `min_size` bytes of the statically sized fields, then asks `byte_len` how long the record is and reads the rest.
+#[inline]
+pub fn read_record<R: ByteReader>(
+    reader: &mut R,
+    storage: &mut [u8],
+    min_size: usize,
+    byte_len: fn(&[u8]) -> usize,
+) -> Result<(), ReadFromError<R::Error>> {
+    let actual = storage.len();
+    if actual < min_size {
+        return Err(LayoutError::StorageTooShort {
+            expected: min_size,
+            actual,
+        }
+        .into());
+    }
+    reader
+        .read_exact(&mut storage[..min_size])
+        .map_err(ReadFromError::Read)?;
+    let expected = byte_len(storage);
+    if actual < expected {
+        return Err(LayoutError::StorageTooShort { expected, actual }.into());
+    }
+    reader
+        .read_exact(&mut storage[min_size..expected])
+        .map_err(ReadFromError::Read)?;
+    Ok(())
+}
+
+/// Internal function, don't use!
+/// Reads a record from a [Read](std::io::Read) into a new `Vec<u8>` for the `read_framed` function generated by
+/// [binary_layout!](crate::binary_layout!). Like [read_record], but the storage grows with the bytes actually read.
+#[cfg(feature = "std")]
+pub fn read_framed_record<R: std::io::Read>(
+    reader: &mut R,
+    min_size: usize,
+    byte_len: fn(&[u8]) -> usize,
+) -> Result<Vec<u8>, ReadFromError<std::io::Error>> {
+    use std::io::Read;
+
+    let mut storage = vec![0; min_size];
+    reader
+        .read_exact(&mut storage)
+        .map_err(ReadFromError::Read)?;
+    let missing = byte_len(&storage).saturating_sub(min_size) as u64;
+    let read = reader
+        .take(missing)
+        .read_to_end(&mut storage)
+        .map_err(ReadFromError::Read)?;
+    if (read as u64) < missing {
+        return Err(ReadFromError::Read(
+            std::io::ErrorKind::UnexpectedEof.into(),
+        ));
+    }
+    Ok(storage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(fixed, LittleEndian, {
+        first: u8,
+        second: u16,
+    });
+
+    binary_layout!(tlv, LittleEndian, {
+        tag: u8,
+        len: u8,
+        value: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(message, LittleEndian, {
+        name: Utf8<2>,
+    });
+
+    fn reader(bytes: &[u8]) -> impl FnMut() -> Result<u8, &'static str> + '_ {
+        let mut bytes = bytes.iter().copied();
+        move || bytes.next().ok_or("end of input")
+    }
+
+    #[test]
+    fn fixed_size() {
+        let mut reader = reader(&[1, 2, 3, 4]);
+        let mut buf = [0; 3];
+        let view = fixed::read_from(&mut reader, &mut buf).unwrap();
+        assert_eq!(1, view.first().read());
+        assert_eq!(0x0302, view.second().read());
+        assert_eq!(Ok(4), reader.read_byte());
+    }
+
+    #[test]
+    fn larger_buffer() {
+        let mut reader = reader(&[1, 2, 3, 4]);
+        let mut buf = [0xFF; 5];
+        fixed::read_from(&mut reader, &mut buf[..]).unwrap();
+        assert_eq!([1, 2, 3, 0xFF, 0xFF], buf);
+        assert_eq!(Ok(4), reader.read_byte());
+    }
+
+    #[test]
+    fn dynamic_size() {
+        let mut reader = reader(&[7, 2, 10, 11, 8, 0]);
+        let mut buf = [0; 8];
+        let view = tlv::read_from(&mut reader, &mut buf).unwrap();
+        assert_eq!(7, view.tag().read());
+        assert_eq!(&[10, 11], view.value());
+        let view = tlv::read_from(&mut reader, &mut buf).unwrap();
+        assert_eq!(8, view.tag().read());
+        assert_eq!(&[] as &[u8], view.value());
+    }
+
+    #[test]
+    fn errors() {
+        let mut buf = [0; 2];
+        assert_eq!(
+            Err(ReadFromError::Layout(LayoutError::StorageTooShort {
+                expected: 3,
+                actual: 2
+            })),
+            fixed::read_from(&mut reader(&[1, 2, 3]), &mut buf).map(|_| ())
+        );
+        let mut buf = [0; 3];
+        assert_eq!(
+            Err(ReadFromError::Read("end of input")),
+            fixed::read_from(&mut reader(&[1, 2]), &mut buf).map(|_| ())
+        );
+        let mut buf = [0; 4];
+        assert_eq!(
+            Err(ReadFromError::Layout(LayoutError::StorageTooShort {
+                expected: 5,
+                actual: 4
+            })),
+            tlv::read_from(&mut reader(&[1, 3, 0, 0, 0]), &mut buf).map(|_| ())
+        );
+        let mut buf = [0; 2];
+        assert_eq!(
+            Err(ReadFromError::Layout(LayoutError::InvalidUtf8 {
+                offset: 0
+            })),
+            message::read_from(&mut reader(&[0xFF, 0]), &mut buf).map(|_| ())
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_framed() {
+        let mut stream: &[u8] = &[7, 2, 10, 11, 8, 0, 1, 2, 3];
+        let view = tlv::read_framed(&mut stream).unwrap();
+        assert_eq!(vec![7, 2, 10, 11], view.into_storage());
+        let view = tlv::read_framed(&mut stream).unwrap();
+        assert_eq!(8, view.tag().read());
+        assert_eq!(&[] as &[u8], view.value());
+        let view = fixed::read_framed(&mut stream).unwrap();
+        assert_eq!(0x0302, view.second().read());
+        assert!(stream.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_framed_errors() {
+        let error = |mut stream: &[u8]| match tlv::read_framed(&mut stream) {
+            Err(ReadFromError::Read(error)) => error.kind(),
+            _ => panic!("expected an io error"),
+        };
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error(&[1]));
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error(&[1, 3, 0, 0]));
+        // A huge length only allocates what the reader delivers
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error(&[1, 255]));
+
+        let mut stream: &[u8] = &[0xFF, 0xFF];
+        assert!(matches!(
+            message::read_framed(&mut stream),
+            Err(ReadFromError::Layout(_))
+        ));
+    }
+}
@@ -0,0 +1,330 @@
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::{FieldReadExt, FieldWriteExt};
+
+/// An algorithm computing a checksum over a block of bytes.
+pub trait ChecksumAlgorithm {
+    /// The type of the computed checksum, e.g. [u32] for [Crc32].
+    type Output: PartialEq;
+
+    /// Compute the checksum over the given data.
+    fn checksum(data: &[u8]) -> Self::Output;
+}
+
+/// The CRC-32 checksum algorithm as used by Ethernet, zlib and many others (polynomial `0x04C11DB7`, reflected).
+///
+/// # Example
+/// ```
+/// use binary_layout::{ChecksumAlgorithm, Crc32};
+///
+/// assert_eq!(0xCBF43926, Crc32::checksum(b"123456789"));
+/// ```
+pub struct Crc32;
+
+impl ChecksumAlgorithm for Crc32 {
+    type Output = u32;
+
+    fn checksum(data: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in data {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                let mask = (crc & 1).wrapping_neg();
+                crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+        !crc
+    }
+}
+
+/// Implement this trait to describe a checksum stored in a layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Crc32, LayoutChecksum};
+/// use core::ops::Range;
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   magic: u32,
+///   payload: [u8; 16],
+///   crc: u32,
+/// });
+///
+/// struct MyLayoutChecksum;
+/// impl LayoutChecksum for MyLayoutChecksum {
+///   type Algorithm = Crc32;
+///   type Field = my_layout::crc;
+///
+///   fn covered_range(_storage: &[u8]) -> Range<usize> {
+///     my_layout::magic::OFFSET..my_layout::crc::OFFSET
+///   }
+/// }
+///
+/// let mut storage = [0; 24];
+/// MyLayoutChecksum::seal(&mut storage);
+/// assert!(MyLayoutChecksum::verify(&storage).is_ok());
+///
+/// storage[5] = 1;
+/// assert!(MyLayoutChecksum::verify(&storage).is_err());
+/// ```
+pub trait LayoutChecksum {
+    /// The algorithm used to compute the checksum
+    type Algorithm: ChecksumAlgorithm;
+
+    /// The field storing the checksum
+    type Field: FieldReadExt<HighLevelType = <Self::Algorithm as ChecksumAlgorithm>::Output>
+        + FieldWriteExt<HighLevelType = <Self::Algorithm as ChecksumAlgorithm>::Output>;
+
+    /// The range of bytes in the storage of the layout that are covered by the checksum.
+    /// This usually shouldn't include the checksum field itself.
+    fn covered_range(storage: &[u8]) -> Range<usize>;
+
+    /// Compute the checksum over the covered range of the given storage.
+    #[inline]
+    fn compute(storage: &[u8]) -> <Self::Algorithm as ChecksumAlgorithm>::Output {
+        Self::Algorithm::checksum(&storage[Self::covered_range(storage)])
+    }
+
+    /// Check that the checksum stored in the given storage matches the data it covers.
+    fn verify(
+        storage: &[u8],
+    ) -> Result<(), ChecksumMismatchError<<Self::Algorithm as ChecksumAlgorithm>::Output>> {
+        let stored = <Self::Field as FieldReadExt>::read(storage);
+        let computed = Self::compute(storage);
+        if stored == computed {
+            Ok(())
+        } else {
+            Err(ChecksumMismatchError { stored, computed })
+        }
+    }
+
+    /// Compute the checksum over the data it covers and store it in the checksum field.
+    fn seal(storage: &mut [u8]) {
+        let computed = Self::compute(storage);
+        <Self::Field as FieldWriteExt>::write(storage, computed);
+    }
+}
+
+/// This error is thrown when a checksum stored in a layout doesn't match the data it covers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChecksumMismatchError<T> {
+    /// The checksum stored in the checksum field
+    pub stored: T,
+    /// The checksum computed from the covered data
+    pub computed: T,
+}
+
+impl<T: Debug> core::fmt::Display for ChecksumMismatchError<T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            fmt,
+            "ChecksumMismatchError: stored checksum is {:?} but computed checksum is {:?}",
+            self.stored, self.computed
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> std::error::Error for ChecksumMismatchError<T> {}
+
+/// A [ChecksummedView] wraps the storage of a layout that has a checksum described by a [LayoutChecksum].
+/// The checksum is verified when the [ChecksummedView] is created, so any data accessed through it
+/// has passed the integrity check. After modifying the data, call [ChecksummedView::seal] to recompute the checksum.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, ChecksummedView, Crc32, LayoutChecksum};
+/// use core::ops::Range;
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   magic: u32,
+///   payload: [u8; 16],
+///   crc: u32,
+/// });
+///
+/// struct MyLayoutChecksum;
+/// impl LayoutChecksum for MyLayoutChecksum {
+///   type Algorithm = Crc32;
+///   type Field = my_layout::crc;
+///
+///   fn covered_range(_storage: &[u8]) -> Range<usize> {
+///     my_layout::magic::OFFSET..my_layout::crc::OFFSET
+///   }
+/// }
+///
+/// fn func(storage_data: &mut [u8]) -> Result<u32, binary_layout::ChecksumMismatchError<u32>> {
+///   let mut checked = ChecksummedView::<_, MyLayoutChecksum>::new(storage_data)?;
+///   let magic = my_layout::View::new(checked.storage()).magic().read();
+///
+///   my_layout::View::new(checked.storage_mut()).magic_mut().write(magic + 1);
+///   checked.seal();
+///   Ok(magic)
+/// }
+///
+/// let mut storage = [0; 24];
+/// MyLayoutChecksum::seal(&mut storage);
+/// assert_eq!(Ok(0), func(&mut storage));
+/// assert_eq!(Ok(1), func(&mut storage));
+/// ```
+pub struct ChecksummedView<S, C: LayoutChecksum> {
+    storage: S,
+    _p: PhantomData<C>,
+}
+
+impl<S: AsRef<[u8]>, C: LayoutChecksum> ChecksummedView<S, C> {
+    /// Create a [ChecksummedView] over the given storage, verifying the checksum stored in it.
+    pub fn new(
+        storage: S,
+    ) -> Result<Self, ChecksumMismatchError<<C::Algorithm as ChecksumAlgorithm>::Output>> {
+        C::verify(storage.as_ref())?;
+        Ok(Self {
+            storage,
+            _p: PhantomData,
+        })
+    }
+
+    /// Borrow the verified storage with read access.
+    #[inline]
+    pub fn storage(&self) -> &[u8] {
+        self.storage.as_ref()
+    }
+
+    /// This destroys the view and returns the underlying storage back to you.
+    #[inline]
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
+
+impl<S: AsRef<[u8]> + AsMut<[u8]>, C: LayoutChecksum> ChecksummedView<S, C> {
+    /// Create a [ChecksummedView] over the given storage and compute and store the checksum
+    /// instead of verifying it. This is useful for creating new messages.
+    pub fn new_sealed(mut storage: S) -> Self {
+        C::seal(storage.as_mut());
+        Self {
+            storage,
+            _p: PhantomData,
+        }
+    }
+
+    /// Borrow the storage with write access. The checksum isn't updated automatically, call
+    /// [ChecksummedView::seal] after you're done modifying the data.
+    #[inline]
+    pub fn storage_mut(&mut self) -> &mut [u8] {
+        self.storage.as_mut()
+    }
+
+    /// Recompute the checksum over the covered data and store it in the checksum field.
+    pub fn seal(&mut self) {
+        C::seal(self.storage.as_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        magic: u32,
+        payload: [u8; 8],
+        crc: u32,
+    });
+
+    binary_layout!(with_attribute, BigEndian, {
+        #[checksum(Crc32, from = crc, to = crc)]
+        #[name(crc_of_crc)]
+        crc2: u32,
+        #[checksum(Crc32, from = magic, to = payload)]
+        crc: u32,
+        magic: u32,
+        payload_len: u8,
+        payload: [u8; expr(|v| v.payload_len().read() as usize)],
+    });
+
+    struct LayoutCrc;
+    impl LayoutChecksum for LayoutCrc {
+        type Algorithm = Crc32;
+        type Field = layout::crc;
+
+        fn covered_range(_storage: &[u8]) -> Range<usize> {
+            layout::magic::OFFSET..layout::crc::OFFSET
+        }
+    }
+
+    #[test]
+    fn crc32() {
+        assert_eq!(0, Crc32::checksum(b""));
+        assert_eq!(0xE8B7BE43, Crc32::checksum(b"a"));
+        assert_eq!(0xCBF43926, Crc32::checksum(b"123456789"));
+        assert_eq!(
+            0x414FA339,
+            Crc32::checksum(b"The quick brown fox jumps over the lazy dog")
+        );
+    }
+
+    #[test]
+    fn given_valid_checksum_then_verifies() {
+        let mut storage = [1; 16];
+        LayoutCrc::seal(&mut storage);
+        assert_eq!(Crc32::checksum(&[1; 12]), layout::crc::read(&storage));
+        assert!(LayoutCrc::verify(&storage).is_ok());
+        assert!(ChecksummedView::<_, LayoutCrc>::new(&storage).is_ok());
+    }
+
+    #[test]
+    fn given_invalid_checksum_then_fails() {
+        let mut storage = [1; 16];
+        LayoutCrc::seal(&mut storage);
+        storage[7] = 2;
+        let expected = ChecksumMismatchError {
+            stored: Crc32::checksum(&[1; 12]),
+            computed: LayoutCrc::compute(&storage),
+        };
+        assert_eq!(Err(expected), LayoutCrc::verify(&storage));
+        assert!(ChecksummedView::<_, LayoutCrc>::new(&storage).is_err());
+    }
+
+    #[test]
+    fn given_modified_data_when_sealing_then_verifies() {
+        let mut storage = [0; 16];
+        let mut view = ChecksummedView::<_, LayoutCrc>::new_sealed(&mut storage);
+        layout::View::new(view.storage_mut()).magic_mut().write(10);
+        assert!(LayoutCrc::verify(view.storage()).is_err());
+        view.seal();
+        assert!(LayoutCrc::verify(view.storage()).is_ok());
+
+        let storage = view.into_storage();
+        assert_eq!(10, layout::magic::read(storage));
+        assert!(ChecksummedView::<_, LayoutCrc>::new(storage).is_ok());
+    }
+
+    #[test]
+    fn checksum_attribute() {
+        assert_eq!(
+            4..8,
+            with_attribute::crc_of_crc_checksum::covered_range(&[])
+        );
+        let mut storage = [0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 2, 5, 6];
+        assert_eq!(8..15, with_attribute::crc_checksum::covered_range(&storage));
+
+        with_attribute::crc_checksum::seal(&mut storage);
+        with_attribute::crc_of_crc_checksum::seal(&mut storage);
+        assert_eq!(
+            Crc32::checksum(&[1, 2, 3, 4, 2, 5, 6]),
+            with_attribute::crc::read(&storage)
+        );
+        assert_eq!(
+            Crc32::checksum(&storage[4..8]),
+            with_attribute::crc_of_crc::read(&storage)
+        );
+        assert!(with_attribute::crc_checksum::verify(&storage).is_ok());
+        assert!(ChecksummedView::<_, with_attribute::crc_checksum>::new(&storage).is_ok());
+
+        storage[14] = 0;
+        assert!(with_attribute::crc_checksum::verify(&storage).is_err());
+        assert!(with_attribute::crc_of_crc_checksum::verify(&storage).is_ok());
+    }
+}
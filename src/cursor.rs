@@ -0,0 +1,244 @@
+use crate::fields::primitive::{BorrowingNestedView, NestedViewInfo};
+
+/// A cursor over a storage holding messages of different layouts back to back, e.g. a demultiplexed stream of
+/// type-length-value (TLV) or length-delimited messages. It tracks the position of the next message, and each call
+/// to [MessageCursor::next] or [MessageCursor::next_with_len] returns a view over the message at that position and
+/// advances behind it. Unlike [Records](crate::Records), the caller chooses the layout of each message, e.g. after
+/// looking at a tag with [MessageCursor::peek].
+///
+/// Layouts are given by the `NestedView` marker type of their generated module, e.g. `header::NestedView`.
+/// Reading a message that doesn't fit into the remaining storage returns `None` and doesn't advance the cursor.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, MessageCursor};
+///
+/// binary_layout!(header, BigEndian, {
+///   tag: u8,
+///   len: u8,
+/// });
+///
+/// binary_layout!(ping, BigEndian, {
+///   tag: u8,
+///   len: u8,
+///   sequence: u16,
+/// });
+///
+/// binary_layout!(text, BigEndian, {
+///   tag: u8,
+///   len: u8,
+///   content: [u8],
+/// });
+///
+/// # fn main() {
+/// let storage = [1, 2, 0, 7, 2, 3, b'a', b'b', b'c', 1, 2, 0, 8];
+/// let mut cursor = MessageCursor::new(&storage);
+/// let mut sequences = vec![];
+/// let mut texts = vec![];
+/// while let Some(header) = cursor.peek::<header::NestedView>() {
+///     // The len field counts the bytes after the header
+///     let message_len = 2 + header.len().read() as usize;
+///     match header.tag().read() {
+///         1 => {
+///             let message = cursor.next_with_len::<ping::NestedView>(|_| message_len).unwrap();
+///             sequences.push(message.sequence().read());
+///         }
+///         2 => {
+///             let message = cursor.next_with_len::<text::NestedView>(|_| message_len).unwrap();
+///             texts.push(message.into_content().into_slice());
+///         }
+///         _ => {
+///             cursor.skip(message_len).unwrap();
+///         }
+///     }
+/// }
+/// assert_eq!(vec![7, 8], sequences);
+/// assert_eq!(vec![&b"abc"[..]], texts);
+/// assert_eq!(13, cursor.position());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageCursor<'a> {
+    storage: &'a [u8],
+    position: usize,
+}
+
+impl<'a> MessageCursor<'a> {
+    /// Create a cursor positioned at the start of the given storage.
+    #[inline]
+    pub fn new(storage: &'a [u8]) -> Self {
+        Self {
+            storage,
+            position: 0,
+        }
+    }
+
+    /// Return the offset of the next message within the storage.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return the part of the storage that wasn't consumed by the cursor yet.
+    #[inline]
+    pub fn remainder(&self) -> &'a [u8] {
+        &self.storage[self.position..]
+    }
+
+    /// Return true if the cursor consumed the whole storage.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.position == self.storage.len()
+    }
+
+    /// Return a view over the statically sized part of the layout `N` at the current position without advancing the cursor,
+    /// e.g. to look at a common header before deciding which layout the message has.
+    /// Returns `None` if the remaining storage is shorter than `N::MIN_SIZE`.
+    #[inline]
+    pub fn peek<N: NestedViewInfo + BorrowingNestedView<&'a [u8]>>(&self) -> Option<N::View> {
+        let remainder = self.remainder();
+        if N::MIN_SIZE > remainder.len() {
+            return None;
+        }
+        Some(N::view(&remainder[..N::MIN_SIZE]))
+    }
+
+    /// Read a message of the layout `N` at the current position and advance behind it. The length of the message is
+    /// its [byte_len](crate::binary_layout#struct-view), i.e. its size for statically sized layouts or the size
+    /// computed from its own fields for layouts ending in a `[u8; expr(...)]` field. Layouts ending in an open ended
+    /// byte slice consume the whole remaining storage.
+    /// Returns `None` if the remaining storage is shorter than the message.
+    #[allow(clippy::should_implement_trait)] // The layout is chosen per call, so this can't be an Iterator.
+    pub fn next<N: NestedViewInfo + BorrowingNestedView<&'a [u8]>>(&mut self) -> Option<N::View> {
+        let remainder = self.remainder();
+        if N::MIN_SIZE > remainder.len() {
+            return None;
+        }
+        let len = N::byte_len(remainder);
+        self.advance::<N>(len)
+    }
+
+    /// Read a message of the layout `N` at the current position and advance behind it. The length of the message is
+    /// returned by `len`, which gets a view over the statically sized part of the message, e.g. to read a length field
+    /// that has to be adjusted for the size of a header. The view returned by this function only covers the message,
+    /// so an open ended byte slice at the end of `N` holds the rest of the message.
+    /// Returns `None` if the length is shorter than `N::MIN_SIZE` or the remaining storage is shorter than the message.
+    pub fn next_with_len<N: NestedViewInfo + BorrowingNestedView<&'a [u8]>>(
+        &mut self,
+        len: impl FnOnce(N::View) -> usize,
+    ) -> Option<N::View> {
+        let len = len(self.peek::<N>()?);
+        if len < N::MIN_SIZE {
+            return None;
+        }
+        self.advance::<N>(len)
+    }
+
+    /// Skip `len` bytes, e.g. a message of an unknown type, and return them.
+    /// Returns `None` and doesn't advance if the remaining storage is shorter than `len`.
+    pub fn skip(&mut self, len: usize) -> Option<&'a [u8]> {
+        let remainder = self.remainder();
+        if len > remainder.len() {
+            return None;
+        }
+        self.position += len;
+        Some(&remainder[..len])
+    }
+
+    fn advance<N: BorrowingNestedView<&'a [u8]>>(&mut self, len: usize) -> Option<N::View> {
+        self.skip(len).map(N::view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(fixed, BigEndian, {
+        tag: u8,
+        value: u16,
+    });
+
+    binary_layout!(computed, BigEndian, {
+        tag: u8,
+        len: u8,
+        data: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(prefixed, BigEndian, {
+        tag: u8,
+        total_len: u8,
+        tail: [u8],
+    });
+
+    #[test]
+    fn next() {
+        let storage = [1, 0, 5, 2, 2, 0xAA, 0xBB, 1, 0, 6];
+        let mut cursor = MessageCursor::new(&storage);
+        assert_eq!(
+            5,
+            cursor.next::<fixed::NestedView>().unwrap().value().read()
+        );
+        let record = cursor.next::<computed::NestedView>().unwrap();
+        assert_eq!(&[0xAA, 0xBB], record.data());
+        assert_eq!(7, cursor.position());
+        assert_eq!(
+            6,
+            cursor.next::<fixed::NestedView>().unwrap().value().read()
+        );
+        assert!(cursor.is_empty());
+        assert!(cursor.next::<fixed::NestedView>().is_none());
+    }
+
+    #[test]
+    fn next_truncated() {
+        let storage = [2, 3, 0xAA, 0xBB];
+        let mut cursor = MessageCursor::new(&storage);
+        assert!(cursor.next::<computed::NestedView>().is_none());
+        assert_eq!(&storage, cursor.remainder());
+        assert!(cursor.next::<fixed::NestedView>().is_some());
+        assert_eq!(&[0xBB], cursor.remainder());
+    }
+
+    #[test]
+    fn next_with_len() {
+        let storage = [3, 4, 0xAA, 0xBB, 3, 2, 3, 9];
+        let mut cursor = MessageCursor::new(&storage);
+        let total_len = |view: prefixed::View<&[u8]>| view.total_len().read() as usize;
+        let message = cursor
+            .next_with_len::<prefixed::NestedView>(total_len)
+            .unwrap();
+        assert_eq!(&[0xAA, 0xBB], message.tail());
+        let message = cursor
+            .next_with_len::<prefixed::NestedView>(total_len)
+            .unwrap();
+        assert!(message.tail().is_empty());
+        // Shorter than the layout
+        assert!(cursor
+            .next_with_len::<prefixed::NestedView>(|_| 1)
+            .is_none());
+        // Longer than the remaining storage
+        assert!(cursor
+            .next_with_len::<prefixed::NestedView>(|_| 3)
+            .is_none());
+        assert_eq!(6, cursor.position());
+        assert!(cursor
+            .next_with_len::<prefixed::NestedView>(total_len)
+            .is_none());
+        assert_eq!(&[3, 9], cursor.skip(2).unwrap());
+        assert!(cursor.peek::<prefixed::NestedView>().is_none());
+    }
+
+    #[test]
+    fn skip() {
+        let storage = [1, 2, 3];
+        let mut cursor = MessageCursor::new(&storage);
+        assert_eq!(&[1, 2], cursor.skip(2).unwrap());
+        assert!(cursor.skip(2).is_none());
+        assert_eq!(2, cursor.position());
+        assert_eq!(&[3], cursor.skip(1).unwrap());
+        assert_eq!(Some(&[][..]), cursor.skip(0));
+        assert!(cursor.is_empty());
+    }
+}
@@ -1,14 +1,162 @@
+use crate::LayoutError;
+
 /// An enum representing the endianness used in a layout for accessing primitive integer fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EndianKind {
+    /// Big endian, see [BigEndian]
     Big,
+    /// Little endian, see [LittleEndian]
     Little,
+    /// The endianness of the system the code is running on, see [NativeEndian]
     Native,
 }
 
+macro_rules! endianness_conversions {
+    ($($type:ident),*) => {
+        paste::paste! {
+            $(
+                doc_comment::doc_comment! {
+                    concat! {"
+                    Convert bytes stored in this endianness into a `", stringify!($type), "`.
+
+                    # Example
+                    ```
+                    use binary_layout::Endianness;
+
+                    fn parse<E: Endianness>(bytes: &[u8]) -> ", stringify!($type), " {
+                        let bytes = bytes[..core::mem::size_of::<", stringify!($type), ">()].try_into().unwrap();
+                        E::read_", stringify!($type), "(bytes)
+                    }
+                    ```
+                    "},
+                    #[inline(always)]
+                    fn [<read_ $type>](bytes: [u8; core::mem::size_of::<$type>()]) -> $type {
+                        match Self::KIND {
+                            EndianKind::Big => <$type>::from_be_bytes(bytes),
+                            EndianKind::Little => <$type>::from_le_bytes(bytes),
+                            EndianKind::Native => <$type>::from_ne_bytes(bytes),
+                        }
+                    }
+                }
+
+                doc_comment::doc_comment! {
+                    concat! {"
+                    Convert a `", stringify!($type), "` into bytes stored in this endianness.
+
+                    # Example
+                    ```
+                    use binary_layout::Endianness;
+
+                    fn serialize<E: Endianness>(value: ", stringify!($type), ", dest: &mut [u8]) {
+                        dest[..core::mem::size_of::<", stringify!($type), ">()].copy_from_slice(&E::write_", stringify!($type), "(value));
+                    }
+                    ```
+                    "},
+                    #[inline(always)]
+                    fn [<write_ $type>](value: $type) -> [u8; core::mem::size_of::<$type>()] {
+                        match Self::KIND {
+                            EndianKind::Big => value.to_be_bytes(),
+                            EndianKind::Little => value.to_le_bytes(),
+                            EndianKind::Native => value.to_ne_bytes(),
+                        }
+                    }
+                }
+
+                doc_comment::doc_comment! {
+                    concat! {"
+                    Convert bytes stored in this endianness into a slice of `", stringify!($type), "` values.
+
+                    This converts the whole slice in one call instead of reading each element through its own field accessor.
+                    It is a convenience API and not an optimization: the elements are still converted one by one with scalar code,
+                    and it isn't faster than converting them in a loop yourself. There is no vectorized code path.
+
+                    # Panics
+                    Panics if `bytes` doesn't have exactly `size_of::<", stringify!($type), ">()` bytes per element of `dest`.
+
+                    # Example
+                    ```
+                    use binary_layout::{prelude::*, Endianness};
+
+                    binary_layout!(my_layout, BigEndian, {
+                        values: [u8; 16],
+                    });
+
+                    fn parse(storage: &[u8]) -> [", stringify!($type), "; 16 / core::mem::size_of::<", stringify!($type), ">()] {
+                        let mut values = [Default::default(); 16 / core::mem::size_of::<", stringify!($type), ">()];
+                        BigEndian::read_", stringify!($type), "_slice(my_layout::values::data(storage), &mut values);
+                        values
+                    }
+                    ```
+                    "},
+                    #[inline]
+                    fn [<read_ $type _slice>](bytes: &[u8], dest: &mut [$type]) {
+                        assert_eq!(dest.len() * core::mem::size_of::<$type>(), bytes.len(), "Source and destination lengths don't match");
+                        for (value, bytes) in dest.iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$type>())) {
+                            *value = Self::[<read_ $type>](bytes.try_into().unwrap());
+                        }
+                    }
+                }
+
+                doc_comment::doc_comment! {
+                    concat! {"
+                    Convert a slice of `", stringify!($type), "` values into bytes stored in this endianness.
+
+                    This converts the whole slice in one call instead of writing each element through its own field accessor.
+                    It is a convenience API and not an optimization: the elements are still converted one by one with scalar code,
+                    and it isn't faster than converting them in a loop yourself. There is no vectorized code path.
+
+                    # Panics
+                    Panics if `dest` doesn't have exactly `size_of::<", stringify!($type), ">()` bytes per element of `values`.
+
+                    # Example
+                    ```
+                    use binary_layout::{prelude::*, Endianness};
+
+                    binary_layout!(my_layout, BigEndian, {
+                        values: [u8; 16],
+                    });
+
+                    fn serialize(values: &[", stringify!($type), "; 16 / core::mem::size_of::<", stringify!($type), ">()], storage: &mut [u8]) {
+                        BigEndian::write_", stringify!($type), "_slice(values, my_layout::values::data_mut(storage));
+                    }
+                    ```
+                    "},
+                    #[inline]
+                    fn [<write_ $type _slice>](values: &[$type], dest: &mut [u8]) {
+                        assert_eq!(values.len() * core::mem::size_of::<$type>(), dest.len(), "Source and destination lengths don't match");
+                        for (value, dest) in values.iter().zip(dest.chunks_exact_mut(core::mem::size_of::<$type>())) {
+                            dest.copy_from_slice(&Self::[<write_ $type>](*value));
+                        }
+                    }
+                }
+            )*
+        }
+    };
+}
+
 /// This marker trait represents the endianness used in a layout for accessing primitive integer fields.
+///
+/// Besides marking layouts, it can be used to write code that is generic over the byte order,
+/// using the `read_*` and `write_*` conversion functions it offers for all primitive integer and float types.
+///
+/// # Example
+/// ```
+/// use binary_layout::{BigEndian, Endianness, LittleEndian};
+///
+/// fn parse_header<E: Endianness>(data: &[u8; 6]) -> (u16, u32) {
+///   let version = E::read_u16([data[0], data[1]]);
+///   let length = E::read_u32([data[2], data[3], data[4], data[5]]);
+///   (version, length)
+/// }
+///
+/// assert_eq!((1, 2), parse_header::<BigEndian>(&[0, 1, 0, 0, 0, 2]));
+/// assert_eq!((1, 2), parse_header::<LittleEndian>(&[1, 0, 2, 0, 0, 0]));
+/// ```
 pub trait Endianness {
     /// Accessor to the endianness as a const value
     const KIND: EndianKind;
+
+    endianness_conversions!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
 }
 
 /// This is a marker type to mark layouts using big endian encoding. The alternative is [LittleEndian] and [NativeEndian] encoding.
@@ -58,3 +206,211 @@ pub struct NativeEndian {}
 impl Endianness for NativeEndian {
     const KIND: EndianKind = EndianKind::Native;
 }
+
+/// Read an unsigned integer with a width and endianness chosen at runtime from `storage` at the given byte `offset`.
+///
+/// This is a low level utility for tools that don't know the layout of the data at compile time, e.g. hex editors
+/// or binary inspectors. If you know the layout at compile time, use [binary_layout!](crate::binary_layout!) instead.
+/// Any width from 1 to 16 bytes is supported, including widths without a matching Rust type like 3 byte integers.
+/// The value is zero extended to a `u128`, so signed values need to be sign extended by the caller.
+///
+/// Returns [LayoutError::UnsupportedWidth] for other widths and [LayoutError::FieldOutOfBounds] if the value
+/// doesn't fit into the storage at the given offset.
+///
+/// # Example
+/// ```
+/// use binary_layout::{read_scalar, EndianKind, LayoutError};
+///
+/// let storage = [0x01, 0x02, 0x03, 0x04];
+/// assert_eq!(Ok(0x0203), read_scalar(&storage, 1, 2, EndianKind::Big));
+/// assert_eq!(Ok(0x040302), read_scalar(&storage, 1, 3, EndianKind::Little));
+/// assert_eq!(
+///     Err(LayoutError::FieldOutOfBounds { offset: 2, expected: 6, actual: 4 }),
+///     read_scalar(&storage, 2, 4, EndianKind::Big),
+/// );
+/// assert_eq!(
+///     Err(LayoutError::UnsupportedWidth { width: 17 }),
+///     read_scalar(&storage, 0, 17, EndianKind::Big),
+/// );
+/// ```
+pub fn read_scalar(
+    storage: &[u8],
+    offset: usize,
+    width: usize,
+    endian: EndianKind,
+) -> Result<u128, LayoutError> {
+    const MAX_WIDTH: usize = core::mem::size_of::<u128>();
+    if width == 0 || width > MAX_WIDTH {
+        return Err(LayoutError::UnsupportedWidth { width });
+    }
+    let bytes = offset
+        .checked_add(width)
+        .and_then(|end| storage.get(offset..end))
+        .ok_or(LayoutError::FieldOutOfBounds {
+            offset,
+            expected: offset.saturating_add(width),
+            actual: storage.len(),
+        })?;
+    let mut buffer = [0; MAX_WIDTH];
+    let endian = match endian {
+        EndianKind::Native if cfg!(target_endian = "big") => EndianKind::Big,
+        EndianKind::Native => EndianKind::Little,
+        endian => endian,
+    };
+    match endian {
+        EndianKind::Big => {
+            buffer[MAX_WIDTH - width..].copy_from_slice(bytes);
+            Ok(u128::from_be_bytes(buffer))
+        }
+        _ => {
+            buffer[..width].copy_from_slice(bytes);
+            Ok(u128::from_le_bytes(buffer))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+    use super::*;
+
+    macro_rules! test_conversions {
+        ($type:ident, $value:expr) => {
+            paste::paste! {
+                #[test]
+                fn [<test_ $type>]() {
+                    let value: $type = $value;
+                    assert_eq!(value.to_be_bytes(), BigEndian::[<write_ $type>](value));
+                    assert_eq!(value.to_le_bytes(), LittleEndian::[<write_ $type>](value));
+                    assert_eq!(value.to_ne_bytes(), NativeEndian::[<write_ $type>](value));
+
+                    assert_eq!(value, BigEndian::[<read_ $type>](value.to_be_bytes()));
+                    assert_eq!(value, LittleEndian::[<read_ $type>](value.to_le_bytes()));
+                    assert_eq!(value, NativeEndian::[<read_ $type>](value.to_ne_bytes()));
+                }
+
+                #[test]
+                fn [<test_ $type _slice>]() {
+                    let mut values: [$type; 100] = [$value; 100];
+                    for (i, value) in values.iter_mut().enumerate() {
+                        *value /= (i as u8 + 1) as $type;
+                    }
+                    let mut bytes = [0u8; 100 * core::mem::size_of::<$type>()];
+                    let mut read_back: [$type; 100] = [Default::default(); 100];
+
+                    BigEndian::[<write_ $type _slice>](&values, &mut bytes);
+                    for (value, bytes) in values.iter().zip(bytes.chunks_exact(core::mem::size_of::<$type>())) {
+                        assert_eq!(&value.to_be_bytes(), bytes);
+                    }
+                    BigEndian::[<read_ $type _slice>](&bytes, &mut read_back);
+                    assert_eq!(values, read_back);
+
+                    LittleEndian::[<write_ $type _slice>](&values, &mut bytes);
+                    for (value, bytes) in values.iter().zip(bytes.chunks_exact(core::mem::size_of::<$type>())) {
+                        assert_eq!(&value.to_le_bytes(), bytes);
+                    }
+                    LittleEndian::[<read_ $type _slice>](&bytes, &mut read_back);
+                    assert_eq!(values, read_back);
+
+                    NativeEndian::[<write_ $type _slice>](&values, &mut bytes);
+                    for (value, bytes) in values.iter().zip(bytes.chunks_exact(core::mem::size_of::<$type>())) {
+                        assert_eq!(&value.to_ne_bytes(), bytes);
+                    }
+                    NativeEndian::[<read_ $type _slice>](&bytes, &mut read_back);
+                    assert_eq!(values, read_back);
+                }
+            }
+        };
+    }
+
+    test_conversions!(u8, 50u8);
+    test_conversions!(u16, 500u16);
+    test_conversions!(u32, 10u32.pow(8));
+    test_conversions!(u64, 10u64.pow(15));
+    test_conversions!(u128, 10u128.pow(30));
+    test_conversions!(i8, -50i8);
+    test_conversions!(i16, -500i16);
+    test_conversions!(i32, -(10i32.pow(8)));
+    test_conversions!(i64, -(10i64.pow(15)));
+    test_conversions!(i128, -(10i128.pow(30)));
+    test_conversions!(f32, 1.5e10f32);
+    test_conversions!(f64, -1.5e100f64);
+
+    #[test]
+    #[should_panic]
+    fn test_read_slice_length_mismatch() {
+        BigEndian::read_u32_slice(&[0; 7], &mut [0; 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_write_slice_length_mismatch() {
+        BigEndian::write_u32_slice(&[0; 2], &mut [0; 9]);
+    }
+
+    #[test]
+    fn test_read_scalar() {
+        let storage = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17];
+        assert_eq!(Ok(1), read_scalar(&storage, 0, 1, EndianKind::Little));
+        assert_eq!(Ok(0x0203), read_scalar(&storage, 1, 2, EndianKind::Big));
+        assert_eq!(Ok(0x0302), read_scalar(&storage, 1, 2, EndianKind::Little));
+        assert_eq!(Ok(0x030405), read_scalar(&storage, 2, 3, EndianKind::Big));
+        assert_eq!(
+            Ok(u64::from_be_bytes([3, 4, 5, 6, 7, 8, 9, 10]) as u128),
+            read_scalar(&storage, 2, 8, EndianKind::Big)
+        );
+        assert_eq!(
+            Ok(u32::from_ne_bytes([3, 4, 5, 6]) as u128),
+            read_scalar(&storage, 2, 4, EndianKind::Native)
+        );
+        assert_eq!(
+            Ok(u128::from_le_bytes(storage[1..].try_into().unwrap())),
+            read_scalar(&storage, 1, 16, EndianKind::Little)
+        );
+    }
+
+    #[test]
+    fn test_read_scalar_errors() {
+        let storage = [0; 8];
+        assert_eq!(
+            Err(LayoutError::UnsupportedWidth { width: 0 }),
+            read_scalar(&storage, 0, 0, EndianKind::Big)
+        );
+        assert_eq!(
+            Err(LayoutError::UnsupportedWidth { width: 17 }),
+            read_scalar(&storage, 0, 17, EndianKind::Big)
+        );
+        assert_eq!(Ok(0), read_scalar(&storage, 4, 4, EndianKind::Big));
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 5,
+                expected: 9,
+                actual: 8
+            }),
+            read_scalar(&storage, 5, 4, EndianKind::Big)
+        );
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 100,
+                expected: 101,
+                actual: 8
+            }),
+            read_scalar(&storage, 100, 1, EndianKind::Big)
+        );
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: usize::MAX,
+                expected: usize::MAX,
+                actual: 8
+            }),
+            read_scalar(&storage, usize::MAX, 2, EndianKind::Big)
+        );
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(EndianKind::Big, BigEndian::KIND);
+        assert_eq!(EndianKind::Little, LittleEndian::KIND);
+        assert_eq!(EndianKind::Native, NativeEndian::KIND);
+    }
+}
@@ -0,0 +1,250 @@
+/// This error is thrown when a storage doesn't match a layout, e.g. from the `try_new` function generated by [binary_layout!](crate::binary_layout!).
+///
+/// It only carries plain values and doesn't allocate, so it works in `no_std` environments.
+/// It implements [core::fmt::Display] and, with the `std` feature, [std::error::Error].
+/// More variants may be added in the future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// The storage is shorter than the number of bytes used by the layout.
+    StorageTooShort {
+        /// The number of bytes needed by the layout. For layouts with a dynamic size,
+        /// this can depend on the values of fields stored in the storage.
+        expected: usize,
+        /// The number of bytes in the storage
+        actual: usize,
+    },
+    /// The storage is longer than the number of bytes used by the layout, returned by `try_new_exact`.
+    StorageTooLong {
+        /// The number of bytes used by the layout. For layouts with a dynamic size,
+        /// this can depend on the values of fields stored in the storage.
+        expected: usize,
+        /// The number of bytes in the storage
+        actual: usize,
+    },
+    /// The storage is too short to hold a field accessed through [FieldCheckedAccess](crate::FieldCheckedAccess).
+    FieldOutOfBounds {
+        /// The offset of the field in the layout
+        offset: usize,
+        /// The number of bytes the storage needs to hold the field, i.e. the end of the field.
+        /// For fields with a dynamic size, this can depend on the values of fields stored in the storage.
+        expected: usize,
+        /// The number of bytes in the storage
+        actual: usize,
+    },
+    /// A `Utf8<N>` field doesn't contain valid UTF-8, see [Utf8](crate::Utf8).
+    InvalidUtf8 {
+        /// The offset of the field in the layout
+        offset: usize,
+    },
+    /// A value of this width can't be read by [read_scalar](crate::read_scalar). Supported widths are 1 to 16 bytes.
+    UnsupportedWidth {
+        /// The requested width in bytes
+        width: usize,
+    },
+    /// A byte that doesn't belong to any field, e.g. a gap before a pinned field or padding, isn't zero.
+    /// Returned by the `check_reserved_zero` function generated by [binary_layout!](crate::binary_layout!).
+    ReservedNotZero {
+        /// The offset of the first byte that isn't zero
+        offset: usize,
+    },
+    /// A region of the storage given by an offset and a length, e.g. from a directory of sub-structures, reaches beyond the end of the storage.
+    /// Returned by the `view_at` function generated by [binary_layout!](crate::binary_layout!).
+    RegionOutOfBounds {
+        /// The offset of the region in the storage
+        offset: usize,
+        /// The length of the region
+        len: usize,
+        /// The number of bytes in the storage
+        actual: usize,
+    },
+    /// The length of the dynamically sized last field doesn't fit into a field declared with a `#[tail_len]` attribute.
+    /// Returned by the `push_tail` function generated by [binary_layout!](crate::binary_layout!).
+    TailLenOutOfRange {
+        /// The offset of the `#[tail_len]` field in the layout
+        offset: usize,
+        /// The length that didn't fit into the field
+        len: usize,
+    },
+    /// A field declared with a `#[magic(...)]` attribute doesn't hold its magic number.
+    /// Returned by the `check_magic` function generated by [binary_layout!](crate::binary_layout!).
+    BadMagic {
+        /// The offset of the field in the layout
+        offset: usize,
+    },
+}
+
+impl core::fmt::Display for LayoutError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::StorageTooShort { expected, actual } => write!(
+                fmt,
+                "LayoutError: storage has {} bytes but the layout needs {} bytes",
+                actual, expected
+            ),
+            Self::StorageTooLong { expected, actual } => write!(
+                fmt,
+                "LayoutError: storage has {} bytes but the layout only uses {} bytes",
+                actual, expected
+            ),
+            Self::FieldOutOfBounds {
+                offset,
+                expected,
+                actual,
+            } => write!(
+                fmt,
+                "LayoutError: storage has {} bytes but the field at offset {} needs {} bytes",
+                actual, offset, expected
+            ),
+            Self::InvalidUtf8 { offset } => write!(
+                fmt,
+                "LayoutError: the field at offset {} doesn't contain valid UTF-8",
+                offset
+            ),
+            Self::UnsupportedWidth { width } => write!(
+                fmt,
+                "LayoutError: can't read a value with a width of {} bytes, supported widths are 1 to 16 bytes",
+                width
+            ),
+            Self::ReservedNotZero { offset } => write!(
+                fmt,
+                "LayoutError: the reserved byte at offset {} isn't zero",
+                offset
+            ),
+            Self::RegionOutOfBounds {
+                offset,
+                len,
+                actual,
+            } => write!(
+                fmt,
+                "LayoutError: storage has {} bytes but the region at offset {} with length {} doesn't fit into it",
+                actual, offset, len
+            ),
+            Self::TailLenOutOfRange { offset, len } => write!(
+                fmt,
+                "LayoutError: a tail length of {} bytes doesn't fit into the length field at offset {}",
+                len, offset
+            ),
+            Self::BadMagic { offset } => write!(
+                fmt,
+                "LayoutError: the field at offset {} doesn't hold its magic number",
+                offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {}
+
+/// Internal function, don't use!
+/// Checks that the storage is long enough for a layout with the given minimum size and byte length function.
+/// `byte_len` is only called once the storage holds the statically sized fields it may read.
+#[inline]
+pub fn check_storage_len(
+    storage: &[u8],
+    min_size: usize,
+    byte_len: fn(&[u8]) -> usize,
+) -> Result<(), LayoutError> {
+    let actual = storage.len();
+    if actual < min_size {
+        return Err(LayoutError::StorageTooShort {
+            expected: min_size,
+            actual,
+        });
+    }
+    let expected = byte_len(storage);
+    if actual < expected {
+        return Err(LayoutError::StorageTooShort { expected, actual });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                LayoutError::StorageTooShort {
+                    expected: 10,
+                    actual: 5,
+                },
+                "LayoutError: storage has 5 bytes but the layout needs 10 bytes",
+            ),
+            (
+                LayoutError::StorageTooLong {
+                    expected: 10,
+                    actual: 12,
+                },
+                "LayoutError: storage has 12 bytes but the layout only uses 10 bytes",
+            ),
+            (
+                LayoutError::FieldOutOfBounds {
+                    offset: 3,
+                    expected: 7,
+                    actual: 5,
+                },
+                "LayoutError: storage has 5 bytes but the field at offset 3 needs 7 bytes",
+            ),
+            (
+                LayoutError::InvalidUtf8 { offset: 4 },
+                "LayoutError: the field at offset 4 doesn't contain valid UTF-8",
+            ),
+            (
+                LayoutError::UnsupportedWidth { width: 17 },
+                "LayoutError: can't read a value with a width of 17 bytes, supported widths are 1 to 16 bytes",
+            ),
+            (
+                LayoutError::ReservedNotZero { offset: 6 },
+                "LayoutError: the reserved byte at offset 6 isn't zero",
+            ),
+            (
+                LayoutError::RegionOutOfBounds {
+                    offset: 8,
+                    len: 4,
+                    actual: 10,
+                },
+                "LayoutError: storage has 10 bytes but the region at offset 8 with length 4 doesn't fit into it",
+            ),
+            (
+                LayoutError::TailLenOutOfRange {
+                    offset: 1,
+                    len: 256,
+                },
+                "LayoutError: a tail length of 256 bytes doesn't fit into the length field at offset 1",
+            ),
+            (
+                LayoutError::BadMagic { offset: 2 },
+                "LayoutError: the field at offset 2 doesn't hold its magic number",
+            ),
+        ];
+        for (error, expected) in cases {
+            let mut buffer = [0u8; 128];
+            assert_eq!(expected, to_str(error, &mut buffer));
+        }
+    }
+
+    fn to_str(error: LayoutError, buffer: &mut [u8]) -> &str {
+        let mut writer = Writer(buffer, 0);
+        core::fmt::write(&mut writer, format_args!("{}", error)).unwrap();
+        let len = writer.1;
+        core::str::from_utf8(&buffer[..len]).unwrap()
+    }
+
+    // Allows testing Display without alloc
+    struct Writer<'a>(&'a mut [u8], usize);
+    impl core::fmt::Write for Writer<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.1 + s.len();
+            self.0
+                .get_mut(self.1..end)
+                .ok_or(core::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.1 = end;
+            Ok(())
+        }
+    }
+}
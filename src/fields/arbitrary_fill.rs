@@ -0,0 +1,67 @@
+use arbitrary::{Arbitrary, Unstructured};
+
+use super::primitive::FieldCopyAccess;
+use super::probe::FieldProbe;
+
+// Used by the [Arbitrary] implementation generated by [binary_layout!](crate::binary_layout!) to overwrite a field
+// with an arbitrary value of its type if that type implements [Arbitrary], so that e.g. `NonZeroU32` fields or custom
+// types declared with `as` hold valid values. Other fields keep the arbitrary bytes they were filled with, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait ArbitraryCopyField {
+    /// Internal function, don't use!
+    fn fill_arbitrary(&self, storage: &mut [u8], u: &mut Unstructured<'_>)
+        -> arbitrary::Result<()>;
+}
+
+impl<F: FieldCopyAccess> ArbitraryCopyField for &FieldProbe<F>
+where
+    F::HighLevelType: for<'a> Arbitrary<'a>,
+{
+    #[inline]
+    fn fill_arbitrary(
+        &self,
+        storage: &mut [u8],
+        u: &mut Unstructured<'_>,
+    ) -> arbitrary::Result<()> {
+        // Values the field can't store, e.g. i32::MIN for a sign-magnitude field, leave the arbitrary bytes in place
+        let _ = F::try_write(storage, u.arbitrary()?);
+        Ok(())
+    }
+}
+
+probe_fallback!(ArbitraryOtherField {
+    fn fill_arbitrary(&self, storage: &mut [u8], u: &mut Unstructured<'_>) -> arbitrary::Result<()> {
+        Ok(())
+    }
+});
+
+/// Internal function, don't use!
+/// Creates the storage for an arbitrary message of a layout. The statically sized part of the layout is filled with
+/// arbitrary bytes, padded with zeros if `u` runs out of data, before `fill_fields` overwrites the fields that can hold
+/// arbitrary values. Layouts with a dynamic size get an arbitrary number of additional bytes, and the storage is then
+/// resized to the length the layout uses. If a computed length needs more bytes than `u` has left, this fails instead of
+/// allocating them.
+pub fn arbitrary_storage(
+    u: &mut Unstructured<'_>,
+    min_size: usize,
+    dynamic_size: bool,
+    byte_len: fn(&[u8]) -> usize,
+    fill_fields: impl FnOnce(&mut [u8], &mut Unstructured<'_>) -> arbitrary::Result<()>,
+) -> arbitrary::Result<Vec<u8>> {
+    let mut storage = vec![0; min_size];
+    u.fill_buffer(&mut storage)?;
+    fill_fields(&mut storage, u)?;
+    if dynamic_size {
+        let tail_len = u.arbitrary_len::<u8>()?;
+        storage.extend_from_slice(u.bytes(tail_len)?);
+        let len = byte_len(&storage);
+        if len > storage.len() {
+            let missing = len - storage.len();
+            storage.extend_from_slice(u.bytes(missing)?);
+        } else {
+            storage.truncate(len);
+        }
+    }
+    Ok(storage)
+}
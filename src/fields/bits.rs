@@ -0,0 +1,412 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use super::int_encoding::IntegerOutOfRangeError;
+use super::primitive::{FieldCopyAccess, FieldView, PrimitiveField};
+use super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::{EndianKind, Endianness};
+
+/// The order in which the bits of a byte are numbered for a [Bits] field, either [Lsb0] or [Msb0].
+pub trait BitOrder {
+    /// Whether bit 0 is the most significant bit of a byte
+    const MSB_FIRST: bool;
+}
+
+/// Bit order marker for [Bits] fields: Bit 0 is the least significant bit of a byte, bit 7 the most significant one.
+/// This is how CAN databases (DBC files) number the bits of a frame.
+pub struct Lsb0 {}
+impl BitOrder for Lsb0 {
+    const MSB_FIRST: bool = false;
+}
+
+/// Bit order marker for [Bits] fields: Bit 0 is the most significant bit of a byte, bit 7 the least significant one.
+/// This is how the diagrams in RFCs number the bits of a header.
+pub struct Msb0 {}
+impl BitOrder for Msb0 {
+    const MSB_FIRST: bool = true;
+}
+
+/// Field type `Bits<O, BYTES, START, LEN>`:
+/// This field type stores an unsigned integer with `LEN` bits (1 to 64 bits) somewhere within `BYTES` bytes (1 to 8 bytes),
+/// e.g. a signal in a CAN frame. The field takes up all `BYTES` bytes, so several [Bits] fields sharing the same bytes are
+/// declared in a `union { ... }` block, see [binary_layout!](crate::binary_layout!). Reading and writing a [Bits] field
+/// leaves the other bits of its bytes unchanged. Values are read as [u64] and writing a value that doesn't fit into
+/// `LEN` bits throws an [IntegerOutOfRangeError], so you need to use [FieldCopyAccess::try_write] to write these fields.
+///
+/// Bit order and byte order are independent of each other:
+/// - The bit order `O` defines how bit numbers are counted within a byte. The bit number `n` is bit `n % 8` of byte `n / 8`,
+///   counted from the least significant bit for [Lsb0] or from the most significant bit for [Msb0].
+/// - The endianness of the layout defines in which order the bits of the value are stored and which bit of the value `START` is.
+///   - [BigEndian](crate::BigEndian): `START` is the most significant bit of the value. The following bits are the next less
+///     significant bits of the same byte and then continue with the most significant bit of the next byte, like in a
+///     big endian integer. In CAN databases, this is the "Motorola" byte order.
+///   - [LittleEndian](crate::LittleEndian): `START` is the least significant bit of the value. The following bits are the next more
+///     significant bits of the same byte and then continue with the least significant bit of the next byte, like in a
+///     little endian integer. In CAN databases, this is the "Intel" byte order.
+///   - [NativeEndian](crate::NativeEndian): Either of the above, depending on the endianness of the system.
+///
+/// Fields whose bits don't fit into `BYTES` bytes fail to compile.
+///
+/// # Example
+/// This decodes signals from a CAN frame described by the following DBC signals, which use the "Motorola" byte order (`@0`)
+/// and number bits LSB first:
+/// ```text
+/// SG_ EngineSpeed : 7|16@0+ (0.25,0) [0|16383.75] "rpm" ECU
+/// SG_ Throttle : 21|10@0+ (0.1,0) [0|102.3] "%" ECU
+/// SG_ Gear : 27|4@0+ (1,0) [0|15] "" ECU
+/// ```
+/// `Throttle` starts at bit 5 of byte 2, which is its most significant bit, and takes up the
+/// lower 6 bits of byte 2 followed by the upper 4 bits of byte 3.
+/// ```
+/// use binary_layout::{prelude::*, Bits, Lsb0};
+///
+/// binary_layout!(engine_status, BigEndian, {
+///   union {
+///     engine_speed: Bits<Lsb0, 4, 7, 16>,
+///     throttle: Bits<Lsb0, 4, 21, 10>,
+///     gear: Bits<Lsb0, 4, 27, 4>,
+///   },
+///   checksum: u8,
+/// });
+///
+/// # fn main() {
+/// //            engine_speed  throttle: 0b10_1101_0011  gear: 0b0101
+/// let storage = [0x1F, 0x40,  0b0010_1101, 0b0011_0101, 0xAA];
+/// assert_eq!(2000.0, engine_status::engine_speed::read(&storage) as f64 * 0.25);
+/// assert_eq!(72.3, engine_status::throttle::read(&storage) as f64 / 10.0);
+/// assert_eq!(5, engine_status::gear::read(&storage));
+///
+/// let mut storage = storage;
+/// engine_status::gear::try_write(&mut storage, 6).unwrap();
+/// assert_eq!([0x1F, 0x40, 0b0010_1101, 0b0011_0110, 0xAA], storage);
+/// assert!(engine_status::gear::try_write(&mut storage, 16).is_err());
+/// # }
+/// ```
+pub struct Bits<O: BitOrder, const BYTES: usize, const START: usize, const LEN: usize> {
+    _p: PhantomData<O>,
+}
+
+impl<
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    // The position of the least significant bit of the value in the bytes of the field,
+    // loaded into a u64 with the endianness of the layout.
+    const SHIFT: usize = {
+        assert!(
+            BYTES >= 1 && BYTES <= 8,
+            "Error: Bits only supports fields with 1 to 8 bytes"
+        );
+        assert!(
+            LEN >= 1 && LEN <= 64,
+            "Error: Bits only supports values with 1 to 64 bits"
+        );
+        assert!(
+            START < BYTES * 8,
+            "Error: The start bit of a Bits field must be within its bytes"
+        );
+        let byte = START / 8;
+        let bit_from_lsb = if O::MSB_FIRST {
+            7 - START % 8
+        } else {
+            START % 8
+        };
+        if Self::BIG_ENDIAN {
+            // Count bits from the most significant bit of the first byte, i.e. in the order they're stored in
+            let start_from_msb = byte * 8 + 7 - bit_from_lsb;
+            assert!(
+                start_from_msb + LEN <= BYTES * 8,
+                "Error: The bits of a Bits field must be within its bytes"
+            );
+            BYTES * 8 - start_from_msb - LEN
+        } else {
+            let start_from_lsb = byte * 8 + bit_from_lsb;
+            assert!(
+                start_from_lsb + LEN <= BYTES * 8,
+                "Error: The bits of a Bits field must be within its bytes"
+            );
+            start_from_lsb
+        }
+    };
+
+    const MASK: u64 = if LEN >= 64 { u64::MAX } else { (1 << LEN) - 1 };
+
+    const BIG_ENDIAN: bool = match E::KIND {
+        EndianKind::Big => true,
+        EndianKind::Little => false,
+        EndianKind::Native => cfg!(target_endian = "big"),
+    };
+
+    #[inline(always)]
+    fn load(storage: &[u8]) -> u64 {
+        let data = &storage[OFFSET_..(OFFSET_ + BYTES)];
+        let mut bytes = [0; 8];
+        if Self::BIG_ENDIAN {
+            bytes[(8 - BYTES)..].copy_from_slice(data);
+            u64::from_be_bytes(bytes)
+        } else {
+            bytes[..BYTES].copy_from_slice(data);
+            u64::from_le_bytes(bytes)
+        }
+    }
+
+    #[inline(always)]
+    fn store(storage: &mut [u8], window: u64) {
+        let data = &mut storage[OFFSET_..(OFFSET_ + BYTES)];
+        if Self::BIG_ENDIAN {
+            data.copy_from_slice(&window.to_be_bytes()[(8 - BYTES)..]);
+        } else {
+            data.copy_from_slice(&window.to_le_bytes()[..BYTES]);
+        }
+    }
+}
+
+impl<
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > FieldCopyAccess for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = Infallible;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = IntegerOutOfRangeError;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = u64;
+
+    /// Read the bits of the field as an unsigned integer using the [Field] API.
+    /// See also [FieldCopyAccess::try_read].
+    #[inline]
+    fn try_read(storage: &[u8]) -> Result<u64, Infallible> {
+        Ok((Self::load(storage) >> Self::SHIFT) & Self::MASK)
+    }
+
+    /// Write an unsigned integer to the bits of the field using the [Field] API. The other bits of the bytes are left unchanged.
+    /// Throws an [IntegerOutOfRangeError] if the value doesn't fit into the bits.
+    /// See also [FieldCopyAccess::try_write].
+    #[inline]
+    fn try_write(storage: &mut [u8], value: u64) -> Result<(), IntegerOutOfRangeError> {
+        if value > Self::MASK {
+            return Err(IntegerOutOfRangeError(()));
+        }
+        let window = Self::load(storage);
+        let window = (window & !(Self::MASK << Self::SHIFT)) | (value << Self::SHIFT);
+        Self::store(storage, window);
+        Ok(())
+    }
+}
+
+impl<
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > Field for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = Some(BYTES);
+}
+
+impl<
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > FieldByteLen for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    #[inline(always)]
+    fn byte_len(_storage: &[u8]) -> usize {
+        BYTES
+    }
+}
+
+impl<
+        'a,
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > StorageToFieldView<&'a [u8]> for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        'a,
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > StorageToFieldView<&'a mut [u8]> for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        S: AsRef<[u8]>,
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > StorageIntoFieldView<S> for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(big_lsb0, BigEndian, {
+        header: u8,
+        union {
+            first: Bits<Lsb0, 3, 7, 4>,
+            crossing: Bits<Lsb0, 3, 1, 10>,
+            last: Bits<Lsb0, 3, 16, 1>,
+        },
+    });
+
+    binary_layout!(big_msb0, BigEndian, {
+        header: u8,
+        union {
+            first: Bits<Msb0, 3, 0, 4>,
+            crossing: Bits<Msb0, 3, 6, 10>,
+            last: Bits<Msb0, 3, 23, 1>,
+        },
+    });
+
+    binary_layout!(little_lsb0, LittleEndian, {
+        header: u8,
+        union {
+            first: Bits<Lsb0, 3, 0, 4>,
+            crossing: Bits<Lsb0, 3, 6, 10>,
+            last: Bits<Lsb0, 3, 23, 1>,
+        },
+    });
+
+    binary_layout!(little_msb0, LittleEndian, {
+        header: u8,
+        union {
+            first: Bits<Msb0, 3, 7, 4>,
+            crossing: Bits<Msb0, 3, 1, 10>,
+            last: Bits<Msb0, 3, 16, 1>,
+        },
+    });
+
+    binary_layout!(full, LittleEndian, {
+        value: Bits<Lsb0, 8, 0, 64>,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, big_lsb0::crossing::OFFSET);
+        assert_eq!(Some(3), big_lsb0::crossing::SIZE);
+        assert_eq!(Some(4), big_lsb0::SIZE);
+    }
+
+    #[test]
+    fn big_endian() {
+        // Both bit orders describe the same bits: the high nibble of byte 0, the low 2 bits of byte 0 followed by byte 1, and the lowest bit of byte 2
+        let mut storage = [0xFF, 0b1010_0011, 0b0101_1010, 0b0000_0001];
+        assert_eq!(0b1010, big_lsb0::first::read(&storage));
+        assert_eq!(0b11_0101_1010, big_lsb0::crossing::read(&storage));
+        assert_eq!(1, big_lsb0::last::read(&storage));
+        assert_eq!(0b1010, big_msb0::first::read(&storage));
+        assert_eq!(0b11_0101_1010, big_msb0::crossing::read(&storage));
+        assert_eq!(1, big_msb0::last::read(&storage));
+
+        big_lsb0::crossing::try_write(&mut storage, 0b01_1111_0000).unwrap();
+        assert_eq!([0xFF, 0b1010_0001, 0b1111_0000, 0b0000_0001], storage);
+        big_msb0::first::try_write(&mut storage, 0b0110).unwrap();
+        big_msb0::last::try_write(&mut storage, 0).unwrap();
+        assert_eq!([0xFF, 0b0110_0001, 0b1111_0000, 0b0000_0000], storage);
+    }
+
+    #[test]
+    fn little_endian() {
+        // Both bit orders describe the same bits: the low nibble of byte 0, the high 2 bits of byte 0 followed by byte 1, and the highest bit of byte 2
+        let mut storage = [0xFF, 0b1100_0101, 0b0101_1010, 0b1000_0000];
+        assert_eq!(0b0101, little_lsb0::first::read(&storage));
+        assert_eq!(0b01_0110_1011, little_lsb0::crossing::read(&storage));
+        assert_eq!(1, little_lsb0::last::read(&storage));
+        assert_eq!(0b0101, little_msb0::first::read(&storage));
+        assert_eq!(0b01_0110_1011, little_msb0::crossing::read(&storage));
+        assert_eq!(1, little_msb0::last::read(&storage));
+
+        little_lsb0::crossing::try_write(&mut storage, 0b11_1100_0010).unwrap();
+        assert_eq!([0xFF, 0b1000_0101, 0b1111_0000, 0b1000_0000], storage);
+        little_msb0::first::try_write(&mut storage, 0b1010).unwrap();
+        little_msb0::last::try_write(&mut storage, 0).unwrap();
+        assert_eq!([0xFF, 0b1000_1010, 0b1111_0000, 0b0000_0000], storage);
+    }
+
+    #[test]
+    fn full_width() {
+        let mut storage = [0; 8];
+        full::value::try_write(&mut storage, u64::MAX - 1).unwrap();
+        assert_eq!([0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], storage);
+        assert_eq!(u64::MAX - 1, full::value::read(&storage));
+    }
+
+    #[test]
+    fn value_out_of_range() {
+        let mut storage = [0; 4];
+        assert_eq!(
+            Err(IntegerOutOfRangeError(())),
+            big_lsb0::first::try_write(&mut storage, 16)
+        );
+        assert_eq!(
+            Err(IntegerOutOfRangeError(())),
+            little_msb0::last::try_write(&mut storage, 2)
+        );
+        assert_eq!([0; 4], storage);
+    }
+
+    #[test]
+    fn view_api() {
+        let mut storage = [0; 4];
+        let mut view = big_msb0::View::new(&mut storage);
+        view.crossing_mut().try_write(0x3FF).unwrap();
+        assert_eq!(0x3FF, view.crossing().read());
+        assert_eq!(0, view.first().read());
+        assert_eq!([0, 0b11, 0xFF, 0], storage);
+    }
+}
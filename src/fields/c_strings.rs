@@ -0,0 +1,170 @@
+use core::slice::ChunksExact;
+use core::str::Utf8Error;
+
+use super::{Field, FieldByteLen};
+
+/// This extension trait allows reading a field that holds several fixed width, nul-padded strings back to back,
+/// e.g. the names in a symbol table or the entries of a directory structure.
+///
+/// [FieldCStrings::strings] splits the bytes of the field into chunks of `width` bytes and trims each chunk at its first
+/// zero byte. Chunks without a zero byte are returned as a whole, i.e. names that use the full width don't need a terminator.
+/// [FieldCStrings::strs] does the same and additionally checks that each string is valid UTF-8. If the length of the field
+/// isn't a multiple of `width`, the remaining bytes at the end don't form a string and are skipped.
+/// Like [FieldToVec](crate::FieldToVec), this works for any field and uses the bytes the field currently takes up,
+/// e.g. the rest of the storage for an open ended byte array.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldCStrings};
+///
+/// binary_layout!(directory, LittleEndian, {
+///   count: u8,
+///   names: [u8; 12],
+/// });
+///
+/// # fn main() {
+/// let storage = *b"\x03init\0\0\0\0boot";
+/// let names: Vec<&[u8]> = directory::names::strings(&storage, 4).collect();
+/// assert_eq!(vec![&b"init"[..], &b""[..], &b"boot"[..]], names);
+///
+/// let names: Result<Vec<&str>, _> = directory::names::strs(&storage, 4).collect();
+/// assert_eq!(vec!["init", "", "boot"], names.unwrap());
+/// # }
+/// ```
+pub trait FieldCStrings: Field {
+    /// Split the field into strings of `width` bytes and trim each string at its first zero byte.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    #[inline]
+    fn strings(storage: &[u8], width: usize) -> CStrings<'_>
+    where
+        Self: FieldByteLen,
+    {
+        assert!(width > 0, "Error: The string width must not be zero");
+        let len = <Self as FieldByteLen>::byte_len(storage);
+        CStrings {
+            chunks: storage[Self::OFFSET..(Self::OFFSET + len)].chunks_exact(width),
+        }
+    }
+
+    /// Split the field into strings of `width` bytes like [FieldCStrings::strings] and check that each string is valid UTF-8.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    #[inline]
+    fn strs(storage: &[u8], width: usize) -> CStrs<'_>
+    where
+        Self: FieldByteLen,
+    {
+        CStrs {
+            strings: Self::strings(storage, width),
+        }
+    }
+}
+
+impl<F: Field> FieldCStrings for F {}
+
+/// An iterator over the nul-padded strings of a field, see [FieldCStrings::strings].
+#[derive(Debug, Clone)]
+pub struct CStrings<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for CStrings<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let chunk = self.chunks.next()?;
+        let len = chunk
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(chunk.len());
+        Some(&chunk[..len])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl ExactSizeIterator for CStrings<'_> {}
+
+/// An iterator over the nul-padded UTF-8 strings of a field, see [FieldCStrings::strs].
+#[derive(Debug, Clone)]
+pub struct CStrs<'a> {
+    strings: CStrings<'a>,
+}
+
+impl<'a> Iterator for CStrs<'a> {
+    type Item = Result<&'a str, Utf8Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.strings.next().map(core::str::from_utf8)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.strings.size_hint()
+    }
+}
+
+impl ExactSizeIterator for CStrs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(table, LittleEndian, {
+        count: u16,
+        names: [u8; 10],
+        tail: [u8],
+    });
+
+    #[test]
+    fn strings() {
+        let storage = *b"\x02\0ab\0cd\0\0\0xyzq";
+        let mut strings = table::names::strings(&storage, 3);
+        assert_eq!(3, strings.len());
+        assert_eq!(Some(&b"ab"[..]), strings.next());
+        assert_eq!(Some(&b"cd"[..]), strings.next());
+        // Bytes after the first zero byte are ignored
+        assert_eq!(Some(&b""[..]), strings.next());
+        // The last byte of the field doesn't form a whole string and is skipped
+        assert_eq!(None, strings.next());
+    }
+
+    #[test]
+    fn full_width_and_open_ended() {
+        let storage = *b"\0\0abcdefghijxyzq";
+        let strings = table::names::strings(&storage, 5);
+        assert!(strings.eq([&b"abcde"[..], &b"fghij"[..]]));
+        let strings = table::tail::strings(&storage, 2);
+        assert!(strings.eq([&b"xy"[..], &b"zq"[..]]));
+        let strings = table::count::strings(&storage, 1);
+        assert!(strings.eq([&b""[..], &b""[..]]));
+    }
+
+    #[test]
+    fn strs() {
+        let mut storage = *b"\0\0ab\0\0\0\xFF\0\0\0\0";
+        let mut strs = table::names::strs(&storage, 5);
+        assert_eq!(2, strs.len());
+        assert_eq!(Some(Ok("ab")), strs.next());
+        assert!(strs.next().unwrap().is_err());
+        assert_eq!(None, strs.next());
+
+        storage[7] = b'x';
+        assert!(table::names::strs(&storage, 5).eq([Ok("ab"), Ok("x")]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: The string width must not be zero")]
+    fn zero_width() {
+        table::names::strings(&[0; 12], 0);
+    }
+}
@@ -0,0 +1,258 @@
+use super::{
+    primitive::{FieldReadExt, FieldSliceAccess, FieldWriteExt},
+    FieldByteLen,
+};
+use crate::LayoutError;
+
+/// This extension trait adds accessors to the [Field](crate::Field) API that check that the storage is long enough
+/// for the field and return a [LayoutError] instead of panicking if it isn't.
+///
+/// The regular accessors like [FieldReadExt::read] or [FieldSliceAccess::data] panic if the storage is too short,
+/// just like indexing into a slice would. The checked accessors check the bounds first and return an error instead of
+/// reaching that panic. They don't make the code provably panic-free though: after the check, they call the regular accessors,
+/// so the indexing and `unwrap` panic paths of those remain in the compiled code, even if they can't be reached for a too short storage.
+/// The length closures of [byte arrays with computed length](crate#byte-arrays-with-computed-length-u8-expr), which the bounds check
+/// calls, and the conversions of custom field types are user code that can panic as well.
+/// See [Panics](crate#panics) for an overview of which functions can panic.
+///
+/// For fields whose [try_read](crate::FieldCopyAccess::try_read) or [try_write](crate::FieldCopyAccess::try_write)
+/// can throw errors, call [FieldCheckedAccess::check_bounds] before calling them.
+///
+/// # Example:
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   field_one: u16,
+///   another_field: [u8; 16],
+/// });
+///
+/// fn func(storage_data: &mut [u8]) -> Result<(), LayoutError> {
+///   let value: u16 = my_layout::field_one::checked_read(storage_data)?;
+///   my_layout::field_one::checked_write(storage_data, value + 1)?;
+///   let data: &[u8; 16] = my_layout::another_field::checked_data(storage_data)?;
+///   Ok(())
+/// }
+///
+/// # fn main() {
+/// assert!(func(&mut [0; 18]).is_ok());
+/// assert_eq!(
+///   Err(LayoutError::FieldOutOfBounds { offset: 2, expected: 18, actual: 10 }),
+///   func(&mut [0; 10]),
+/// );
+/// # }
+/// ```
+pub trait FieldCheckedAccess: FieldByteLen {
+    /// Check that the storage is long enough to hold the field.
+    /// For fields with a dynamic size, this checks the current size of the field, see [FieldByteLen::byte_len].
+    #[inline(always)]
+    fn check_bounds(storage: &[u8]) -> Result<(), LayoutError> {
+        let actual = storage.len();
+        let out_of_bounds = |expected| LayoutError::FieldOutOfBounds {
+            offset: Self::OFFSET,
+            expected,
+            actual,
+        };
+        // Computing the size of a dynamically sized field can read from the storage, so we have to check the minimum size first
+        let min_end = Self::OFFSET.saturating_add(Self::MIN_LEN);
+        if actual < min_end {
+            return Err(out_of_bounds(min_end));
+        }
+        let end = Self::OFFSET.saturating_add(Self::byte_len(storage));
+        if actual < end {
+            return Err(out_of_bounds(end));
+        }
+        Ok(())
+    }
+
+    /// Read the field like [FieldReadExt::read], but return a [LayoutError] if the storage is too short.
+    #[inline(always)]
+    fn checked_read(storage: &[u8]) -> Result<<Self as FieldReadExt>::HighLevelType, LayoutError>
+    where
+        Self: FieldReadExt,
+    {
+        Self::check_bounds(storage)?;
+        Ok(Self::read(storage))
+    }
+
+    /// Read the field if the storage is long enough for it and return `None` otherwise, no matter whether the storage
+    /// is long enough for the rest of the layout. This is for reading an early field, e.g. a message type, from a
+    /// partially received frame to decide how to handle it before the rest of the frame arrives.
+    ///
+    /// This is the same as [FieldCheckedAccess::checked_read], but returns an [Option] because a storage that is too short
+    /// is an expected case and not an error when peeking.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(frame, BigEndian, {
+    ///   kind: u8,
+    ///   len: u16,
+    ///   payload: [u8; 32],
+    /// });
+    ///
+    /// # fn main() {
+    /// let received = [7, 0];
+    /// assert_eq!(Some(7), frame::kind::peek(&received));
+    /// assert_eq!(None, frame::len::peek(&received));
+    /// # }
+    /// ```
+    #[inline(always)]
+    fn peek(storage: &[u8]) -> Option<<Self as FieldReadExt>::HighLevelType>
+    where
+        Self: FieldReadExt,
+    {
+        Self::checked_read(storage).ok()
+    }
+
+    /// Write the field like [FieldWriteExt::write], but return a [LayoutError] if the storage is too short.
+    #[inline(always)]
+    fn checked_write(
+        storage: &mut [u8],
+        value: <Self as FieldWriteExt>::HighLevelType,
+    ) -> Result<(), LayoutError>
+    where
+        Self: FieldWriteExt,
+    {
+        Self::check_bounds(storage)?;
+        Self::write(storage, value);
+        Ok(())
+    }
+
+    /// Borrow the field like [FieldSliceAccess::data], but return a [LayoutError] if the storage is too short.
+    #[inline(always)]
+    fn checked_data<'a>(
+        storage: &'a [u8],
+    ) -> Result<<Self as FieldSliceAccess<'a>>::SliceType, LayoutError>
+    where
+        Self: FieldSliceAccess<'a>,
+    {
+        Self::check_bounds(storage)?;
+        Ok(Self::data(storage))
+    }
+
+    /// Borrow the field like [FieldSliceAccess::data_mut], but return a [LayoutError] if the storage is too short.
+    #[inline(always)]
+    fn checked_data_mut<'a>(
+        storage: &'a mut [u8],
+    ) -> Result<<Self as FieldSliceAccess<'a>>::MutSliceType, LayoutError>
+    where
+        Self: FieldSliceAccess<'a>,
+    {
+        Self::check_bounds(storage)?;
+        Ok(Self::data_mut(storage))
+    }
+}
+
+impl<F: FieldByteLen> FieldCheckedAccess for F {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::PrimitiveField;
+
+    #[test]
+    fn copy_field() {
+        type Field1 = PrimitiveField<u32, LittleEndian, 5>;
+        let mut storage = [0; 9];
+        Field1::checked_write(&mut storage, 1000).unwrap();
+        assert_eq!(Ok(1000), Field1::checked_read(&storage));
+
+        let expected_error = LayoutError::FieldOutOfBounds {
+            offset: 5,
+            expected: 9,
+            actual: 8,
+        };
+        assert_eq!(Err(expected_error), Field1::checked_read(&storage[..8]));
+        assert_eq!(
+            Err(expected_error),
+            Field1::checked_write(&mut storage[..8], 0)
+        );
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 5,
+                expected: 9,
+                actual: 2,
+            }),
+            Field1::check_bounds(&storage[..2])
+        );
+    }
+
+    #[test]
+    fn peek() {
+        binary_layout!(layout, LittleEndian, {
+            kind: u8,
+            len: u16,
+            payload: [u8; 16],
+        });
+        let storage = [3, 1, 2];
+        assert_eq!(Some(3), layout::kind::peek(&storage));
+        assert_eq!(Some(0x0201), layout::len::peek(&storage));
+        assert_eq!(None, layout::len::peek(&storage[..2]));
+        assert_eq!(None, layout::kind::peek(&[]));
+    }
+
+    #[test]
+    fn fixed_size_slice_field() {
+        type Field1 = PrimitiveField<[u8; 3], LittleEndian, 2>;
+        let mut storage = [0; 5];
+        Field1::checked_data_mut(&mut storage)
+            .unwrap()
+            .copy_from_slice(&[1, 2, 3]);
+        assert_eq!(Ok(&[1, 2, 3]), Field1::checked_data(&storage));
+
+        let expected_error = LayoutError::FieldOutOfBounds {
+            offset: 2,
+            expected: 5,
+            actual: 4,
+        };
+        assert_eq!(Err(expected_error), Field1::checked_data(&storage[..4]));
+        assert_eq!(
+            Some(expected_error),
+            Field1::checked_data_mut(&mut storage[..4]).err()
+        );
+    }
+
+    #[test]
+    fn open_ended_slice_field() {
+        type Field1 = PrimitiveField<[u8], LittleEndian, 2>;
+        let storage = [0; 5];
+        assert_eq!(Ok(&[0u8; 3][..]), Field1::checked_data(&storage));
+        assert_eq!(Ok(&[][..]), Field1::checked_data(&storage[..2]));
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 2,
+                expected: 2,
+                actual: 1,
+            }),
+            Field1::checked_data(&storage[..1])
+        );
+    }
+
+    #[test]
+    fn computed_length_field() {
+        binary_layout!(layout, LittleEndian, {
+            len: u8,
+            data: [u8; expr(|v| v.len().read() as usize)],
+        });
+        let storage = [3, 1, 2, 3];
+        assert_eq!(Ok(&[1, 2, 3][..]), layout::data::checked_data(&storage));
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 1,
+                expected: 4,
+                actual: 3,
+            }),
+            layout::data::checked_data(&storage[..3])
+        );
+        assert_eq!(
+            Err(LayoutError::FieldOutOfBounds {
+                offset: 1,
+                expected: 1,
+                actual: 0,
+            }),
+            layout::data::checked_data(&storage[..0])
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use core::marker::PhantomData;
+
+use super::{
+    primitive::{FieldCopyAccess, FieldView},
+    wrapped::WrappedFieldError,
+    Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView,
+};
+
+/// Implementing the [LayoutAsComposite] trait for a custom type allows that custom type to be used as the type of
+/// a layout field that spans two adjacent primitive values, e.g. a version number stored as a [u32] major version
+/// followed by a [u16] minor version. Like for [LayoutAs](crate::LayoutAs), the value is copied each time it is accessed.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutAsComposite};
+/// use core::convert::Infallible;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Version {
+///   major: u32,
+///   minor: u16,
+/// }
+///
+/// impl LayoutAsComposite<u32, u16> for Version {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///
+///   fn try_read(major: u32, minor: u16) -> Result<Version, Infallible> {
+///     Ok(Version { major, minor })
+///   }
+///
+///   fn try_write(v: Version) -> Result<(u32, u16), Infallible> {
+///     Ok((v.major, v.minor))
+///   }
+/// }
+///
+/// binary_layout!(header, BigEndian, {
+///   magic: u16,
+///   version: Version as (u32, u16),
+///   flags: u8,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 9];
+/// header::version::write(&mut storage, Version { major: 3, minor: 1 });
+/// assert_eq!([0, 0, 0, 0, 0, 3, 0, 1, 0], storage);
+/// assert_eq!(8, header::flags::OFFSET);
+/// assert_eq!(Version { major: 3, minor: 1 }, header::View::new(&storage).version().read());
+/// # }
+/// ```
+pub trait LayoutAsComposite<A, B>: Sized {
+    /// See [FieldCopyAccess::ReadError].
+    ///
+    /// If reading cannot fail, set this to [core::convert::Infallible] because that will make
+    /// [FieldReadExt::read](crate::FieldReadExt::read) available for the type.
+    type ReadError;
+    /// See [FieldCopyAccess::WriteError].
+    ///
+    /// If writing cannot fail, set this to [core::convert::Infallible] because that will make
+    /// [FieldWriteExt::write](crate::FieldWriteExt::write) available for the type.
+    type WriteError;
+
+    /// Implement this to define how the custom type is constructed from the two underlying values
+    /// after they were read from a layouted binary slice.
+    fn try_read(a: A, b: B) -> Result<Self, Self::ReadError>;
+
+    /// Implement this to define how the custom type is converted into the two underlying values
+    /// so they can be written into a layouted binary slice.
+    fn try_write(v: Self) -> Result<(A, B), Self::WriteError>;
+}
+
+/// A [CompositeField] is a [Field] that spans two adjacent fields storing the values `A` and `B`
+/// and represents a custom type converted from/to them using the [LayoutAsComposite] trait.
+/// See [Field] for more info on this API.
+///
+/// The `field: MyType as (A, B)` syntax of [binary_layout!](crate::binary_layout!) generates a
+/// `CompositeField<A, B, MyType, PrimitiveField<A, E, OFFSET>, PrimitiveField<B, E, OFFSET + size_of::<A>()>>`.
+/// Both underlying fields need to have the same error types, which is the case for all primitive integer and float types.
+/// See [LayoutAsComposite] for an example.
+pub struct CompositeField<A, B, T: LayoutAsComposite<A, B>, F1: Field, F2: Field> {
+    _p1: PhantomData<A>,
+    _p2: PhantomData<B>,
+    _p3: PhantomData<T>,
+    _p4: PhantomData<F1>,
+    _p5: PhantomData<F2>,
+}
+
+impl<A, B, T: LayoutAsComposite<A, B>, F1: Field, F2: Field> Field
+    for CompositeField<A, B, T, F1, F2>
+{
+    /// See [Field::Endian]
+    type Endian = F1::Endian;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = F1::OFFSET;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = match (F1::SIZE, F2::SIZE) {
+        (Some(first), Some(second)) => Some(first + second),
+        _ => None,
+    };
+}
+
+impl<A, B, T: LayoutAsComposite<A, B>, F1: FieldByteLen, F2: FieldByteLen> FieldByteLen
+    for CompositeField<A, B, T, F1, F2>
+{
+    const MIN_LEN: usize = F1::MIN_LEN + F2::MIN_LEN;
+
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        F1::byte_len(storage) + F2::byte_len(storage)
+    }
+}
+
+impl<
+        'a,
+        A,
+        B,
+        T: LayoutAsComposite<A, B>,
+        F1: FieldCopyAccess<HighLevelType = A>,
+        F2: FieldCopyAccess<HighLevelType = B>,
+    > StorageToFieldView<&'a [u8]> for CompositeField<A, B, T, F1, F2>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        'a,
+        A,
+        B,
+        T: LayoutAsComposite<A, B>,
+        F1: FieldCopyAccess<HighLevelType = A>,
+        F2: FieldCopyAccess<HighLevelType = B>,
+    > StorageToFieldView<&'a mut [u8]> for CompositeField<A, B, T, F1, F2>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        A,
+        B,
+        S: AsRef<[u8]>,
+        T: LayoutAsComposite<A, B>,
+        F1: FieldCopyAccess<HighLevelType = A>,
+        F2: FieldCopyAccess<HighLevelType = B>,
+    > StorageIntoFieldView<S> for CompositeField<A, B, T, F1, F2>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<
+        A,
+        B,
+        T: LayoutAsComposite<A, B>,
+        F1: FieldCopyAccess<HighLevelType = A>,
+        F2: FieldCopyAccess<HighLevelType = B, ReadError = F1::ReadError, WriteError = F1::WriteError>,
+    > FieldCopyAccess for CompositeField<A, B, T, F1, F2>
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = WrappedFieldError<F1::ReadError, T::ReadError>;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = WrappedFieldError<F1::WriteError, T::WriteError>;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = T;
+
+    /// Read both underlying fields from a given data region and convert them into the custom type, using the [Field] API.
+    ///
+    /// # Example:
+    /// See [LayoutAsComposite] for an example
+    #[inline(always)]
+    fn try_read(storage: &[u8]) -> Result<Self::HighLevelType, Self::ReadError> {
+        let a = F1::try_read(storage).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        let b = F2::try_read(storage).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        <T as LayoutAsComposite<A, B>>::try_read(a, b).map_err(WrappedFieldError::LayoutAsError)
+    }
+
+    /// Convert the custom type into the underlying values and write them to a given data region, using the [Field] API.
+    ///
+    /// # Example:
+    /// See [LayoutAsComposite] for an example
+    #[inline(always)]
+    fn try_write(storage: &mut [u8], v: Self::HighLevelType) -> Result<(), Self::WriteError> {
+        let (a, b) = <T as LayoutAsComposite<A, B>>::try_write(v)
+            .map_err(WrappedFieldError::LayoutAsError)?;
+        F1::try_write(storage, a).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        F2::try_write(storage, b).map_err(WrappedFieldError::PrimitiveAccessError)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use core::convert::Infallible;
+    use core::num::NonZeroU16;
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Point {
+        x: i16,
+        y: f32,
+    }
+
+    impl LayoutAsComposite<i16, f32> for Point {
+        type ReadError = Infallible;
+        type WriteError = Infallible;
+
+        fn try_read(x: i16, y: f32) -> Result<Self, Infallible> {
+            Ok(Point { x, y })
+        }
+
+        fn try_write(v: Self) -> Result<(i16, f32), Infallible> {
+            Ok((v.x, v.y))
+        }
+    }
+
+    // A range with the invariant start <= end, stored as two non-zero values
+    #[derive(Debug, PartialEq)]
+    pub struct Range {
+        start: NonZeroU16,
+        end: NonZeroU16,
+    }
+
+    impl LayoutAsComposite<NonZeroU16, NonZeroU16> for Range {
+        type ReadError = &'static str;
+        type WriteError = &'static str;
+
+        fn try_read(start: NonZeroU16, end: NonZeroU16) -> Result<Self, &'static str> {
+            if start <= end {
+                Ok(Range { start, end })
+            } else {
+                Err("start after end")
+            }
+        }
+
+        fn try_write(v: Self) -> Result<(NonZeroU16, NonZeroU16), &'static str> {
+            if v.start <= v.end {
+                Ok((v.start, v.end))
+            } else {
+                Err("start after end")
+            }
+        }
+    }
+
+    binary_layout!(layout, LittleEndian, {
+        header: u8,
+        point: Point as (i16, f32),
+        range: Range as (NonZeroU16, NonZeroU16),
+        tail: [u8],
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, layout::point::OFFSET);
+        assert_eq!(Some(6), layout::point::SIZE);
+        assert_eq!(7, layout::range::OFFSET);
+        assert_eq!(Some(4), layout::range::SIZE);
+        assert_eq!(11, layout::tail::OFFSET);
+    }
+
+    #[test]
+    fn infallible() {
+        let mut storage = [0; 11];
+        let point = Point { x: -2, y: 1.5 };
+        layout::point::write(&mut storage, point);
+        assert_eq!(&(-2i16).to_le_bytes(), &storage[1..3]);
+        assert_eq!(&1.5f32.to_le_bytes(), &storage[3..7]);
+        assert_eq!(point, layout::point::read(&storage));
+
+        let mut view = layout::View::new(&mut storage);
+        view.point_mut().write(Point { x: 7, y: -1.0 });
+        assert_eq!(Point { x: 7, y: -1.0 }, view.point().read());
+        assert_eq!(Point { x: 7, y: -1.0 }, view.into_point().read());
+    }
+
+    #[test]
+    fn fallible() {
+        let mut storage = [0; 11];
+        assert!(matches!(
+            layout::range::try_read(&storage),
+            Err(WrappedFieldError::PrimitiveAccessError(NonZeroIsZeroError(
+                _
+            )))
+        ));
+
+        let start = NonZeroU16::new(3).unwrap();
+        let end = NonZeroU16::new(5).unwrap();
+        layout::range::try_write(&mut storage, Range { start, end }).unwrap();
+        assert_eq!([3, 0, 5, 0], storage[7..]);
+        assert_eq!(
+            Range { start, end },
+            layout::range::try_read(&storage).unwrap()
+        );
+
+        assert!(matches!(
+            layout::range::try_write(
+                &mut storage,
+                Range {
+                    start: end,
+                    end: start
+                }
+            ),
+            Err(WrappedFieldError::LayoutAsError("start after end"))
+        ));
+        storage[7] = 6;
+        assert!(matches!(
+            layout::View::new(&storage).range().try_read(),
+            Err(WrappedFieldError::LayoutAsError("start after end"))
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use super::{primitive::FieldSliceAccess, Field};
+
+/// This extension trait adds a constant time comparison to byte array fields, e.g. fields holding MACs or authentication tags.
+///
+/// Comparing such fields with `==` returns early at the first byte that differs, and the time it takes can tell an attacker
+/// how many bytes of a forged tag were correct. [FieldConstantTimeEq::ct_eq] always looks at all bytes of the field instead.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldConstantTimeEq};
+///
+/// binary_layout!(message, BigEndian, {
+///   payload: [u8; 16],
+///   tag: [u8; 8],
+/// });
+///
+/// fn verify(storage: &[u8], expected_tag: &[u8; 8]) -> bool {
+///   message::tag::ct_eq(storage, expected_tag)
+/// }
+///
+/// # fn main() {
+/// let mut storage = [0; 24];
+/// storage[16..].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+/// assert!(verify(&storage, &[1, 2, 3, 4, 5, 6, 7, 8]));
+/// assert!(!verify(&storage, &[1, 2, 3, 4, 5, 6, 7, 9]));
+/// # }
+/// ```
+pub trait FieldConstantTimeEq: Field {
+    /// Compare the bytes of the field with `expected`, taking the same time no matter at which position the bytes differ.
+    /// Returns `false` if `expected` has a different length than the field. The length itself isn't treated as secret.
+    ///
+    /// This doesn't use `unsafe` code to hide the comparison from the optimizer, so it relies on the compiler not turning
+    /// the branch-free comparison back into one that returns early. Use a dedicated crate like `subtle` if you need stronger guarantees.
+    #[inline]
+    fn ct_eq<'a>(storage: &'a [u8], expected: &[u8]) -> bool
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+    {
+        constant_time_eq(Self::data(storage).as_ref(), expected)
+    }
+}
+
+impl<F: Field> FieldConstantTimeEq for F {}
+
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let diff = lhs
+        .iter()
+        .zip(rhs)
+        .fold(0u8, |diff, (lhs, rhs)| diff | (lhs ^ rhs));
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        len: u8,
+        tag: [u8; 4],
+        data: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(open_ended, LittleEndian, {
+        header: u16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn fixed_size_field() {
+        let storage = [2, 1, 2, 3, 4, 5, 6, 7];
+        assert!(layout::tag::ct_eq(&storage, &[1, 2, 3, 4]));
+        assert!(!layout::tag::ct_eq(&storage, &[0, 2, 3, 4]));
+        assert!(!layout::tag::ct_eq(&storage, &[1, 2, 3, 5]));
+        assert!(!layout::tag::ct_eq(&storage, &[1, 2, 3]));
+        assert!(!layout::tag::ct_eq(&storage, &[1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn dynamically_sized_fields() {
+        let storage = [2, 1, 2, 3, 4, 5, 6, 7];
+        assert!(layout::data::ct_eq(&storage, &[5, 6]));
+        assert!(!layout::data::ct_eq(&storage, &[5, 7]));
+        assert!(open_ended::tail::ct_eq(&storage, &[2, 3, 4, 5, 6, 7]));
+        assert!(!open_ended::tail::ct_eq(&storage, &[2, 3, 4, 5, 6, 8]));
+        assert!(open_ended::tail::ct_eq(&storage[..2], &[]));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(!constant_time_eq(&[], &[0]));
+    }
+}
@@ -0,0 +1,265 @@
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use super::primitive::{FieldReadExt, FieldWriteExt};
+use super::Field;
+
+struct AssertSameSize<Src, Dst> {
+    _p: PhantomData<(Src, Dst)>,
+}
+
+impl<Src: Field, Dst: Field> AssertSameSize<Src, Dst> {
+    const SIZE: usize = match (Src::SIZE, Dst::SIZE) {
+        (Some(src_size), Some(dst_size)) if src_size == dst_size => src_size,
+        _ => panic!("Error: copy_field can only copy between fields with the same static size"),
+    };
+}
+
+/// Copy the bytes of the field `Src` to the field `Dst` within the same storage, using the [Field] API.
+///
+/// The bytes are copied as they are, without reading and writing the values, so this works for any pair of fields
+/// with the same static size and preserves the exact bit pattern, e.g. the payload of a NaN float.
+/// Fields with different sizes or without a static size are a compile time error. The fields may overlap.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, copy_field};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   current: f32,
+///   previous: f32,
+///   raw: [u8; 4],
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 12];
+/// my_layout::current::write(&mut storage, f32::from_bits(0x7FC0_1234));
+/// copy_field::<my_layout::current, my_layout::previous>(&mut storage);
+/// copy_field::<my_layout::current, my_layout::raw>(&mut storage);
+/// assert_eq!(0x7FC0_1234, my_layout::previous::read(&storage).to_bits());
+/// assert_eq!(&0x7FC0_1234u32.to_le_bytes(), my_layout::raw::data(&storage));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::{prelude::*, copy_field};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   small: u16,
+///   large: u32,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 6];
+/// // fails to compile because the fields have different sizes
+/// copy_field::<my_layout::small, my_layout::large>(&mut storage);
+/// # }
+/// ```
+#[inline(always)]
+pub fn copy_field<Src: Field, Dst: Field>(storage: &mut [u8]) {
+    let size = AssertSameSize::<Src, Dst>::SIZE;
+    storage.copy_within(Src::OFFSET..(Src::OFFSET + size), Dst::OFFSET);
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// This trait is implemented by the primitive integer types `u8`, `u16`, `u32`, `u64`, `u128`, `i8`, `i16`, `i32`, `i64` and `i128`
+/// and allows [copy_field_saturating] to convert between them. It is sealed and can't be implemented outside of this crate.
+pub trait SaturatingInteger: Copy + private::Sealed {
+    /// Internal function, don't use!
+    #[doc(hidden)]
+    fn split_sign(self) -> Result<u128, i128>;
+
+    /// Internal function, don't use!
+    #[doc(hidden)]
+    fn from_split_sign_saturating(value: Result<u128, i128>) -> Self;
+}
+
+macro_rules! impl_saturating_integer {
+    ($($type: ty),*) => {
+        $(
+            impl private::Sealed for $type {}
+
+            impl SaturatingInteger for $type {
+                #[inline(always)]
+                #[allow(unused_comparisons)]
+                fn split_sign(self) -> Result<u128, i128> {
+                    if self >= 0 {
+                        Ok(self as u128)
+                    } else {
+                        Err(self as i128)
+                    }
+                }
+
+                #[inline(always)]
+                fn from_split_sign_saturating(value: Result<u128, i128>) -> Self {
+                    match value {
+                        Ok(non_negative) => <$type>::try_from(non_negative).unwrap_or(<$type>::MAX),
+                        Err(negative) => <$type>::try_from(negative).unwrap_or(<$type>::MIN),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_saturating_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Read the integer field `Src` and write its value to the integer field `Dst` within the same storage, clamping it to the range of `Dst`.
+///
+/// Unlike [copy_field], the fields can have different widths and signedness. Values greater than the maximum of the type of `Dst`
+/// are written as that maximum, e.g. `u8::MAX` for a `u8` field, and values less than its minimum are written as that minimum,
+/// e.g. `i16::MIN` for an `i16` field or `0` for any unsigned field. Values in range are written unchanged, regardless of the
+/// endianness of the fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, copy_field_saturating};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   wide: i32,
+///   narrow: i8,
+///   unsigned: u16,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 7];
+/// my_layout::wide::write(&mut storage, -1000);
+/// copy_field_saturating::<my_layout::wide, my_layout::narrow>(&mut storage);
+/// copy_field_saturating::<my_layout::wide, my_layout::unsigned>(&mut storage);
+/// assert_eq!(i8::MIN, my_layout::narrow::read(&storage));
+/// assert_eq!(0, my_layout::unsigned::read(&storage));
+///
+/// my_layout::wide::write(&mut storage, 100);
+/// copy_field_saturating::<my_layout::wide, my_layout::narrow>(&mut storage);
+/// assert_eq!(100, my_layout::narrow::read(&storage));
+/// # }
+/// ```
+#[inline]
+pub fn copy_field_saturating<Src, Dst>(storage: &mut [u8])
+where
+    Src: FieldReadExt,
+    Src::HighLevelType: SaturatingInteger,
+    Dst: FieldWriteExt,
+    Dst::HighLevelType: SaturatingInteger,
+{
+    let value = Src::read(storage).split_sign();
+    Dst::write(
+        storage,
+        <Dst::HighLevelType as SaturatingInteger>::from_split_sign_saturating(value),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, BigEndian, {
+        first: u32,
+        second: i32,
+        third: [u8; 4],
+        fourth: f32,
+    });
+
+    #[test]
+    fn copy_between_fields() {
+        let mut storage = [0; 16];
+        layout::first::write(&mut storage, 0x0102_0304);
+        copy_field::<layout::first, layout::second>(&mut storage);
+        copy_field::<layout::second, layout::third>(&mut storage);
+        assert_eq!(0x0102_0304, layout::second::read(&storage));
+        assert_eq!(&[1, 2, 3, 4], layout::third::data(&storage));
+    }
+
+    #[test]
+    fn preserves_nan_payload() {
+        let mut storage = [0; 16];
+        let nan = f32::from_bits(0xFFC0_0001);
+        layout::fourth::write(&mut storage, nan);
+        copy_field::<layout::fourth, layout::first>(&mut storage);
+        assert_eq!(0xFFC0_0001, layout::first::read(&storage));
+    }
+
+    #[test]
+    fn overlapping_fields() {
+        type Head = PrimitiveField<[u8; 4], BigEndian, 0>;
+        type Shifted = PrimitiveField<[u8; 4], BigEndian, 2>;
+        let mut storage = [1, 2, 3, 4, 5, 6];
+        copy_field::<Head, Shifted>(&mut storage);
+        assert_eq!([1, 2, 1, 2, 3, 4], storage);
+        copy_field::<Shifted, Head>(&mut storage);
+        assert_eq!([1, 2, 3, 4, 3, 4], storage);
+    }
+
+    binary_layout!(widths, BigEndian, {
+        byte: i8,
+        ubyte: u8,
+        short: i16,
+        ushort: u16,
+        long: i64,
+        ulong: u64,
+        huge: i128,
+        uhuge: u128,
+    });
+
+    #[test]
+    fn saturating_narrowing() {
+        let mut storage = [0; 62];
+        widths::long::write(&mut storage, i64::MAX);
+        copy_field_saturating::<widths::long, widths::byte>(&mut storage);
+        copy_field_saturating::<widths::long, widths::ubyte>(&mut storage);
+        copy_field_saturating::<widths::long, widths::short>(&mut storage);
+        assert_eq!(i8::MAX, widths::byte::read(&storage));
+        assert_eq!(u8::MAX, widths::ubyte::read(&storage));
+        assert_eq!(i16::MAX, widths::short::read(&storage));
+
+        widths::long::write(&mut storage, i64::MIN);
+        copy_field_saturating::<widths::long, widths::byte>(&mut storage);
+        copy_field_saturating::<widths::long, widths::ushort>(&mut storage);
+        assert_eq!(i8::MIN, widths::byte::read(&storage));
+        assert_eq!(0, widths::ushort::read(&storage));
+
+        widths::long::write(&mut storage, -300);
+        copy_field_saturating::<widths::long, widths::short>(&mut storage);
+        copy_field_saturating::<widths::long, widths::byte>(&mut storage);
+        assert_eq!(-300, widths::short::read(&storage));
+        assert_eq!(i8::MIN, widths::byte::read(&storage));
+    }
+
+    #[test]
+    fn saturating_same_width_and_widening() {
+        let mut storage = [0; 62];
+        widths::ulong::write(&mut storage, u64::MAX);
+        copy_field_saturating::<widths::ulong, widths::long>(&mut storage);
+        assert_eq!(i64::MAX, widths::long::read(&storage));
+        copy_field_saturating::<widths::ulong, widths::uhuge>(&mut storage);
+        assert_eq!(u128::from(u64::MAX), widths::uhuge::read(&storage));
+
+        widths::uhuge::write(&mut storage, u128::MAX);
+        copy_field_saturating::<widths::uhuge, widths::huge>(&mut storage);
+        assert_eq!(i128::MAX, widths::huge::read(&storage));
+
+        widths::huge::write(&mut storage, i128::MIN);
+        copy_field_saturating::<widths::huge, widths::uhuge>(&mut storage);
+        copy_field_saturating::<widths::huge, widths::long>(&mut storage);
+        assert_eq!(0, widths::uhuge::read(&storage));
+        assert_eq!(i64::MIN, widths::long::read(&storage));
+
+        widths::byte::write(&mut storage, -5);
+        copy_field_saturating::<widths::byte, widths::huge>(&mut storage);
+        assert_eq!(-5, widths::huge::read(&storage));
+    }
+
+    #[test]
+    fn saturating_mixed_endianness() {
+        type Big = PrimitiveField<i32, BigEndian, 0>;
+        type Little = PrimitiveField<i16, LittleEndian, 4>;
+        let mut storage = [0; 6];
+        Big::write(&mut storage, 0x1234);
+        copy_field_saturating::<Big, Little>(&mut storage);
+        assert_eq!([0, 0, 0x12, 0x34, 0x34, 0x12], storage);
+    }
+}
@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+use super::{primitive::FieldSliceAccess, Field};
+
+/// This extension trait allows accessing byte array fields as a [Cow], for APIs that accept a [Cow] and shouldn't force a copy.
+///
+/// [FieldCowAccess::cow] borrows the field from a borrowed storage, like [FieldSliceAccess::data] does.
+/// [FieldCowAccess::into_cow] takes a storage that is itself a [Cow]. It borrows the field if the storage is borrowed,
+/// and if the storage is owned, it cuts the field out of the owned `Vec<u8>` in place, reusing its allocation.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldCowAccess};
+/// use std::borrow::Cow;
+///
+/// binary_layout!(packet, BigEndian, {
+///   kind: u8,
+///   payload: [u8],
+/// });
+///
+/// # fn main() {
+/// let storage = vec![1, 10, 11, 12];
+/// let borrowed: Cow<[u8]> = packet::payload::cow(&storage);
+/// assert!(matches!(borrowed, Cow::Borrowed(&[10, 11, 12])));
+///
+/// let owned: Cow<[u8]> = packet::payload::into_cow(Cow::Owned(storage));
+/// assert_eq!(Cow::<[u8]>::Owned(vec![10, 11, 12]), owned);
+/// # }
+/// ```
+pub trait FieldCowAccess: Field {
+    /// Borrow the data of the field from a borrowed storage as a [Cow]. This never copies.
+    #[inline]
+    fn cow<'a>(storage: &'a [u8]) -> Cow<'a, [u8]>
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+    {
+        let len = Self::data(storage).as_ref().len();
+        Cow::Borrowed(&storage[Self::OFFSET..(Self::OFFSET + len)])
+    }
+
+    /// Get the data of the field from a storage that is a [Cow]. If the storage is borrowed, this borrows the field from it.
+    /// If the storage is owned, this removes the bytes before and after the field from the `Vec<u8>` and returns it.
+    /// This moves the field to the start of the vec but doesn't reallocate.
+    fn into_cow(storage: Cow<'_, [u8]>) -> Cow<'_, [u8]>
+    where
+        Self: for<'b> FieldSliceAccess<'b>,
+        for<'b> <Self as FieldSliceAccess<'b>>::SliceType: AsRef<[u8]>,
+    {
+        match storage {
+            Cow::Borrowed(storage) => Self::cow(storage),
+            Cow::Owned(mut storage) => {
+                let len = Self::data(&storage).as_ref().len();
+                storage.truncate(Self::OFFSET + len);
+                storage.drain(..Self::OFFSET);
+                Cow::Owned(storage)
+            }
+        }
+    }
+}
+
+impl<F: Field> FieldCowAccess for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        first: u8,
+        fixed: [u8; 3],
+        len: u8,
+        computed: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(open_ended, LittleEndian, {
+        first: u16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn cow_borrows() {
+        let storage = [1, 2, 3, 4, 2, 5, 6, 7];
+        assert!(matches!(
+            layout::fixed::cow(&storage),
+            Cow::Borrowed(&[2, 3, 4])
+        ));
+        assert!(matches!(
+            layout::computed::cow(&storage),
+            Cow::Borrowed(&[5, 6])
+        ));
+        assert!(matches!(
+            open_ended::tail::cow(&storage),
+            Cow::Borrowed(&[3, 4, 2, 5, 6, 7])
+        ));
+    }
+
+    #[test]
+    fn into_cow_borrowed() {
+        let storage = [1, 2, 3, 4, 2, 5, 6, 7];
+        assert!(matches!(
+            layout::fixed::into_cow(Cow::Borrowed(&storage)),
+            Cow::Borrowed(&[2, 3, 4])
+        ));
+        assert!(matches!(
+            layout::computed::into_cow(Cow::Borrowed(&storage)),
+            Cow::Borrowed(&[5, 6])
+        ));
+    }
+
+    #[test]
+    fn into_cow_owned_keeps_allocation() {
+        let storage = vec![1, 2, 3, 4, 2, 5, 6, 7];
+        let capacity = storage.capacity();
+        match layout::fixed::into_cow(Cow::Owned(storage)) {
+            Cow::Owned(field) => {
+                assert_eq!(vec![2, 3, 4], field);
+                assert_eq!(capacity, field.capacity());
+            }
+            Cow::Borrowed(_) => panic!("Expected an owned field"),
+        }
+
+        let storage = vec![1, 2, 3, 4, 2, 5, 6, 7];
+        assert_eq!(
+            Cow::<[u8]>::Owned(vec![5, 6]),
+            layout::computed::into_cow(Cow::Owned(storage))
+        );
+        let storage = vec![1, 2, 3, 4];
+        assert_eq!(
+            Cow::<[u8]>::Owned(vec![3, 4]),
+            open_ended::tail::into_cow(Cow::Owned(storage))
+        );
+    }
+}
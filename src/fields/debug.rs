@@ -0,0 +1,33 @@
+use core::fmt::Debug;
+
+use super::primitive::FieldCopyAccess;
+use super::probe::FieldProbe;
+
+// Used by the `debug_fields` function generated by [binary_layout!](crate::binary_layout!) to read a field
+// into a `Box<dyn Debug>` if it can be read and to skip it otherwise, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait DebugCopyField {
+    /// Internal function, don't use!
+    fn debug_value(&self, storage: &[u8]) -> Option<Box<dyn Debug>>;
+}
+
+impl<F: FieldCopyAccess> DebugCopyField for &FieldProbe<F>
+where
+    F::HighLevelType: Debug + 'static,
+    F::ReadError: Debug + 'static,
+{
+    #[inline]
+    fn debug_value(&self, storage: &[u8]) -> Option<Box<dyn Debug>> {
+        Some(match F::try_read(storage) {
+            Ok(value) => Box::new(value),
+            Err(error) => Box::new(error),
+        })
+    }
+}
+
+probe_fallback!(DebugOtherField {
+    fn debug_value(&self, storage: &[u8]) -> Option<Box<dyn Debug>> {
+        None
+    }
+});
@@ -0,0 +1,103 @@
+use core::fmt::Debug;
+use core::ops::Range;
+
+use super::primitive::{FieldCopyAccess, FieldSliceAccess};
+use super::probe::FieldProbe;
+
+/// A difference between two storages of the same layout, as returned by the `diff` function generated by [binary_layout!](crate::binary_layout!).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldDiff {
+    /// A field that can be read as a whole, e.g. an integer field, is stored differently.
+    /// `a` and `b` are the values read from the two storages, formatted with [Debug].
+    /// If a value fails to read, e.g. a [NonZeroU8](core::num::NonZeroU8) field storing zero, the error is formatted instead.
+    Value {
+        /// The name of the field
+        name: &'static str,
+        /// The value of the field in the first storage
+        a: String,
+        /// The value of the field in the second storage
+        b: String,
+    },
+    /// A slice field, e.g. a byte array, is stored differently.
+    /// `range` spans from the first to the last differing byte, relative to the start of the field.
+    /// If the field has a different length in the two storages, the range extends to the end of the longer one.
+    Bytes {
+        /// The name of the field
+        name: &'static str,
+        /// The range of bytes that differ
+        range: Range<usize>,
+    },
+}
+
+// Used by the `diff` function generated by [binary_layout!](crate::binary_layout!) to compare a field
+// depending on how it can be accessed, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait DiffCopyField {
+    /// Internal function, don't use!
+    fn diff(&self, name: &'static str, a: &[u8], b: &[u8]) -> Option<FieldDiff>;
+}
+
+impl<F: FieldCopyAccess> DiffCopyField for &&FieldProbe<F>
+where
+    F::HighLevelType: Debug,
+    F::ReadError: Debug,
+{
+    #[inline]
+    fn diff(&self, name: &'static str, a: &[u8], b: &[u8]) -> Option<FieldDiff> {
+        let range = F::OFFSET..(F::OFFSET + F::SIZE.unwrap());
+        if a[range.clone()] == b[range] {
+            return None;
+        }
+        Some(FieldDiff::Value {
+            name,
+            a: format_value::<F>(a),
+            b: format_value::<F>(b),
+        })
+    }
+}
+
+fn format_value<F: FieldCopyAccess>(storage: &[u8]) -> String
+where
+    F::HighLevelType: Debug,
+    F::ReadError: Debug,
+{
+    match F::try_read(storage) {
+        Ok(value) => format!("{:?}", value),
+        Err(error) => format!("{:?}", error),
+    }
+}
+
+/// Internal trait, don't use!
+pub trait DiffSliceField {
+    /// Internal function, don't use!
+    fn diff(&self, name: &'static str, a: &[u8], b: &[u8]) -> Option<FieldDiff>;
+}
+
+impl<F> DiffSliceField for &FieldProbe<F>
+where
+    F: for<'a> FieldSliceAccess<'a>,
+    for<'a> <F as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+{
+    #[inline]
+    fn diff(&self, name: &'static str, a: &[u8], b: &[u8]) -> Option<FieldDiff> {
+        let a = F::data(a);
+        let b = F::data(b);
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let differs = |i: &usize| a.get(*i) != b.get(*i);
+        let len = usize::max(a.len(), b.len());
+        let start = (0..len).find(differs)?;
+        let end = (0..len).rfind(differs).unwrap() + 1;
+        Some(FieldDiff::Bytes {
+            name,
+            range: start..end,
+        })
+    }
+}
+
+probe_fallback!(DiffOtherField {
+    fn diff(&self, name: &'static str, a: &[u8], b: &[u8]) -> Option<FieldDiff> {
+        None
+    }
+});
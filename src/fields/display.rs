@@ -0,0 +1,99 @@
+use core::fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex};
+use core::marker::PhantomData;
+
+use super::primitive::FieldCopyAccess;
+
+/// Internal type, don't use!
+/// Used by the `Display` implementation generated by [binary_layout!](crate::binary_layout!) for layouts with a
+/// `#[display(...)]` attribute. It reads the field when it is formatted, so fields that aren't used in the format string
+/// aren't read, and it only implements the formatting traits for fields that can be read into a type implementing them.
+pub struct DisplayField<'a, F> {
+    storage: &'a [u8],
+    _p: PhantomData<F>,
+}
+
+impl<'a, F> DisplayField<'a, F> {
+    /// Internal function, don't use!
+    #[inline(always)]
+    pub fn new(storage: &'a [u8]) -> Self {
+        Self {
+            storage,
+            _p: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_format_trait {
+    ($($format_trait: ident),*) => {
+        $(
+            impl<F: FieldCopyAccess> $format_trait for DisplayField<'_, F>
+            where
+                F::HighLevelType: $format_trait,
+                F::ReadError: Debug,
+            {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    match F::try_read(self.storage) {
+                        Ok(value) => $format_trait::fmt(&value, f),
+                        Err(error) => write!(f, "<{:?}>", error),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_format_trait!(Display, LowerHex, UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(#[display("kind={kind} len={length:>4} flags={flags:#04x} id={id}")] packet, BigEndian, {
+        kind: u8,
+        length: u16,
+        flags: u8,
+        id: core::num::NonZeroU16,
+        payload: [u8],
+    });
+
+    binary_layout!(#[display("[{value}]")] #[pad_to(8)] padded, LittleEndian, {
+        value: f32,
+    });
+
+    binary_layout!(#[pad_to(8)] #[display("{value}")] #[deny_gaps] #[assert_pow2_size] combined, LittleEndian, {
+        value: i16,
+    });
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let storage = [3, 0, 12, 0xA, 0, 1, 10, 11];
+        assert_eq!(
+            "kind=3 len=  12 flags=0x0a id=1",
+            packet::View::new(&storage[..]).to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_error() {
+        let storage = [3, 0, 12, 0xA, 0, 0];
+        assert_eq!(
+            "kind=3 len=  12 flags=0x0a id=<NonZeroIsZeroError(())>",
+            packet::View::new(&storage[..]).to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn with_other_attributes() {
+        let mut storage = [0; 8];
+        padded::value::write(&mut storage, 1.5);
+        assert_eq!("[1.5]", padded::View::new(&storage).to_string());
+        assert_eq!(Some(8), padded::SIZE);
+
+        combined::value::write(&mut storage, -2);
+        assert_eq!("-2", combined::View::new(&storage).to_string());
+        assert_eq!(Some(8), combined::SIZE);
+    }
+}
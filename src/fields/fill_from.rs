@@ -0,0 +1,173 @@
+use std::io::{self, Read};
+
+use super::{primitive::FieldSliceAccess, Field};
+
+/// This extension trait allows reading bytes from a [Read] directly into a byte array field, e.g. to copy a payload
+/// from a socket into a message without going through an intermediate buffer.
+///
+/// For fields with a static size, e.g. `[u8; N]`, [FieldFillFrom::fill_from] fills the whole field and returns an
+/// [io::ErrorKind::UnexpectedEof] error if the reader ends before that. For fields with a dynamic size, e.g. an open ended
+/// byte array, it reads until the field is full or the reader ends and returns the number of bytes read, so the rest of the
+/// storage can be used as the capacity of the field.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldFillFrom};
+///
+/// binary_layout!(packet, BigEndian, {
+///   token: [u8; 4],
+///   payload: [u8],
+/// });
+///
+/// # fn main() -> std::io::Result<()> {
+/// let mut socket: &[u8] = &[1, 2, 3, 4, 10, 11, 12];
+/// let mut storage = [0; 16];
+/// assert_eq!(4, packet::token::fill_from(&mut storage, &mut socket)?);
+/// let len = packet::payload::fill_from(&mut storage, &mut socket)?;
+/// assert_eq!(3, len);
+/// assert_eq!([1, 2, 3, 4], storage[..4]);
+/// assert_eq!([10, 11, 12], packet::payload::data(&storage)[..len]);
+/// # Ok(())
+/// # }
+/// ```
+pub trait FieldFillFrom: Field {
+    /// Read bytes from `reader` into the field in `storage` and return the number of bytes read,
+    /// see [FieldFillFrom] for how fields with a static and a dynamic size differ.
+    /// Reads interrupted with [io::ErrorKind::Interrupted] are retried, other errors are returned.
+    ///
+    /// # Panics
+    /// This panics if `storage` is too short for the field, like [FieldSliceAccess::data_mut].
+    fn fill_from<R: Read + ?Sized>(storage: &mut [u8], reader: &mut R) -> io::Result<usize>
+    where
+        Self: for<'a> FieldSliceAccess<'a>,
+        for<'a> <Self as FieldSliceAccess<'a>>::MutSliceType: AsMut<[u8]>,
+    {
+        let mut data = Self::data_mut(storage);
+        let data = data.as_mut();
+        let mut filled = 0;
+        while filled < data.len() {
+            match reader.read(&mut data[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        if Self::SIZE.is_some() && filled < data.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "reader ended before the field was filled",
+            ));
+        }
+        Ok(filled)
+    }
+}
+
+impl<F: Field> FieldFillFrom for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        int: u16,
+        array: [u8; 3],
+        tail: [u8],
+    });
+
+    // Returns at most two bytes per read and an interruption before each read
+    struct ChunkedReader<'a> {
+        remaining: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(self.remaining.len()).min(2);
+            buf[..len].copy_from_slice(&self.remaining[..len]);
+            self.remaining = &self.remaining[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn fill_array() {
+        let mut storage = [0xFF; 8];
+        let mut reader: &[u8] = &[1, 2, 3, 4];
+        assert_eq!(
+            3,
+            layout::array::fill_from(&mut storage, &mut reader).unwrap()
+        );
+        assert_eq!([0xFF, 0xFF, 1, 2, 3, 0xFF, 0xFF, 0xFF], storage);
+        assert_eq!([4], reader);
+    }
+
+    #[test]
+    fn fill_array_eof() {
+        let mut storage = [0; 8];
+        let mut reader: &[u8] = &[1, 2];
+        let err = layout::array::fill_from(&mut storage, &mut reader).unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn fill_tail() {
+        let mut storage = [0; 8];
+        let mut reader: &[u8] = &[1, 2];
+        assert_eq!(
+            2,
+            layout::tail::fill_from(&mut storage, &mut reader).unwrap()
+        );
+        assert_eq!([0, 0, 0, 0, 0, 1, 2, 0], storage);
+
+        let mut reader: &[u8] = &[1, 2, 3, 4, 5];
+        assert_eq!(
+            3,
+            layout::tail::fill_from(&mut storage, &mut reader).unwrap()
+        );
+        assert_eq!([0, 0, 0, 0, 0, 1, 2, 3], storage);
+        assert_eq!([4, 5], reader);
+
+        let mut reader: &[u8] = &[1, 2];
+        assert_eq!(
+            0,
+            layout::tail::fill_from(&mut storage[..5], &mut reader).unwrap()
+        );
+    }
+
+    #[test]
+    fn fill_in_chunks() {
+        let mut storage = [0; 9];
+        let mut reader = ChunkedReader {
+            remaining: &[1, 2, 3, 4, 5, 6, 7],
+            interrupt: false,
+        };
+        assert_eq!(
+            3,
+            layout::array::fill_from(&mut storage, &mut reader).unwrap()
+        );
+        assert_eq!(
+            4,
+            layout::tail::fill_from(&mut storage, &mut reader).unwrap()
+        );
+        assert_eq!([0, 0, 1, 2, 3, 4, 5, 6, 7], storage);
+    }
+
+    #[test]
+    fn error() {
+        struct FailingReader;
+        impl Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::ConnectionReset.into())
+            }
+        }
+        let mut storage = [0; 8];
+        let err = layout::tail::fill_from(&mut storage, &mut FailingReader).unwrap_err();
+        assert_eq!(io::ErrorKind::ConnectionReset, err.kind());
+    }
+}
@@ -0,0 +1,200 @@
+use core::convert::{Infallible, TryFrom};
+use core::marker::PhantomData;
+use num_rational::Ratio;
+
+use crate::{IntegerOutOfRangeError, LayoutAs};
+
+/// An exact rational value stored as an integer in units of `1 / DENOM`, i.e. the stored integer `raw` means the value `raw / DENOM`.
+/// This is the exact counterpart of [Scaled](crate::Scaled) for fixed-point encodings, e.g. prices stored in cents as
+/// `FixedRatio<i32, 100> as i32`, and avoids the rounding errors of [f64]. Use it in a [binary_layout!](crate::binary_layout!)
+/// layout with the same integer type for `RAW` and the type after `as`. This is only available with the `num-rational` feature.
+///
+/// Reading returns the [Ratio] `raw / DENOM` in its reduced form. Writing only succeeds if the value is a multiple of `1 / DENOM` and the
+/// resulting integer fits into `RAW`, otherwise it throws an [IntegerOutOfRangeError], so you need to use
+/// [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) to write these fields. `DENOM` must be positive, otherwise
+/// the field fails to compile when it is accessed.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FixedRatio};
+/// use num_rational::Ratio;
+///
+/// binary_layout!(order, LittleEndian, {
+///   // in cents
+///   price: FixedRatio<i32, 100> as i32,
+///   // in units of 1/3, e.g. for thirds of a share
+///   quantity: FixedRatio<u16, 3> as u16,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 6];
+/// order::price::try_write(&mut storage, FixedRatio::new(Ratio::new(-1999, 100))).unwrap();
+/// order::quantity::try_write(&mut storage, FixedRatio::new(Ratio::new(7, 3))).unwrap();
+/// assert_eq!([0x31, 0xF8, 0xFF, 0xFF, 7, 0], storage);
+/// assert_eq!(Ratio::new(-1999, 100), order::price::read(&storage).value());
+/// assert_eq!(Ratio::new(7, 3), order::quantity::read(&storage).value());
+///
+/// // One half isn't a multiple of 1/3
+/// assert!(order::quantity::try_write(&mut storage, FixedRatio::new(Ratio::new(1, 2))).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedRatio<RAW, const DENOM: i64> {
+    value: Ratio<i64>,
+    _raw: PhantomData<RAW>,
+}
+
+impl<RAW, const DENOM: i64> FixedRatio<RAW, DENOM> {
+    /// Fails to evaluate if the denominator isn't positive. Negative denominators would make reading `i64::MIN` overflow.
+    const POSITIVE_DENOM: () = assert!(
+        DENOM > 0,
+        "Error: DENOM of FixedRatio<RAW, DENOM> must be positive"
+    );
+
+    /// Create a [FixedRatio] holding the given value.
+    #[inline]
+    pub const fn new(value: Ratio<i64>) -> Self {
+        Self {
+            value,
+            _raw: PhantomData,
+        }
+    }
+
+    /// Return the value.
+    #[inline]
+    pub const fn value(self) -> Ratio<i64> {
+        self.value
+    }
+
+    #[inline]
+    fn from_raw(raw: i64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::POSITIVE_DENOM;
+        Self::new(Ratio::new(raw, DENOM))
+    }
+
+    // The stored integer for this value, or None if the value isn't a multiple of 1 / DENOM
+    #[inline]
+    fn exact_raw(self) -> Option<i128> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::POSITIVE_DENOM;
+        // Can't overflow because both factors fit into i64
+        let scaled = i128::from(*self.value.numer()) * i128::from(DENOM);
+        let denom = i128::from(*self.value.denom());
+        if scaled % denom == 0 {
+            Some(scaled / denom)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_fixed_ratio {
+    ($($type: ty),*) => {
+        $(
+            impl<const DENOM: i64> LayoutAs<$type> for FixedRatio<$type, DENOM> {
+                type ReadError = Infallible;
+                type WriteError = IntegerOutOfRangeError;
+
+                #[inline]
+                fn try_read(v: $type) -> Result<Self, Infallible> {
+                    Ok(Self::from_raw(i64::from(v)))
+                }
+
+                #[inline]
+                fn try_write(v: Self) -> Result<$type, IntegerOutOfRangeError> {
+                    v.exact_raw()
+                        .and_then(|raw| <$type>::try_from(raw).ok())
+                        .ok_or(IntegerOutOfRangeError(()))
+                }
+            }
+        )*
+    };
+}
+
+// u64 isn't supported because its values don't all fit into the numerator of a Ratio<i64>
+impl_fixed_ratio!(u8, u16, u32, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, BigEndian, {
+        cents: FixedRatio<i32, 100> as i32,
+        thirds: FixedRatio<u8, 3> as u8,
+        quarters: FixedRatio<i64, 4> as i64,
+    });
+
+    #[test]
+    fn read() {
+        let mut storage = [0; 13];
+        storage[0..4].copy_from_slice(&(-250i32).to_be_bytes());
+        storage[4] = 4;
+        storage[5..13].copy_from_slice(&i64::MAX.to_be_bytes());
+        assert_eq!(Ratio::new(-5, 2), layout::cents::read(&storage).value());
+        assert_eq!(Ratio::new(4, 3), layout::thirds::read(&storage).value());
+        assert_eq!(
+            Ratio::new(i64::MAX, 4),
+            layout::quarters::read(&storage).value()
+        );
+    }
+
+    #[test]
+    fn write() {
+        let mut storage = [0; 13];
+        layout::cents::try_write(&mut storage, FixedRatio::new(Ratio::new(3, 4))).unwrap();
+        assert_eq!(75, i32::from_be_bytes(storage[0..4].try_into().unwrap()));
+        layout::thirds::try_write(&mut storage, FixedRatio::new(Ratio::from_integer(85))).unwrap();
+        assert_eq!(255, storage[4]);
+        layout::quarters::try_write(&mut storage, FixedRatio::new(Ratio::new(-1, 2))).unwrap();
+        assert_eq!(-2, i64::from_be_bytes(storage[5..13].try_into().unwrap()));
+        layout::quarters::try_write(
+            &mut storage,
+            FixedRatio::new(Ratio::from_integer(i64::MIN / 4)),
+        )
+        .unwrap();
+        assert_eq!(
+            i64::MIN,
+            i64::from_be_bytes(storage[5..13].try_into().unwrap())
+        );
+        assert_eq!(
+            Ratio::from_integer(i64::MIN / 4),
+            layout::quarters::read(&storage).value()
+        );
+    }
+
+    #[test]
+    fn write_inexact_or_out_of_range() {
+        let mut storage = [0; 13];
+        for value in [Ratio::new(1, 3), Ratio::new(1, 1000)] {
+            assert!(layout::cents::try_write(&mut storage, FixedRatio::new(value)).is_err());
+        }
+        for value in [Ratio::new(-1, 3), Ratio::from_integer(86), Ratio::new(1, 2)] {
+            assert!(layout::thirds::try_write(&mut storage, FixedRatio::new(value)).is_err());
+        }
+        assert!(layout::quarters::try_write(
+            &mut storage,
+            FixedRatio::new(Ratio::from_integer(i64::MAX))
+        )
+        .is_err());
+        assert_eq!([0; 13], storage);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut storage = [0; 13];
+        for raw in [i32::MIN, -101, -100, -1, 0, 1, 99, 100, 12345, i32::MAX] {
+            storage[0..4].copy_from_slice(&raw.to_be_bytes());
+            let value = layout::cents::read(&storage);
+            layout::cents::try_write(&mut storage, value).unwrap();
+            assert_eq!(raw, i32::from_be_bytes(storage[0..4].try_into().unwrap()));
+        }
+        for raw in 0..=u8::MAX {
+            storage[4] = raw;
+            let value = layout::thirds::read(&storage);
+            layout::thirds::try_write(&mut storage, value).unwrap();
+            assert_eq!(raw, storage[4]);
+        }
+    }
+}
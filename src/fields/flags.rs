@@ -0,0 +1,187 @@
+use super::primitive::{FieldReadExt, FieldWriteExt, PrimitiveField};
+use crate::endianness::Endianness;
+
+/// This extension trait allows checking single bits of unsigned integer fields that hold flags, without defining a type for the flags.
+/// [FieldFlags::has] and [FieldFlags::any] read the field and apply a mask to it.
+///
+/// [FieldFlags::to_bools] expands the bits of the field into an array with one bool per bit, e.g. `[bool; 16]` for a `u16` field,
+/// and [FieldFlags::from_bools] packs such an array back into the field. Index `i` of the array is the bit with the value `1 << i`,
+/// i.e. index 0 is the least significant bit. This mapping refers to the value of the field, so it is the same for all endiannesses.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldFlags};
+///
+/// const FLAG_ACK: u16 = 0x0010;
+/// const FLAG_SYN: u16 = 0x0002;
+/// const FLAG_FIN: u16 = 0x0001;
+///
+/// binary_layout!(segment, BigEndian, {
+///   seq: u32,
+///   flags: u16,
+/// });
+///
+/// # fn main() {
+/// let storage = [0, 0, 0, 1, 0x00, 0x12];
+/// assert!(segment::flags::has(&storage, FLAG_SYN | FLAG_ACK));
+/// assert!(!segment::flags::has(&storage, FLAG_SYN | FLAG_FIN));
+/// assert!(segment::flags::any(&storage, FLAG_SYN | FLAG_FIN));
+/// assert!(!segment::flags::any(&storage, FLAG_FIN));
+///
+/// let bools = segment::flags::to_bools(&storage);
+/// assert_eq!([false, true, false, false, true], bools[..5]);
+///
+/// let mut storage = storage;
+/// let mut bools = [false; 16];
+/// bools[0] = true;
+/// bools[15] = true;
+/// segment::flags::from_bools(&mut storage, bools);
+/// assert_eq!(0x8001, segment::flags::read(&storage));
+/// # }
+/// ```
+pub trait FieldFlags: FieldReadExt {
+    /// Read the field and return whether all bits that are set in `mask` are set in the field.
+    /// This returns `true` if `mask` is zero.
+    fn has(storage: &[u8], mask: Self::HighLevelType) -> bool;
+
+    /// Read the field and return whether any of the bits that are set in `mask` are set in the field.
+    /// This returns `false` if `mask` is zero.
+    fn any(storage: &[u8], mask: Self::HighLevelType) -> bool;
+
+    /// An array with one bool per bit of the field, e.g. `[bool; 16]` for a `u16` field
+    type Bools;
+
+    /// Read the field and expand its bits into an array, with index `i` holding whether the bit with the value `1 << i` is set.
+    fn to_bools(storage: &[u8]) -> Self::Bools;
+
+    /// Write the field with the bits given by an array, with index `i` defining whether the bit with the value `1 << i` is set.
+    /// This is the inverse of [FieldFlags::to_bools].
+    fn from_bools(storage: &mut [u8], bools: Self::Bools);
+}
+
+macro_rules! impl_field_flags {
+    ($($type: ty),*) => {
+        $(
+            impl<E: Endianness, const OFFSET_: usize> FieldFlags for PrimitiveField<$type, E, OFFSET_> {
+                #[inline(always)]
+                fn has(storage: &[u8], mask: $type) -> bool {
+                    Self::read(storage) & mask == mask
+                }
+
+                #[inline(always)]
+                fn any(storage: &[u8], mask: $type) -> bool {
+                    Self::read(storage) & mask != 0
+                }
+
+                type Bools = [bool; <$type>::BITS as usize];
+
+                #[inline]
+                fn to_bools(storage: &[u8]) -> Self::Bools {
+                    let value = Self::read(storage);
+                    let mut bools = [false; <$type>::BITS as usize];
+                    for (index, bit) in bools.iter_mut().enumerate() {
+                        *bit = value & (1 << index) != 0;
+                    }
+                    bools
+                }
+
+                #[inline]
+                fn from_bools(storage: &mut [u8], bools: Self::Bools) {
+                    let mut value: $type = 0;
+                    for (index, bit) in bools.iter().enumerate() {
+                        if *bit {
+                            value |= 1 << index;
+                        }
+                    }
+                    Self::write(storage, value);
+                }
+            }
+        )*
+    };
+}
+
+impl_field_flags!(u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(big, BigEndian, {
+        small: u8,
+        flags: u32,
+        wide: u128,
+    });
+
+    binary_layout!(little, LittleEndian, {
+        small: u8,
+        flags: u32,
+    });
+
+    #[test]
+    fn has() {
+        let storage = [0b101, 0x80, 0, 0, 0x01];
+        assert!(big::small::has(&storage, 0b101));
+        assert!(big::small::has(&storage, 0b100));
+        assert!(!big::small::has(&storage, 0b110));
+        assert!(big::flags::has(&storage, 0x8000_0001));
+        assert!(!big::flags::has(&storage, 0x0000_0081));
+        assert!(little::flags::has(&storage, 0x0100_0080));
+        assert!(!little::flags::has(&storage, 0x8000_0001));
+    }
+
+    #[test]
+    fn any() {
+        let storage = [0b101, 0x80, 0, 0, 0x01];
+        assert!(big::small::any(&storage, 0b110));
+        assert!(!big::small::any(&storage, 0b010));
+        assert!(big::flags::any(&storage, 0x0000_0003));
+        assert!(!big::flags::any(&storage, 0x7FFF_FFFE));
+        assert!(little::flags::any(&storage, 0x0000_0081));
+        assert!(!little::flags::any(&storage, 0x0000_0001));
+    }
+
+    #[test]
+    fn zero_mask() {
+        let storage = [0; 21];
+        assert!(big::flags::has(&storage, 0));
+        assert!(!big::flags::any(&storage, 0));
+        assert!(big::wide::has(&storage, 0));
+        assert!(!big::wide::any(&storage, u128::MAX));
+    }
+
+    #[test]
+    fn to_bools() {
+        let storage = [0b1000_0101, 0x80, 0, 0, 0x01];
+        assert_eq!(
+            [true, false, true, false, false, false, false, true],
+            big::small::to_bools(&storage)
+        );
+        let bools = big::flags::to_bools(&storage);
+        assert_eq!(32, bools.len());
+        assert_eq!(2, bools.iter().filter(|bit| **bit).count());
+        assert!(bools[0] && bools[31]);
+        let bools = little::flags::to_bools(&storage);
+        assert_eq!(2, bools.iter().filter(|bit| **bit).count());
+        assert!(bools[7] && bools[24]);
+    }
+
+    #[test]
+    fn from_bools() {
+        let mut storage = [0xFF; 21];
+        let mut bools = [false; 32];
+        bools[1] = true;
+        bools[30] = true;
+        big::flags::from_bools(&mut storage, bools);
+        assert_eq!([0x40, 0, 0, 0x02], storage[1..5]);
+        assert_eq!(bools, big::flags::to_bools(&storage));
+        little::flags::from_bools(&mut storage, bools);
+        assert_eq!([0x02, 0, 0, 0x40], storage[1..5]);
+
+        let mut bools = [false; 128];
+        bools[127] = true;
+        big::wide::from_bools(&mut storage, bools);
+        assert_eq!(1 << 127, big::wide::read(&storage));
+        assert_eq!(0xFF, storage[0]);
+    }
+}
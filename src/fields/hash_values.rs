@@ -0,0 +1,143 @@
+use core::hash::{Hash, Hasher};
+
+use super::{
+    primitive::{FieldReadExt, FieldSliceAccess, PrimitiveField},
+    Field,
+};
+
+/// This extension trait allows hashing the numbers stored in a byte array field, independent of the byte order they're stored in.
+///
+/// Like for [FieldWriteFromIter](crate::FieldWriteFromIter), the numbers are stored back to back in a byte array field like `[u8; 16]`.
+/// [FieldHashValues::hash_values] decodes each number with the endianness of the layout and feeds the decoded value into a [Hasher].
+/// This means that two byte arrays storing the same numbers hash equally, even if one of them stores them in big endian and the other one
+/// in little endian. Hashing the bytes of the field, e.g. by calling [Hash::hash] on the result of [FieldSliceAccess::data], doesn't do that.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldHashValues};
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::Hasher;
+///
+/// binary_layout!(big, BigEndian, {
+///   table: [u8; 8],
+/// });
+/// binary_layout!(little, LittleEndian, {
+///   table: [u8; 8],
+/// });
+///
+/// # fn main() {
+/// let big_storage = [0, 0, 0, 1, 0, 0, 0, 2];
+/// let little_storage = [1, 0, 0, 0, 2, 0, 0, 0];
+///
+/// let mut big_hasher = DefaultHasher::new();
+/// big::table::hash_values::<u32, _>(&big_storage, &mut big_hasher);
+/// let mut little_hasher = DefaultHasher::new();
+/// little::table::hash_values::<u32, _>(&little_storage, &mut little_hasher);
+/// assert_eq!(big_hasher.finish(), little_hasher.finish());
+/// # }
+/// ```
+pub trait FieldHashValues: Field {
+    /// Decode the numbers stored in the field as values of type `T`, using the endianness of the layout,
+    /// and feed them into `hasher` in the order they're stored in.
+    ///
+    /// If the length of the field isn't a multiple of the size of `T`, the remaining bytes at the end don't form a value and are ignored.
+    fn hash_values<'a, T, H>(storage: &'a [u8], hasher: &mut H)
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+        PrimitiveField<T, Self::Endian, 0>: FieldReadExt<HighLevelType = T>,
+        T: Hash,
+        H: Hasher,
+    {
+        let element_size = <PrimitiveField<T, Self::Endian, 0> as Field>::SIZE
+            .expect("Error: hash_values can only read values with a static size");
+        for chunk in Self::data(storage).as_ref().chunks_exact(element_size) {
+            PrimitiveField::<T, Self::Endian, 0>::read(chunk).hash(hasher);
+        }
+    }
+}
+
+impl<F: Field> FieldHashValues for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(big, BigEndian, {
+        len: u8,
+        table: [u8; 9],
+        tail: [u8],
+    });
+
+    binary_layout!(little, LittleEndian, {
+        len: u8,
+        table: [u8; 9],
+        tail: [u8],
+    });
+
+    // FNV-1a, so the tests don't need std
+    struct TestHasher(u64);
+    impl Default for TestHasher {
+        fn default() -> Self {
+            Self(0xcbf2_9ce4_8422_2325)
+        }
+    }
+    impl Hasher for TestHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+
+    fn hash<'a, F, T>(storage: &'a [u8]) -> u64
+    where
+        F: FieldHashValues + FieldSliceAccess<'a>,
+        <F as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+        PrimitiveField<T, F::Endian, 0>: FieldReadExt<HighLevelType = T>,
+        T: Hash,
+    {
+        let mut hasher = TestHasher::default();
+        F::hash_values::<T, _>(storage, &mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn same_values_in_different_endianness_hash_equally() {
+        let big_storage = [
+            0, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xFF, 1, 2,
+        ];
+        let little_storage = [
+            0, 0x02, 0x01, 0x04, 0x03, 0x06, 0x05, 0x08, 0x07, 0xEE, 2, 1,
+        ];
+        assert_eq!(
+            hash::<big::table, u16>(&big_storage),
+            hash::<little::table, u16>(&little_storage)
+        );
+        assert_eq!(
+            hash::<big::tail, u16>(&big_storage),
+            hash::<little::tail, u16>(&little_storage)
+        );
+        assert_ne!(
+            hash::<big::table, u32>(&big_storage),
+            hash::<little::table, u32>(&little_storage)
+        );
+        assert_ne!(
+            hash::<big::table, u16>(&big_storage),
+            hash::<big::table, u16>(&little_storage)
+        );
+    }
+
+    #[test]
+    fn hashes_decoded_values() {
+        let storage = [0, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xFF];
+        let mut expected = TestHasher::default();
+        0x0102_0304u32.hash(&mut expected);
+        0x0506_0708u32.hash(&mut expected);
+        assert_eq!(expected.finish(), hash::<big::table, u32>(&storage));
+    }
+}
@@ -0,0 +1,350 @@
+use core::convert::{Infallible, TryFrom};
+
+use crate::{LayoutAs, LayoutAsSaturating};
+
+/// This error is thrown when writing an integer that can't be represented in the encoding of the field,
+/// e.g. [i8::MIN] as a [SignMagnitude] integer, or when reading a value that doesn't fit into the integer type.
+/// It is also thrown when writing a [Scaled](crate::Scaled) value whose stored integer would be out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerOutOfRangeError(pub(crate) ());
+
+impl core::fmt::Display for IntegerOutOfRangeError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "IntegerOutOfRangeError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IntegerOutOfRangeError {}
+
+/// A signed integer stored in sign-magnitude encoding, i.e. the most significant bit is the sign
+/// and the remaining bits are the absolute value. Use it as `SignMagnitude<i16> as u16` in a
+/// [binary_layout!](crate::binary_layout!) layout, with a signed and an unsigned integer type of the same width.
+///
+/// Sign-magnitude has two encodings of zero. Negative zero (only the sign bit set) is read as `0`,
+/// and `0` is always written as positive zero. The smallest value of the signed type, e.g. [i16::MIN],
+/// can't be represented and throws an [IntegerOutOfRangeError] when written, so you need to use
+/// [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) or `write_saturating` to write these fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, SignMagnitude};
+///
+/// binary_layout!(sample, BigEndian, {
+///   offset: SignMagnitude<i16> as u16,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 2];
+/// sample::offset::try_write(&mut storage, SignMagnitude(-5)).unwrap();
+/// assert_eq!([0x80, 0x05], storage);
+/// assert_eq!(SignMagnitude(-5), sample::offset::read(&storage));
+///
+/// sample::offset::write_saturating(&mut storage, SignMagnitude(i16::MIN));
+/// assert_eq!(SignMagnitude(-i16::MAX), sample::offset::read(&storage));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignMagnitude<T>(pub T);
+
+macro_rules! impl_sign_magnitude {
+    ($unsigned: ty, $signed: ty) => {
+        impl LayoutAs<$unsigned> for SignMagnitude<$signed> {
+            type ReadError = Infallible;
+            type WriteError = IntegerOutOfRangeError;
+
+            #[inline]
+            fn try_read(v: $unsigned) -> Result<Self, Infallible> {
+                const SIGN_BIT: $unsigned = 1 << (<$unsigned>::BITS - 1);
+                // The magnitude has one bit less than the signed type, so it always fits
+                let magnitude = (v & !SIGN_BIT) as $signed;
+                if v & SIGN_BIT == 0 {
+                    Ok(SignMagnitude(magnitude))
+                } else {
+                    Ok(SignMagnitude(-magnitude))
+                }
+            }
+
+            #[inline]
+            fn try_write(v: Self) -> Result<$unsigned, IntegerOutOfRangeError> {
+                const SIGN_BIT: $unsigned = 1 << (<$unsigned>::BITS - 1);
+                if v.0 == <$signed>::MIN {
+                    Err(IntegerOutOfRangeError(()))
+                } else if v.0 < 0 {
+                    Ok(v.0.unsigned_abs() | SIGN_BIT)
+                } else {
+                    Ok(v.0 as $unsigned)
+                }
+            }
+        }
+
+        impl LayoutAsSaturating<$unsigned> for SignMagnitude<$signed> {
+            #[inline]
+            fn write_saturating(v: Self) -> $unsigned {
+                let clamped = SignMagnitude(<$signed>::max(v.0, -<$signed>::MAX));
+                <Self as LayoutAs<$unsigned>>::try_write(clamped).unwrap()
+            }
+        }
+    };
+}
+
+impl_sign_magnitude!(u8, i8);
+impl_sign_magnitude!(u16, i16);
+impl_sign_magnitude!(u32, i32);
+impl_sign_magnitude!(u64, i64);
+
+/// A signed integer stored in excess-K (biased) encoding, i.e. the value `v` is stored as the unsigned integer `v + K`.
+/// This is used, for example, for the exponent of IEEE 754 floats (excess-127 for `f32`).
+/// Use it as `ExcessK<i16, 127> as u8` in a [binary_layout!](crate::binary_layout!) layout, with any signed type for the value
+/// and any unsigned type for the stored integer.
+///
+/// The stored integer ranges from `0` to its maximum value, so the values that can be stored range from `-K` to `MAX - K`.
+/// Values out of that range throw an [IntegerOutOfRangeError] when written, and stored integers whose value doesn't fit into
+/// the signed type throw an [IntegerOutOfRangeError] when read. You need to use [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read)
+/// and [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) for these fields, or `write_saturating` to clamp values when writing.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, ExcessK};
+///
+/// binary_layout!(float32, BigEndian, {
+///   exponent: ExcessK<i16, 127> as u8,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 1];
+/// float32::exponent::try_write(&mut storage, ExcessK(-3)).unwrap();
+/// assert_eq!([124], storage);
+/// assert_eq!(ExcessK(-3), float32::exponent::try_read(&storage).unwrap());
+/// assert!(float32::exponent::try_write(&mut storage, ExcessK(129)).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExcessK<T, const K: u64>(pub T);
+
+macro_rules! impl_excess_k {
+    ($unsigned: ty, [$($signed: ty),*]) => {
+        $(
+            impl<const K: u64> LayoutAs<$unsigned> for ExcessK<$signed, K> {
+                type ReadError = IntegerOutOfRangeError;
+                type WriteError = IntegerOutOfRangeError;
+
+                #[inline]
+                fn try_read(v: $unsigned) -> Result<Self, IntegerOutOfRangeError> {
+                    let value = i128::from(v) - i128::from(K);
+                    <$signed>::try_from(value)
+                        .map(ExcessK)
+                        .map_err(|_| IntegerOutOfRangeError(()))
+                }
+
+                #[inline]
+                fn try_write(v: Self) -> Result<$unsigned, IntegerOutOfRangeError> {
+                    let stored = i128::from(v.0) + i128::from(K);
+                    <$unsigned>::try_from(stored).map_err(|_| IntegerOutOfRangeError(()))
+                }
+            }
+
+            impl<const K: u64> LayoutAsSaturating<$unsigned> for ExcessK<$signed, K> {
+                #[inline]
+                fn write_saturating(v: Self) -> $unsigned {
+                    let stored = i128::from(v.0) + i128::from(K);
+                    if stored < 0 {
+                        0
+                    } else {
+                        <$unsigned>::try_from(stored).unwrap_or(<$unsigned>::MAX)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_excess_k!(u8, [i8, i16, i32, i64]);
+impl_excess_k!(u16, [i8, i16, i32, i64]);
+impl_excess_k!(u32, [i8, i16, i32, i64]);
+impl_excess_k!(u64, [i8, i16, i32, i64]);
+
+/// An unsigned integer stored with its bits in reverse order, e.g. for serial protocols that transmit bytes least significant bit first.
+/// Use it as `BitReversed<u8> as u8` in a [binary_layout!](crate::binary_layout!) layout, with the same integer type on both sides.
+///
+/// The bits of the whole integer are reversed, see [u16::reverse_bits], and the result is stored in the byte order of the layout.
+/// Reversing twice gives the original value, and every value can be represented, so reading and writing can't fail.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, BitReversed};
+///
+/// binary_layout!(frame, BigEndian, {
+///   command: BitReversed<u8> as u8,
+///   address: BitReversed<u16> as u16,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 3];
+/// frame::command::write(&mut storage, BitReversed(0b0000_0001));
+/// frame::address::write(&mut storage, BitReversed(0x0003));
+/// assert_eq!([0b1000_0000, 0xC0, 0x00], storage);
+/// assert_eq!(BitReversed(0x0003), frame::address::read(&storage));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitReversed<T>(pub T);
+
+macro_rules! impl_bit_reversed {
+    ($($type: ty),*) => {
+        $(
+            impl LayoutAs<$type> for BitReversed<$type> {
+                type ReadError = Infallible;
+                type WriteError = Infallible;
+
+                #[inline]
+                fn try_read(v: $type) -> Result<Self, Infallible> {
+                    Ok(BitReversed(v.reverse_bits()))
+                }
+
+                #[inline]
+                fn try_write(v: Self) -> Result<$type, Infallible> {
+                    Ok(v.0.reverse_bits())
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_reversed!(u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::WrappedFieldError;
+
+    binary_layout!(layout, LittleEndian, {
+        small: SignMagnitude<i8> as u8,
+        large: SignMagnitude<i64> as u64,
+        exponent: ExcessK<i16, 127> as u8,
+        narrow: ExcessK<i8, 200> as u16,
+    });
+
+    binary_layout!(reversed, BigEndian, {
+        byte: BitReversed<u8> as u8,
+        word: BitReversed<u16> as u16,
+        long: BitReversed<u64> as u64,
+    });
+
+    #[test]
+    fn sign_magnitude_read() {
+        let cases: [(u8, i8); 6] = [
+            (0x00, 0),
+            (0x80, 0),
+            (0x01, 1),
+            (0x81, -1),
+            (0x7F, 127),
+            (0xFF, -127),
+        ];
+        for (stored, value) in cases {
+            assert_eq!(SignMagnitude(value), layout::small::read(&[stored; 19]));
+        }
+        let mut storage = [0; 19];
+        storage[1..9].copy_from_slice(&0x8000_0000_0000_0000u64.to_le_bytes());
+        assert_eq!(SignMagnitude(0), layout::large::read(&storage));
+        storage[1..9].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(SignMagnitude(-i64::MAX), layout::large::read(&storage));
+    }
+
+    #[test]
+    fn sign_magnitude_write() {
+        let mut storage = [0; 19];
+        for value in [0, 1, -1, 127, -127, 42, -42] {
+            layout::small::try_write(&mut storage, SignMagnitude(value)).unwrap();
+            assert_eq!(SignMagnitude(value), layout::small::read(&storage));
+        }
+        layout::small::try_write(&mut storage, SignMagnitude(-0)).unwrap();
+        assert_eq!(0x00, storage[0]);
+        layout::small::try_write(&mut storage, SignMagnitude(-127)).unwrap();
+        assert_eq!(0xFF, storage[0]);
+
+        assert!(matches!(
+            layout::small::try_write(&mut storage, SignMagnitude(i8::MIN)),
+            Err(WrappedFieldError::LayoutAsError(IntegerOutOfRangeError(())))
+        ));
+        layout::small::write_saturating(&mut storage, SignMagnitude(i8::MIN));
+        assert_eq!(0xFF, storage[0]);
+        layout::large::write_saturating(&mut storage, SignMagnitude(i64::MIN));
+        assert_eq!(SignMagnitude(-i64::MAX), layout::large::read(&storage));
+    }
+
+    #[test]
+    fn excess_k_bias_boundaries() {
+        let mut storage = [0; 19];
+        let cases: [(u8, i16); 4] = [(0, -127), (127, 0), (128, 1), (255, 128)];
+        for (stored, value) in cases {
+            storage[17] = 0xAA;
+            storage[9] = stored;
+            assert_eq!(
+                ExcessK(value),
+                layout::exponent::try_read(&storage).unwrap()
+            );
+            layout::exponent::try_write(&mut storage, ExcessK(value)).unwrap();
+            assert_eq!(stored, storage[9]);
+            assert_eq!(0xAA, storage[17]);
+        }
+        assert!(layout::exponent::try_write(&mut storage, ExcessK(-128)).is_err());
+        assert!(layout::exponent::try_write(&mut storage, ExcessK(129)).is_err());
+        layout::exponent::write_saturating(&mut storage, ExcessK(-1000));
+        assert_eq!(0, storage[9]);
+        layout::exponent::write_saturating(&mut storage, ExcessK(1000));
+        assert_eq!(255, storage[9]);
+    }
+
+    #[test]
+    fn excess_k_value_out_of_range() {
+        // excess-200 in a u16 stores values from -200 to 65335, but an i8 only holds -128 to 127
+        let mut storage = [0; 19];
+        layout::narrow::try_write(&mut storage, ExcessK(-128)).unwrap();
+        assert_eq!(72, u16::from_le_bytes([storage[10], storage[11]]));
+        assert_eq!(ExcessK(-128), layout::narrow::try_read(&storage).unwrap());
+        layout::narrow::try_write(&mut storage, ExcessK(127)).unwrap();
+        assert_eq!(327, u16::from_le_bytes([storage[10], storage[11]]));
+
+        storage[10..12].copy_from_slice(&71u16.to_le_bytes());
+        assert!(matches!(
+            layout::narrow::try_read(&storage),
+            Err(WrappedFieldError::LayoutAsError(IntegerOutOfRangeError(())))
+        ));
+        storage[10..12].copy_from_slice(&328u16.to_le_bytes());
+        assert!(layout::narrow::try_read(&storage).is_err());
+    }
+
+    #[test]
+    fn bit_reversed_read_write() {
+        let mut storage = [0; 11];
+        reversed::byte::write(&mut storage, BitReversed(0b1100_1010));
+        reversed::word::write(&mut storage, BitReversed(0x1234));
+        reversed::long::write(&mut storage, BitReversed(1));
+        assert_eq!(0b0101_0011, storage[0]);
+        assert_eq!([0x2C, 0x48], storage[1..3]);
+        assert_eq!([0x80, 0, 0, 0, 0, 0, 0, 0], storage[3..]);
+        assert_eq!(BitReversed(0b1100_1010), reversed::byte::read(&storage));
+        assert_eq!(BitReversed(0x1234), reversed::word::read(&storage));
+        assert_eq!(BitReversed(1), reversed::long::read(&storage));
+    }
+
+    #[test]
+    fn bit_reversed_twice_is_identity() {
+        for value in 0..=u8::MAX {
+            let once = <BitReversed<u8> as LayoutAs<u8>>::try_write(BitReversed(value)).unwrap();
+            let twice = <BitReversed<u8> as LayoutAs<u8>>::try_write(BitReversed(once)).unwrap();
+            assert_eq!(value, twice);
+            assert_eq!(
+                BitReversed(value),
+                <BitReversed<u8> as LayoutAs<u8>>::try_read(once).unwrap()
+            );
+        }
+        for value in [0, 1, 0x1234, 0x8000, u16::MAX] {
+            let once = <BitReversed<u16> as LayoutAs<u16>>::try_write(BitReversed(value)).unwrap();
+            let twice = <BitReversed<u16> as LayoutAs<u16>>::try_write(BitReversed(once)).unwrap();
+            assert_eq!(value, twice);
+        }
+    }
+}
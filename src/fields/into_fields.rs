@@ -0,0 +1,326 @@
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8,
+};
+use core::str::Utf8Error;
+
+use super::bits::{BitOrder, Bits};
+use super::composite::{CompositeField, LayoutAsComposite};
+use super::layout_as_array::{ElementReadError, LayoutAsArray};
+use super::packed_array::PackedArray;
+use super::pascal_string::{PascalString, PascalStringLength};
+use super::primitive::{
+    ComputedLength, ComputedSlice, FieldCopyAccess, FieldReadExt, FieldSliceAccess,
+    NonZeroIsZeroError, PrimitiveField,
+};
+use super::utf8::Utf8;
+use super::wrapped::WrappedField;
+use super::{Field, FieldByteLen};
+use crate::endianness::Endianness;
+use crate::LayoutAs;
+
+/// Internal trait, don't use!
+/// Used by the `read_fields` function generated by [binary_layout!](crate::binary_layout!) to decode
+/// a field into a plain value that doesn't borrow the storage.
+/// - Integer and float fields are read as their value, since reading them can't fail.
+/// - Other fields that are read by copying, e.g. `NonZeroU32` fields or custom types with `as`, are read as the [Result] of [FieldCopyAccess::try_read].
+/// - Byte arrays are copied into a `Vec<u8>`, `Utf8<N>` and `PascalString<L>` fields into a [String] and arrays of custom types into a `Vec`.
+/// - Nested layouts are decoded into the `Fields` struct of the nested layout.
+pub trait FieldValue: Field {
+    /// Internal type, don't use!
+    type Value;
+
+    /// Internal function, don't use!
+    fn value(storage: &[u8]) -> Self::Value;
+}
+
+/// Internal trait, don't use!
+/// The [binary_layout!](crate::binary_layout!) macro implements this for the `NestedView` marker type of each layout
+/// so that nested layouts can be decoded into the `Fields` struct of the nested layout.
+pub trait NestedFields {
+    /// Internal type, don't use!
+    type Fields;
+
+    /// Internal function, don't use!
+    /// The storage only contains the space of the nested layout.
+    fn fields(storage: &[u8]) -> Self::Fields;
+}
+
+macro_rules! impl_infallible_value {
+    ($($type: ty),*) => {
+        $(
+            impl<E: Endianness, const OFFSET_: usize> FieldValue for PrimitiveField<$type, E, OFFSET_> {
+                type Value = $type;
+
+                #[inline]
+                fn value(storage: &[u8]) -> $type {
+                    Self::read(storage)
+                }
+            }
+        )*
+    };
+}
+
+impl_infallible_value!(
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    f32,
+    f64,
+    ()
+);
+
+#[cfg(feature = "half")]
+impl_infallible_value!(half::f16, half::bf16);
+
+macro_rules! impl_nonzero_value {
+    ($($type: ty),*) => {
+        $(
+            impl<E: Endianness, const OFFSET_: usize> FieldValue for PrimitiveField<$type, E, OFFSET_> {
+                type Value = Result<$type, NonZeroIsZeroError>;
+
+                #[inline]
+                fn value(storage: &[u8]) -> Self::Value {
+                    Self::try_read(storage)
+                }
+            }
+        )*
+    };
+}
+
+impl_nonzero_value!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128
+);
+
+impl<U, T: LayoutAs<U>, F: FieldCopyAccess<HighLevelType = U>> FieldValue for WrappedField<U, T, F>
+where
+    Self: FieldCopyAccess,
+{
+    type Value =
+        Result<<Self as FieldCopyAccess>::HighLevelType, <Self as FieldCopyAccess>::ReadError>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Self::Value {
+        Self::try_read(storage)
+    }
+}
+
+impl<
+        A,
+        B,
+        T: LayoutAsComposite<A, B>,
+        F1: FieldCopyAccess<HighLevelType = A>,
+        F2: FieldCopyAccess<HighLevelType = B>,
+    > FieldValue for CompositeField<A, B, T, F1, F2>
+where
+    Self: FieldCopyAccess,
+{
+    type Value =
+        Result<<Self as FieldCopyAccess>::HighLevelType, <Self as FieldCopyAccess>::ReadError>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Self::Value {
+        Self::try_read(storage)
+    }
+}
+
+impl<E: Endianness, const OFFSET_: usize> FieldValue for PrimitiveField<[u8], E, OFFSET_> {
+    type Value = Vec<u8>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Vec<u8> {
+        Self::data(storage).to_vec()
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldValue
+    for PrimitiveField<[u8; N], E, OFFSET_>
+{
+    type Value = Vec<u8>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Vec<u8> {
+        Self::data(storage).to_vec()
+    }
+}
+
+impl<L: ComputedLength, E: Endianness, const OFFSET_: usize> FieldValue
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    type Value = Vec<u8>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Vec<u8> {
+        Self::data(storage).to_vec()
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldValue
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    type Value = Result<String, Utf8Error>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Self::Value {
+        Self::try_as_str(storage).map(String::from)
+    }
+}
+
+impl<L: PascalStringLength, E: Endianness, const OFFSET_: usize> FieldValue
+    for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    type Value = Result<String, Utf8Error>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Self::Value {
+        Self::try_as_str(storage).map(String::from)
+    }
+}
+
+impl<
+        O: BitOrder,
+        E: Endianness,
+        const BYTES: usize,
+        const START: usize,
+        const LEN: usize,
+        const OFFSET_: usize,
+    > FieldValue for PrimitiveField<Bits<O, BYTES, START, LEN>, E, OFFSET_>
+{
+    type Value = u64;
+
+    #[inline]
+    fn value(storage: &[u8]) -> u64 {
+        Self::read(storage)
+    }
+}
+
+impl<E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize> FieldValue
+    for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    type Value = Vec<u16>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Vec<u16> {
+        (0..N).map(|index| Self::get(storage, index)).collect()
+    }
+}
+
+impl<U, T: LayoutAs<U>, E: Endianness, const N: usize, const OFFSET_: usize> FieldValue
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    Self: Field,
+    PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U>,
+{
+    type Value = Result<Vec<T>, ElementReadError<U, T, E>>;
+
+    #[inline]
+    fn value(storage: &[u8]) -> Self::Value {
+        (0..N).map(|index| Self::try_get(storage, index)).collect()
+    }
+}
+
+impl<N: NestedFields, E: Endianness, const OFFSET_: usize> FieldValue
+    for PrimitiveField<N, E, OFFSET_>
+where
+    Self: FieldByteLen,
+{
+    type Value = N::Fields;
+
+    #[inline]
+    fn value(storage: &[u8]) -> N::Fields {
+        let len = Self::byte_len(storage);
+        N::fields(&storage[Self::OFFSET..(Self::OFFSET + len)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::LayoutAs;
+    use core::convert::Infallible;
+    use core::num::NonZeroU16;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Id(u16);
+
+    impl LayoutAs<u16> for Id {
+        type ReadError = Infallible;
+        type WriteError = Infallible;
+
+        fn try_read(v: u16) -> Result<Self, Infallible> {
+            Ok(Id(v))
+        }
+
+        fn try_write(v: Self) -> Result<u16, Infallible> {
+            Ok(v.0)
+        }
+    }
+
+    binary_layout!(#[derive(Fields)] header, BigEndian, {
+        a: u8,
+        b: [u8; 2],
+    });
+
+    binary_layout!(#[derive(Fields)] layout, BigEndian, {
+        int: u16,
+        nonzero: NonZeroU16,
+        id: Id as u16,
+        ids: [Id; 2] as u16,
+        name: Utf8<4>,
+        packed: PackedArray<4, 2>,
+        nested: header::NestedView,
+        len: u8,
+        computed: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(#[derive(Fields)] open_ended, LittleEndian, {
+        int: i16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn read_fields() {
+        let storage = vec![
+            0, 1, 0, 0, 0, 2, 0, 3, 0, 4, b'a', b'b', 0, 0, 0x12, 5, 6, 7, 2, 8, 9,
+        ];
+        let fields = layout::read_fields(&storage);
+        assert_eq!(1, fields.int);
+        assert!(fields.nonzero.is_err());
+        assert_eq!(Id(2), fields.id.unwrap());
+        assert_eq!(vec![Id(3), Id(4)], fields.ids.unwrap());
+        assert_eq!("ab\0\0", fields.name.unwrap());
+        assert_eq!(vec![1, 2], fields.packed);
+        assert_eq!(5, fields.nested.a);
+        assert_eq!(vec![6, 7], fields.nested.b);
+        assert_eq!(2, fields.len);
+        assert_eq!(vec![8, 9], fields.computed);
+
+        let fields = open_ended::read_fields(&[0xFF, 0xFF, 10, 11]);
+        assert_eq!(-1, fields.int);
+        assert_eq!(vec![10, 11], fields.tail);
+    }
+
+    #[test]
+    fn read_fields_destructuring() {
+        let storage: &[u8] = &[1, 2, 3];
+        let header::Fields { a, b } = header::read_fields(storage);
+        assert_eq!(1, a);
+        assert_eq!(vec![2, 3], b);
+    }
+}
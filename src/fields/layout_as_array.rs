@@ -0,0 +1,370 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use super::primitive::{FieldCopyAccess, FieldSliceAccess, FieldView, PrimitiveField};
+use super::wrapped::{LayoutAs, WrappedFieldError};
+use super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::Endianness;
+
+/// Field type `[T; N] as U`:
+/// This field type stores `N` values of a custom type `T` back to back, each of them stored as the primitive type `U`
+/// using the [LayoutAs] implementation of `T`, e.g. an array of typed identifiers stored as [u32].
+/// The field takes up `N` times the size of `U` and each element is stored with the endianness of the layout.
+///
+/// Elements are accessed by index with [try_get](PrimitiveField::try_get) and [try_set](PrimitiveField::try_set),
+/// or with [get](PrimitiveField::get) and [set](PrimitiveField::set) if the conversions can't fail.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutAs};
+/// use core::convert::Infallible;
+///
+/// #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// pub struct UserId(u32);
+///
+/// impl LayoutAs<u32> for UserId {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///
+///   fn try_read(v: u32) -> Result<UserId, Infallible> {
+///     Ok(UserId(v))
+///   }
+///
+///   fn try_write(v: UserId) -> Result<u32, Infallible> {
+///     Ok(v.0)
+///   }
+/// }
+///
+/// binary_layout!(group, BigEndian, {
+///   num_members: u8,
+///   members: [UserId; 4] as u32,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 17];
+/// group::members::set(&mut storage, 1, UserId(0x0102));
+/// assert_eq!(UserId(0x0102), group::members::get(&storage, 1));
+/// assert_eq!([0, 0, 0, 1, 2], storage[4..9]);
+///
+/// let view = group::View::new(&storage);
+/// assert_eq!(UserId(0), view.members().get(0));
+/// # }
+/// ```
+pub struct LayoutAsArray<U, T, const N: usize> {
+    _p1: PhantomData<U>,
+    _p2: PhantomData<T>,
+}
+
+// The errors returned when reading or writing an element of a `[T; N] as U` field
+pub(crate) type ElementReadError<U, T, E> = WrappedFieldError<
+    <PrimitiveField<U, E, 0> as FieldCopyAccess>::ReadError,
+    <T as LayoutAs<U>>::ReadError,
+>;
+pub(crate) type ElementWriteError<U, T, E> = WrappedFieldError<
+    <PrimitiveField<U, E, 0> as FieldCopyAccess>::WriteError,
+    <T as LayoutAs<U>>::WriteError,
+>;
+
+impl<U, T, E: Endianness, const N: usize, const OFFSET_: usize>
+    PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    T: LayoutAs<U>,
+    PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U>,
+{
+    const ELEMENT_SIZE: usize = match <PrimitiveField<U, E, 0> as Field>::SIZE {
+        Some(size) => size,
+        None => {
+            panic!("Error: The elements of a `[T; N] as U` field need to have a static size");
+        }
+    };
+
+    /// The number of elements in the array
+    pub const LEN: usize = N;
+
+    /// Read the element with the given index using the [Field] API and convert it into `T`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`.
+    #[inline]
+    pub fn try_get(storage: &[u8], index: usize) -> Result<T, ElementReadError<U, T, E>> {
+        let value = PrimitiveField::<U, E, 0>::try_read(Self::element(storage, index))
+            .map_err(WrappedFieldError::PrimitiveAccessError)?;
+        <T as LayoutAs<U>>::try_read(value).map_err(WrappedFieldError::LayoutAsError)
+    }
+
+    /// Convert `value` into `U` and write it to the element with the given index using the [Field] API.
+    /// The other elements are left unchanged.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`.
+    #[inline]
+    pub fn try_set(
+        storage: &mut [u8],
+        index: usize,
+        value: T,
+    ) -> Result<(), ElementWriteError<U, T, E>> {
+        let value =
+            <T as LayoutAs<U>>::try_write(value).map_err(WrappedFieldError::LayoutAsError)?;
+        PrimitiveField::<U, E, 0>::try_write(Self::element_mut(storage, index), value)
+            .map_err(WrappedFieldError::PrimitiveAccessError)
+    }
+
+    /// Read the element with the given index like [try_get](PrimitiveField::try_get).
+    /// This is only available if neither reading `U` nor converting it into `T` can fail.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`.
+    #[inline]
+    pub fn get(storage: &[u8], index: usize) -> T
+    where
+        T: LayoutAs<U, ReadError = Infallible>,
+        PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U, ReadError = Infallible>,
+    {
+        match Self::try_get(storage, index) {
+            Ok(value) => value,
+            Err(WrappedFieldError::PrimitiveAccessError(infallible)) => match infallible {},
+            Err(WrappedFieldError::LayoutAsError(infallible)) => match infallible {},
+        }
+    }
+
+    /// Write the element with the given index like [try_set](PrimitiveField::try_set).
+    /// This is only available if neither converting `T` into `U` nor writing `U` can fail.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`.
+    #[inline]
+    pub fn set(storage: &mut [u8], index: usize, value: T)
+    where
+        T: LayoutAs<U, WriteError = Infallible>,
+        PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U, WriteError = Infallible>,
+    {
+        match Self::try_set(storage, index, value) {
+            Ok(()) => (),
+            Err(WrappedFieldError::PrimitiveAccessError(infallible)) => match infallible {},
+            Err(WrappedFieldError::LayoutAsError(infallible)) => match infallible {},
+        }
+    }
+
+    #[inline(always)]
+    fn element_start(index: usize) -> usize {
+        assert!(
+            index < N,
+            "Index {} is out of bounds for an array with {} elements",
+            index,
+            N
+        );
+        OFFSET_ + index * Self::ELEMENT_SIZE
+    }
+
+    #[inline(always)]
+    fn element(storage: &[u8], index: usize) -> &[u8] {
+        let start = Self::element_start(index);
+        &storage[start..(start + Self::ELEMENT_SIZE)]
+    }
+
+    #[inline(always)]
+    fn element_mut(storage: &mut [u8], index: usize) -> &mut [u8] {
+        let start = Self::element_start(index);
+        &mut storage[start..(start + Self::ELEMENT_SIZE)]
+    }
+}
+
+impl<'a, U, T, E: Endianness, const N: usize, const OFFSET_: usize> FieldSliceAccess<'a>
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    type SliceType = &'a [u8];
+    type MutSliceType = &'a mut [u8];
+
+    /// Borrow the raw bytes of the field with read access using the [Field] API.
+    /// See also [FieldSliceAccess::data].
+    #[inline(always)]
+    fn data(storage: &'a [u8]) -> &'a [u8] {
+        &storage[Self::OFFSET..(Self::OFFSET + <Self as FieldByteLen>::MIN_LEN)]
+    }
+
+    /// Borrow the raw bytes of the field with write access using the [Field] API.
+    /// See also [FieldSliceAccess::data_mut].
+    #[inline(always)]
+    fn data_mut(storage: &'a mut [u8]) -> &'a mut [u8] {
+        &mut storage[Self::OFFSET..(Self::OFFSET + <Self as FieldByteLen>::MIN_LEN)]
+    }
+}
+
+impl<U, T, E: Endianness, const N: usize, const OFFSET_: usize> Field
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = match <PrimitiveField<U, E, 0> as Field>::SIZE {
+        Some(size) => Some(size * N),
+        None => None,
+    };
+}
+
+impl<U, T, E: Endianness, const N: usize, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    #[inline(always)]
+    fn byte_len(_storage: &[u8]) -> usize {
+        Self::MIN_LEN
+    }
+}
+
+impl<'a, U, T, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, U, T, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, U, T, E: Endianness, const N: usize, const OFFSET_: usize>
+    StorageIntoFieldView<S> for PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>
+where
+    PrimitiveField<U, E, 0>: Field,
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use core::num::NonZeroU16;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Id(u16);
+
+    impl LayoutAs<u16> for Id {
+        type ReadError = Infallible;
+        type WriteError = Infallible;
+
+        fn try_read(v: u16) -> Result<Self, Infallible> {
+            Ok(Id(v))
+        }
+
+        fn try_write(v: Self) -> Result<u16, Infallible> {
+            Ok(v.0)
+        }
+    }
+
+    // An even number, stored as its half in a non-zero integer
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Even(u16);
+
+    impl LayoutAs<NonZeroU16> for Even {
+        type ReadError = Infallible;
+        type WriteError = &'static str;
+
+        fn try_read(v: NonZeroU16) -> Result<Self, Infallible> {
+            Ok(Even(v.get() * 2))
+        }
+
+        fn try_write(v: Self) -> Result<NonZeroU16, &'static str> {
+            if v.0 % 2 == 0 {
+                NonZeroU16::new(v.0 / 2).ok_or("zero")
+            } else {
+                Err("odd")
+            }
+        }
+    }
+
+    binary_layout!(big, BigEndian, {
+        header: u8,
+        ids: [Id; 3] as u16,
+        evens: [Even; 2] as NonZeroU16,
+        footer: u8,
+    });
+
+    binary_layout!(little, LittleEndian, {
+        ids: [Id; 3] as u16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, big::ids::OFFSET);
+        assert_eq!(Some(6), big::ids::SIZE);
+        assert_eq!(3, big::ids::LEN);
+        assert_eq!(7, big::evens::OFFSET);
+        assert_eq!(Some(4), big::evens::SIZE);
+        assert_eq!(11, big::footer::OFFSET);
+        assert_eq!(6, little::tail::OFFSET);
+    }
+
+    #[test]
+    fn infallible() {
+        let mut storage = [0; 12];
+        big::ids::set(&mut storage, 0, Id(0x0102));
+        big::ids::set(&mut storage, 2, Id(0x0304));
+        assert_eq!([0, 1, 2, 0, 0, 3, 4, 0], storage[..8]);
+        assert_eq!(Id(0x0304), big::ids::get(&storage, 2));
+        assert_eq!(&[1, 2, 0, 0, 3, 4], big::ids::data(&storage));
+
+        let mut storage = [0; 6];
+        let mut view = little::View::new(&mut storage);
+        view.ids_mut().set(1, Id(0x0102));
+        assert_eq!(Id(0x0102), view.ids().get(1));
+        assert_eq!([0, 0, 2, 1, 0, 0], storage);
+    }
+
+    #[test]
+    fn fallible() {
+        let mut storage = [0; 12];
+        assert!(matches!(
+            big::evens::try_get(&storage, 0),
+            Err(WrappedFieldError::PrimitiveAccessError(NonZeroIsZeroError(
+                _
+            )))
+        ));
+        big::evens::try_set(&mut storage, 1, Even(6)).unwrap();
+        assert_eq!([0, 3], storage[9..11]);
+        assert_eq!(Even(6), big::evens::try_get(&storage, 1).unwrap());
+        assert!(matches!(
+            big::evens::try_set(&mut storage, 1, Even(5)),
+            Err(WrappedFieldError::LayoutAsError("odd"))
+        ));
+
+        let mut view = big::View::new(&mut storage);
+        view.evens_mut().try_set(0, Even(2)).unwrap();
+        assert_eq!(Even(2), view.evens().try_get(0).unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 3 is out of bounds for an array with 3 elements")]
+    fn out_of_bounds() {
+        big::ids::get(&[0; 12], 3);
+    }
+}
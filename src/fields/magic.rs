@@ -0,0 +1,143 @@
+use core::fmt::Debug;
+
+use super::primitive::FieldReadExt;
+use super::{Field, FieldByteLen};
+
+/// This error is thrown by [FieldMagic::check] and [FieldMagic::check_bytes] when a field doesn't hold the expected magic number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadMagicError<T> {
+    /// The magic number the field should hold
+    pub expected: T,
+    /// The value the field holds
+    pub actual: T,
+}
+
+impl<T: Debug> core::fmt::Display for BadMagicError<T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            fmt,
+            "BadMagicError: expected magic number {:?} but found {:?}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> std::error::Error for BadMagicError<T> {}
+
+/// This extension trait allows checking that a field holds a magic number, which is usually the first step of parsing a file or message.
+///
+/// - [FieldMagic::check] reads a field that can be read infallibly, e.g. an integer field, and compares it against the expected value.
+/// - [FieldMagic::check_bytes] compares the bytes of a field as they're stored, e.g. of a byte array field like `[u8; 4]`, against the expected bytes.
+///
+/// Both return a [BadMagicError] holding the expected and the actual value if they differ. A layout can also declare the magic number
+/// of a field with a `#[magic(...)]` attribute, see [binary_layout!](crate::binary_layout#magic-numbers).
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, BadMagicError, FieldMagic};
+///
+/// binary_layout!(png_chunk, BigEndian, {
+///   length: u32,
+///   chunk_type: [u8; 4],
+/// });
+///
+/// binary_layout!(elf_ident, LittleEndian, {
+///   magic: u32,
+///   class: u8,
+/// });
+///
+/// # fn main() {
+/// let storage = [0, 0, 0, 13, b'I', b'H', b'D', b'R'];
+/// assert_eq!(Ok(()), png_chunk::chunk_type::check_bytes(&storage, b"IHDR"));
+/// assert_eq!(
+///     Err(BadMagicError { expected: &b"IEND"[..], actual: &b"IHDR"[..] }),
+///     png_chunk::chunk_type::check_bytes(&storage, b"IEND"),
+/// );
+///
+/// let storage = [0x7F, b'E', b'L', b'F', 2];
+/// assert_eq!(Ok(()), elf_ident::magic::check(&storage, 0x464C_457F));
+/// assert_eq!(
+///     Err(BadMagicError { expected: 0xCAFE_BABE, actual: 0x464C_457F }),
+///     elf_ident::magic::check(&storage, 0xCAFE_BABE),
+/// );
+/// # }
+/// ```
+pub trait FieldMagic: Field {
+    /// Read the field and return an error if it doesn't hold `expected`.
+    #[inline]
+    fn check(
+        storage: &[u8],
+        expected: <Self as FieldReadExt>::HighLevelType,
+    ) -> Result<(), BadMagicError<<Self as FieldReadExt>::HighLevelType>>
+    where
+        Self: FieldReadExt,
+        <Self as FieldReadExt>::HighLevelType: PartialEq,
+    {
+        let actual = Self::read(storage);
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(BadMagicError { expected, actual })
+        }
+    }
+
+    /// Compare the bytes of the field, as they're stored, against `expected` and return an error if they differ.
+    /// For fields with a dynamic size, this compares the bytes the field currently takes up, so their length has to match as well.
+    #[inline]
+    fn check_bytes<'a>(storage: &'a [u8], expected: &'a [u8]) -> Result<(), BadMagicError<&'a [u8]>>
+    where
+        Self: FieldByteLen,
+    {
+        let len = <Self as FieldByteLen>::byte_len(storage);
+        let actual = &storage[Self::OFFSET..(Self::OFFSET + len)];
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(BadMagicError { expected, actual })
+        }
+    }
+}
+
+impl<F: Field> FieldMagic for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        magic: u16,
+        signature: [u8; 3],
+        tail: [u8],
+    });
+
+    #[test]
+    fn check() {
+        let storage = [0xFE, 0xCA, b'a', b'b', b'c', 1];
+        assert_eq!(Ok(()), layout::magic::check(&storage, 0xCAFE));
+        assert_eq!(
+            Err(BadMagicError {
+                expected: 0xFECA,
+                actual: 0xCAFE
+            }),
+            layout::magic::check(&storage, 0xFECA)
+        );
+    }
+
+    #[test]
+    fn check_bytes() {
+        let storage = [0xFE, 0xCA, b'a', b'b', b'c', 1];
+        assert_eq!(Ok(()), layout::signature::check_bytes(&storage, b"abc"));
+        assert_eq!(Ok(()), layout::magic::check_bytes(&storage, &[0xFE, 0xCA]));
+        assert_eq!(Ok(()), layout::tail::check_bytes(&storage, &[1]));
+        assert_eq!(
+            Err(BadMagicError {
+                expected: &b"abd"[..],
+                actual: &b"abc"[..]
+            }),
+            layout::signature::check_bytes(&storage, b"abd")
+        );
+        assert!(layout::tail::check_bytes(&storage, &[1, 2]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use super::{
+    primitive::{FieldReadExt, FieldSliceAccess, PrimitiveField},
+    Field,
+};
+
+/// This extension trait allows finding the smallest and the largest of the numbers stored in a byte array field,
+/// e.g. for quick statistics over an array of sensor samples.
+///
+/// Like for [FieldWriteFromIter](crate::FieldWriteFromIter), the numbers are stored back to back in a byte array field like `[u8; 16]`.
+/// [FieldMinMax::min] and [FieldMinMax::max] decode each number with the endianness of the layout while iterating over the field,
+/// so they don't need to copy the numbers into a `Vec`. They return `None` if the field doesn't hold any numbers, e.g. for an
+/// open ended byte array that is empty in the given storage.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldMinMax};
+///
+/// binary_layout!(samples, BigEndian, {
+///   count: u8,
+///   values: [u8],
+/// });
+///
+/// # fn main() {
+/// let storage = [3, 0x01, 0x00, 0xFF, 0x38, 0x00, 0x20];
+/// assert_eq!(Some(-200), samples::values::min::<i16>(&storage));
+/// assert_eq!(Some(256), samples::values::max::<i16>(&storage));
+/// assert_eq!(None, samples::values::max::<i16>(&storage[..1]));
+/// # }
+/// ```
+pub trait FieldMinMax: Field {
+    /// Decode the numbers stored in the field as values of type `T`, using the endianness of the layout,
+    /// and return the smallest of them, or `None` if the field doesn't hold any numbers.
+    ///
+    /// If the length of the field isn't a multiple of the size of `T`, the remaining bytes at the end don't form a value and are ignored.
+    fn min<'a, T>(storage: &'a [u8]) -> Option<T>
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+        PrimitiveField<T, Self::Endian, 0>: FieldReadExt<HighLevelType = T>,
+        T: Ord,
+    {
+        reduce_values::<Self, T>(Self::data(storage).as_ref(), Ord::min)
+    }
+
+    /// Decode the numbers stored in the field as values of type `T`, using the endianness of the layout,
+    /// and return the largest of them, or `None` if the field doesn't hold any numbers.
+    ///
+    /// If the length of the field isn't a multiple of the size of `T`, the remaining bytes at the end don't form a value and are ignored.
+    fn max<'a, T>(storage: &'a [u8]) -> Option<T>
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+        PrimitiveField<T, Self::Endian, 0>: FieldReadExt<HighLevelType = T>,
+        T: Ord,
+    {
+        reduce_values::<Self, T>(Self::data(storage).as_ref(), Ord::max)
+    }
+}
+
+impl<F: Field> FieldMinMax for F {}
+
+fn reduce_values<F: Field + ?Sized, T>(data: &[u8], reduce: fn(T, T) -> T) -> Option<T>
+where
+    PrimitiveField<T, F::Endian, 0>: FieldReadExt<HighLevelType = T>,
+{
+    let element_size = <PrimitiveField<T, F::Endian, 0> as Field>::SIZE
+        .expect("Error: min and max can only read values with a static size");
+    data.chunks_exact(element_size)
+        .map(PrimitiveField::<T, F::Endian, 0>::read)
+        .reduce(reduce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(big, BigEndian, {
+        len: u8,
+        table: [u8; 9],
+        tail: [u8],
+    });
+
+    binary_layout!(little, LittleEndian, {
+        len: u8,
+        table: [u8; 9],
+        tail: [u8],
+    });
+
+    #[test]
+    fn fixed_size() {
+        let big_storage = [0, 0x00, 0x05, 0xFF, 0xFE, 0x01, 0x00, 0x00, 0x07, 0x00];
+        assert_eq!(Some(-2), big::table::min::<i16>(&big_storage));
+        assert_eq!(Some(0x0100), big::table::max::<i16>(&big_storage));
+        assert_eq!(Some(5), big::table::min::<u16>(&big_storage));
+        assert_eq!(Some(0xFFFE), big::table::max::<u16>(&big_storage));
+
+        let little_storage = [0, 0x05, 0x00, 0xFE, 0xFF, 0x00, 0x01, 0x07, 0x00, 0xFF];
+        assert_eq!(Some(-2), little::table::min::<i16>(&little_storage));
+        assert_eq!(Some(0x0100), little::table::max::<i16>(&little_storage));
+    }
+
+    #[test]
+    fn remaining_bytes_are_ignored() {
+        // The last byte of the table doesn't form a u16 value
+        let storage = [0, 1, 1, 1, 1, 1, 1, 1, 1, 0];
+        assert_eq!(Some(0x0101), big::table::min::<u16>(&storage));
+        assert_eq!(Some(0x0101_0101), big::table::max::<u32>(&storage));
+        assert_eq!(Some(0), big::table::min::<u8>(&storage));
+    }
+
+    #[test]
+    fn empty() {
+        let storage = [0; 10];
+        assert_eq!(None, big::tail::min::<u8>(&storage));
+        assert_eq!(None, little::tail::max::<u64>(&storage));
+
+        let storage = [0; 13];
+        assert_eq!(None, big::tail::min::<u32>(&storage));
+        assert_eq!(Some(0), big::tail::min::<u16>(&storage));
+    }
+}
@@ -1,9 +1,77 @@
 use super::endianness::Endianness;
 
+// Define the fallback trait of a [FieldProbe](probe::FieldProbe). It's implemented for the probe itself, i.e. behind fewer
+// references than all other traits of the probe, and its methods handle fields that can't be handled otherwise.
+macro_rules! probe_fallback {
+    ($trait: ident {
+        $(fn $method: ident(&self $(, $arg: ident: $arg_type: ty)*) -> $ret: ty $body: block)*
+    }) => {
+        /// Internal trait, don't use!
+        pub trait $trait {
+            $(
+                /// Internal function, don't use!
+                #[inline]
+                #[allow(unused_variables)]
+                fn $method(&self $(, $arg: $arg_type)*) -> $ret $body
+            )*
+        }
+
+        impl<F> $trait for $crate::fields::probe::FieldProbe<F> {}
+    };
+}
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_fill;
+pub mod bits;
 pub mod bool;
+pub mod c_strings;
 pub mod char;
+pub mod checked;
+pub mod composite;
+pub mod constant_time;
+pub mod copy_field;
+#[cfg(feature = "std")]
+pub mod cow;
+#[cfg(feature = "std")]
+pub mod debug;
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod display;
+#[cfg(feature = "std")]
+pub mod fill_from;
+#[cfg(feature = "num-rational")]
+pub mod fixed_ratio;
+pub mod flags;
+pub mod hash_values;
+pub mod int_encoding;
+#[cfg(feature = "std")]
+pub mod into_fields;
+pub mod layout_as_array;
+pub mod magic;
+pub mod min_max;
+pub mod nested;
+pub mod numeric;
+pub mod packed_array;
+#[cfg(feature = "std")]
+pub mod parse;
+pub mod pascal_string;
 pub mod primitive;
+pub mod probe;
+pub mod range;
+pub mod read_with_raw;
+pub mod scaled;
+#[cfg(feature = "std")]
+pub mod system_time;
+#[cfg(feature = "chrono")]
+pub mod timestamp;
+#[cfg(feature = "std")]
+pub mod to_vec;
+#[cfg(feature = "std")]
+pub mod translate;
+pub mod utf8;
+pub mod validate;
 pub mod wrapped;
+pub mod write_from_iter;
 
 ///
 /// A field represents one of the fields in the data layout and offers accessors
@@ -53,6 +121,33 @@ pub mod wrapped;
 /// ```
 pub trait Field {
     /// The endianness of the field. Can be [LittleEndian](crate::LittleEndian) or [BigEndian](crate::BigEndian).
+    ///
+    /// This is the endianness the field is actually stored in, so tools like hex dumpers can use `Endian::KIND` to label fields
+    /// of layouts that mix endianness by nesting layouts. A [NestedField](crate::NestedField) reports the endianness of the
+    /// nested layout, not the one of the layout it is nested in.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, EndianKind, Endianness};
+    ///
+    /// binary_layout!(le_header, LittleEndian, {
+    ///   len: u16,
+    /// });
+    /// binary_layout!(packet, BigEndian, {
+    ///   id: u32,
+    ///   #[flatten(le_header::{len})]
+    ///   header: le_header::NestedView,
+    /// });
+    ///
+    /// fn endian_kind<F: Field>() -> EndianKind {
+    ///   <F::Endian as Endianness>::KIND
+    /// }
+    ///
+    /// # fn main() {
+    /// assert_eq!(EndianKind::Big, endian_kind::<packet::id>());
+    /// assert_eq!(EndianKind::Little, endian_kind::<packet::header_len>());
+    /// # }
+    /// ```
     type Endian: Endianness;
 
     /// The offset of the field in the layout.
@@ -109,3 +204,20 @@ pub trait StorageToFieldView<S> {
     type View;
     fn view(storage: S) -> Self::View;
 }
+
+/// Internal trait. Don't use this in user code.
+/// Returns the number of bytes a field takes up in a given storage. For fields with a
+/// static size, this is the same as [Field::SIZE], but fields with a dynamic size
+/// (e.g. open ended byte arrays) compute their size based on the storage.
+#[doc(hidden)]
+pub trait FieldByteLen: Field {
+    /// The minimum number of bytes the field takes up, e.g. the length of the header
+    /// of a nested layout with a dynamic size. A storage must be at least this long
+    /// (plus the offset of the field) for [FieldByteLen::byte_len] to not panic.
+    const MIN_LEN: usize = match Self::SIZE {
+        Some(size) => size,
+        None => 0,
+    };
+
+    fn byte_len(storage: &[u8]) -> usize;
+}
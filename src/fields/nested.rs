@@ -0,0 +1,294 @@
+use core::marker::PhantomData;
+
+use super::{
+    primitive::{FieldCopyAccess, FieldSliceAccess, NestedViewInfo, PrimitiveField},
+    Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView,
+};
+use crate::endianness::Endianness;
+use crate::utils::data::Data;
+
+/// Internal trait, don't use!
+/// The [binary_layout!](crate::binary_layout!) macro implements this for the hidden marker types it generates for each field,
+/// so a [NestedField] can check that its inner path belongs to the layout nested at its outer path.
+pub trait FieldPath {
+    /// The `NestedView` of the layout the field belongs to
+    type Layout;
+    /// The field, with its offset relative to the start of that layout, or `()` if the field is disabled by a cfg predicate
+    type Field;
+}
+
+/// Internal trait, don't use!
+/// Implemented by fields that hold a nested layout, see [NestedField].
+pub trait NestedLayoutField: Field {
+    /// The `NestedView` of the nested layout
+    type Layout;
+}
+
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> NestedLayoutField
+    for PrimitiveField<N, E, OFFSET_>
+{
+    type Layout = N;
+}
+
+/// A [NestedField] is a [Field] that gives direct access to a field of a nested layout.
+///
+/// [NestedField]s are the type of the flattened aliases that the [binary_layout!](crate::binary_layout!) macro generates for
+/// fields with a `#[flatten(...)]` attribute, see [flattening nested layouts](crate::binary_layout!#flattening-nested-layouts).
+/// `Outer` is the path to the field of the outer layout holding the nested layout and `Inner` is the path to the field within
+/// the nested layout. The [OFFSET](Field::OFFSET) of a [NestedField] is the absolute offset of the inner field within the outer layout.
+///
+/// This allows accessing leaf fields of deeply nested layouts without constructing views for all the intermediate layouts.
+/// See [Field] for more info on this API.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(deep_layout, LittleEndian, {
+///   value: u16,
+/// });
+/// binary_layout!(middle_layout, LittleEndian, {
+///   flags: u8,
+///   #[flatten(deep_layout::{value})]
+///   deep: deep_layout::NestedView,
+/// });
+/// binary_layout!(my_layout, LittleEndian, {
+///   header: u32,
+///   #[flatten(middle_layout::{flags, deep_value})]
+///   middle: middle_layout::NestedView,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 1024];
+/// my_layout::middle_deep_value::write(&mut storage, 1000);
+/// assert_eq!(5, my_layout::middle_deep_value::OFFSET);
+/// assert_eq!(1000, my_layout::middle_deep_value::read(&storage));
+/// assert_eq!(1000, my_layout::View::new(&storage).middle().deep().value().read());
+/// # }
+/// ```
+pub struct NestedField<Outer: FieldPath, Inner: FieldPath> {
+    _p1: PhantomData<Outer>,
+    _p2: PhantomData<Inner>,
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> FieldPath for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+{
+    type Layout = Outer::Layout;
+    type Field = Self;
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> NestedLayoutField for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: NestedLayoutField,
+{
+    type Layout = <Inner::Field as NestedLayoutField>::Layout;
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> Field for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: Field,
+{
+    /// See [Field::Endian]
+    type Endian = <Inner::Field as Field>::Endian;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = <Outer::Field as Field>::OFFSET + <Inner::Field as Field>::OFFSET;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = <Inner::Field as Field>::SIZE;
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> FieldByteLen for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: FieldByteLen,
+{
+    const MIN_LEN: usize = <Inner::Field as FieldByteLen>::MIN_LEN;
+
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        <Inner::Field as FieldByteLen>::byte_len(storage.get(Self::outer_offset()..).unwrap_or(&[]))
+    }
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+{
+    // The offset of the nested layout within the outer layout
+    #[inline(always)]
+    fn outer_offset() -> usize {
+        <Outer::Field as Field>::OFFSET
+    }
+}
+
+impl<Outer: FieldPath, Inner: FieldPath> FieldCopyAccess for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: FieldCopyAccess,
+{
+    /// See [FieldCopyAccess::ReadError]
+    type ReadError = <Inner::Field as FieldCopyAccess>::ReadError;
+    /// See [FieldCopyAccess::WriteError]
+    type WriteError = <Inner::Field as FieldCopyAccess>::WriteError;
+    /// See [FieldCopyAccess::HighLevelType]
+    type HighLevelType = <Inner::Field as FieldCopyAccess>::HighLevelType;
+
+    #[inline(always)]
+    fn try_read(storage: &[u8]) -> Result<Self::HighLevelType, Self::ReadError> {
+        <Inner::Field as FieldCopyAccess>::try_read(&storage[Self::outer_offset()..])
+    }
+
+    #[inline(always)]
+    fn try_write(storage: &mut [u8], v: Self::HighLevelType) -> Result<(), Self::WriteError> {
+        <Inner::Field as FieldCopyAccess>::try_write(&mut storage[Self::outer_offset()..], v)
+    }
+}
+
+impl<'a, Outer: FieldPath, Inner: FieldPath> FieldSliceAccess<'a> for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: FieldSliceAccess<'a>,
+{
+    type SliceType = <Inner::Field as FieldSliceAccess<'a>>::SliceType;
+    type MutSliceType = <Inner::Field as FieldSliceAccess<'a>>::MutSliceType;
+
+    #[inline(always)]
+    fn data(storage: &'a [u8]) -> Self::SliceType {
+        <Inner::Field as FieldSliceAccess<'a>>::data(&storage[Self::outer_offset()..])
+    }
+
+    #[inline(always)]
+    fn data_mut(storage: &'a mut [u8]) -> Self::MutSliceType {
+        <Inner::Field as FieldSliceAccess<'a>>::data_mut(&mut storage[Self::outer_offset()..])
+    }
+}
+
+impl<'a, Outer: FieldPath, Inner: FieldPath> StorageToFieldView<&'a [u8]>
+    for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: StorageToFieldView<&'a [u8]>,
+{
+    type View = <Inner::Field as StorageToFieldView<&'a [u8]>>::View;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        <Inner::Field as StorageToFieldView<&'a [u8]>>::view(&storage[Self::outer_offset()..])
+    }
+}
+
+impl<'a, Outer: FieldPath, Inner: FieldPath> StorageToFieldView<&'a mut [u8]>
+    for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: StorageToFieldView<&'a mut [u8]>,
+{
+    type View = <Inner::Field as StorageToFieldView<&'a mut [u8]>>::View;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        <Inner::Field as StorageToFieldView<&'a mut [u8]>>::view(
+            &mut storage[Self::outer_offset()..],
+        )
+    }
+}
+
+impl<S: AsRef<[u8]>, Outer: FieldPath, Inner: FieldPath> StorageIntoFieldView<S>
+    for NestedField<Outer, Inner>
+where
+    Outer::Field: NestedLayoutField<Layout = Inner::Layout>,
+    Inner::Field: StorageIntoFieldView<Data<S>>,
+{
+    type View = <Inner::Field as StorageIntoFieldView<Data<S>>>::View;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        <Inner::Field as StorageIntoFieldView<Data<S>>>::into_view(
+            Data::from(storage).into_subregion(Self::outer_offset()..),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(deep_layout, LittleEndian, {
+        padding: [u8; 2],
+        copy: u32,
+    });
+    binary_layout!(inner_layout, BigEndian, {
+        copy: u32,
+        padding: [u8; 4],
+        #[flatten(deep_layout::{copy})]
+        deep: deep_layout::NestedView,
+        slice: [u8; 5],
+    });
+    binary_layout!(outer, LittleEndian, {
+        padding: [u8; 4],
+        #[flatten(inner_layout::{copy, slice, deep, deep_copy})]
+        inner: inner_layout::NestedView,
+        tail: [u8],
+    });
+    binary_layout!(open, LittleEndian, {
+        padding: [u8; 4],
+        #[flatten(tail_layout::{tail})]
+        rest: tail_layout::NestedView,
+    });
+    binary_layout!(tail_layout, LittleEndian, {
+        tail: [u8],
+    });
+
+    type Copy = outer::inner_copy;
+    type Slice = outer::inner_slice;
+    type Deep = outer::inner_deep_copy;
+    // Paths can also be nested to the left, i.e. the outer path can itself be a NestedField
+    type DeepLeft = NestedField<outer::__inner_deep__path, deep_layout::__copy__path>;
+
+    #[test]
+    fn metadata() {
+        assert_eq!(4, Copy::OFFSET);
+        assert_eq!(Some(4), Copy::SIZE);
+        assert_eq!(18, Slice::OFFSET);
+        assert_eq!(Some(5), Slice::SIZE);
+        assert_eq!(14, Deep::OFFSET);
+        assert_eq!(Some(4), Deep::SIZE);
+        assert_eq!(14, DeepLeft::OFFSET);
+        assert_eq!(Some(4), DeepLeft::SIZE);
+    }
+
+    #[test]
+    fn copy_access() {
+        let mut storage = [0; 1024];
+        Copy::write(&mut storage, 0x01020304);
+        assert_eq!(0x01020304, Copy::read(&storage));
+        assert_eq!(&[1, 2, 3, 4], &storage[4..8]);
+
+        Deep::write(&mut storage, 0x05060708);
+        assert_eq!(0x05060708, Deep::read(&storage));
+        assert_eq!(0x05060708, DeepLeft::read(&storage));
+        assert_eq!(&[8, 7, 6, 5], &storage[14..18]);
+    }
+
+    #[test]
+    fn slice_access() {
+        let mut storage = [0; 1024];
+        Slice::data_mut(&mut storage).copy_from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(&[1, 2, 3, 4, 5], Slice::data(&storage));
+        assert_eq!(&[1, 2, 3, 4, 5], &storage[18..23]);
+    }
+
+    #[test]
+    fn byte_len() {
+        let storage = [0; 1024];
+        assert_eq!(4, <Copy as FieldByteLen>::byte_len(&storage));
+        assert_eq!(
+            1024 - 4,
+            <open::rest_tail as FieldByteLen>::byte_len(&storage)
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use core::fmt::{Debug, Display};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, Sub, SubAssign};
+
+use super::primitive::{FieldReadExt, FieldWriteExt, PrimitiveField};
+use crate::endianness::Endianness;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// This trait is implemented by the integer and float fields, i.e. fields of type
+/// `u8`, `u16`, `u32`, `u64`, `u128`, `i8`, `i16`, `i32`, `i64`, `i128`, `f32` and `f64`.
+/// Other fields, e.g. `NonZeroU32` fields or custom types declared with `as`, don't implement it.
+///
+/// Generic code can take any of these fields with `F: NumericField` and use [NumericField::Number], the type the field is read as,
+/// with arithmetic operators, comparisons and [Iterator::sum], without spelling out these bounds for
+/// [FieldReadExt::HighLevelType] itself. The field can be read and written with [FieldReadExt::read] and [FieldWriteExt::write].
+/// This trait is sealed and can't be implemented outside of this crate.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, NumericField};
+///
+/// binary_layout!(sample, LittleEndian, {
+///   count: u16,
+///   temperature: f32,
+/// });
+///
+/// fn sum_field<F: NumericField>(messages: &[&[u8]]) -> F::Number {
+///   messages.iter().map(|storage| F::read(storage)).sum()
+/// }
+///
+/// # fn main() {
+/// let mut a = [0; 6];
+/// let mut b = [0; 6];
+/// sample::count::write(&mut a, 3);
+/// sample::count::write(&mut b, 4);
+/// sample::temperature::write(&mut a, 20.5);
+/// sample::temperature::write(&mut b, 21.0);
+/// assert_eq!(7, sum_field::<sample::count>(&[&a, &b]));
+/// assert_eq!(41.5, sum_field::<sample::temperature>(&[&a, &b]));
+/// # }
+/// ```
+pub trait NumericField:
+    FieldReadExt<HighLevelType = <Self as NumericField>::Number>
+    + FieldWriteExt<HighLevelType = <Self as NumericField>::Number>
+    + private::Sealed
+{
+    /// The number type the field is read as and written from, e.g. `u32` for a `u32` field
+    type Number: Copy
+        + Default
+        + PartialEq
+        + PartialOrd
+        + Debug
+        + Display
+        + Add<Output = Self::Number>
+        + Sub<Output = Self::Number>
+        + Mul<Output = Self::Number>
+        + Div<Output = Self::Number>
+        + Rem<Output = Self::Number>
+        + AddAssign
+        + SubAssign
+        + MulAssign
+        + DivAssign
+        + Sum
+        + Product;
+}
+
+macro_rules! impl_numeric_field {
+    ($($type: ty),*) => {
+        $(
+            impl<E: Endianness, const OFFSET_: usize> private::Sealed for PrimitiveField<$type, E, OFFSET_> {}
+
+            impl<E: Endianness, const OFFSET_: usize> NumericField for PrimitiveField<$type, E, OFFSET_> {
+                type Number = $type;
+            }
+        )*
+    };
+}
+
+impl_numeric_field!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, BigEndian, {
+        small: i8,
+        wide: u128,
+        float: f64,
+    });
+
+    fn max_field<F: NumericField>(messages: &[&[u8]]) -> Option<F::Number> {
+        messages
+            .iter()
+            .map(|storage| F::read(storage))
+            .fold(None, |max, value| match max {
+                Some(max) if max >= value => Some(max),
+                _ => Some(value),
+            })
+    }
+
+    fn scale_field<F: NumericField>(storage: &mut [u8], factor: F::Number) {
+        let mut value = F::read(storage);
+        value *= factor;
+        F::write(storage, value);
+    }
+
+    #[test]
+    fn generic_read() {
+        let mut a = [0; 25];
+        let mut b = [0; 25];
+        layout::small::write(&mut a, -5);
+        layout::small::write(&mut b, -7);
+        layout::wide::write(&mut a, u128::MAX);
+        layout::wide::write(&mut b, 1);
+        layout::float::write(&mut a, 0.5);
+        layout::float::write(&mut b, 1.5);
+        assert_eq!(Some(-5), max_field::<layout::small>(&[&a, &b]));
+        assert_eq!(Some(u128::MAX), max_field::<layout::wide>(&[&a, &b]));
+        assert_eq!(Some(1.5), max_field::<layout::float>(&[&a, &b]));
+        assert_eq!(None, max_field::<layout::float>(&[]));
+    }
+
+    #[test]
+    fn generic_write() {
+        let mut storage = [0; 25];
+        layout::small::write(&mut storage, -5);
+        layout::float::write(&mut storage, 0.5);
+        scale_field::<layout::small>(&mut storage, 3);
+        scale_field::<layout::float>(&mut storage, -4.0);
+        assert_eq!(-15, layout::small::read(&storage));
+        assert_eq!(-2.0, layout::float::read(&storage));
+    }
+}
@@ -0,0 +1,317 @@
+use super::primitive::{FieldSliceAccess, FieldView, PrimitiveField};
+use super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::{EndianKind, Endianness};
+
+/// Field type `PackedArray<BITS, N>`:
+/// This field type stores `N` unsigned integers with `BITS` bits each (1 to 16 bits), packed back to back
+/// without any padding, e.g. 12 bit samples as used in medical imaging. The field takes up `ceil(BITS * N / 8)` bytes.
+///
+/// Elements aren't aligned to bytes, so they're accessed by index with [get](PrimitiveField::get) and [set](PrimitiveField::set).
+/// Element `i` is stored in the bits `i * BITS` to `(i + 1) * BITS - 1` of the field, and the bit order follows the endianness of the layout:
+/// - [BigEndian](crate::BigEndian): Bits are counted from the most significant bit of each byte, and the most significant bit of an element is stored first.
+///   For 12 bit elements, element 0 is stored in byte 0 and the high nibble of byte 1, element 1 in the low nibble of byte 1 and byte 2.
+/// - [LittleEndian](crate::LittleEndian): Bits are counted from the least significant bit of each byte, and the least significant bit of an element is stored first.
+///   For 12 bit elements, element 0 is stored in byte 0 and the low nibble of byte 1, element 1 in the high nibble of byte 1 and byte 2.
+/// - [NativeEndian](crate::NativeEndian): Either of the above, depending on the endianness of the system.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(scan_line, BigEndian, {
+///   num_samples: u16,
+///   samples: PackedArray<12, 4>,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 8];
+/// scan_line::samples::set(&mut storage, 0, 0xABC);
+/// scan_line::samples::set(&mut storage, 1, 0x123);
+/// assert_eq!(0x123, scan_line::samples::get(&storage, 1));
+/// assert_eq!([0, 0, 0xAB, 0xC1, 0x23, 0, 0, 0], storage);
+///
+/// let view = scan_line::View::new(&storage);
+/// assert_eq!(0xABC, view.samples().get(0));
+/// # }
+/// ```
+pub struct PackedArray<const BITS: usize, const N: usize> {
+    _p: (),
+}
+
+impl<E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    const BYTE_LEN: usize = {
+        assert!(
+            BITS >= 1 && BITS <= 16,
+            "Error: PackedArray only supports elements with 1 to 16 bits"
+        );
+        (BITS * N + 7) / 8
+    };
+
+    /// The number of elements in the array
+    pub const LEN: usize = N;
+
+    /// Read the element with the given index using the [Field] API.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`.
+    #[inline]
+    pub fn get(storage: &[u8], index: usize) -> u16 {
+        let (window, shift) = Self::window(storage, index);
+        ((window >> shift) & Self::mask()) as u16
+    }
+
+    /// Write the element with the given index using the [Field] API. The other elements are left unchanged.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds, i.e. not less than `N`, or if `value` doesn't fit into `BITS` bits.
+    #[inline]
+    pub fn set(storage: &mut [u8], index: usize, value: u16) {
+        assert!(
+            u32::from(value) <= Self::mask(),
+            "Value {} doesn't fit into {} bits",
+            value,
+            BITS
+        );
+        let (window, shift) = Self::window(storage, index);
+        let window = (window & !(Self::mask() << shift)) | (u32::from(value) << shift);
+        let bytes = match Self::bit_order() {
+            EndianKind::Big => window.to_be_bytes(),
+            _ => window.to_le_bytes(),
+        };
+        let data = &mut storage[OFFSET_..(OFFSET_ + Self::BYTE_LEN)];
+        let start = index * BITS / 8;
+        let end = usize::min(start + 3, Self::BYTE_LEN);
+        match Self::bit_order() {
+            EndianKind::Big => data[start..end].copy_from_slice(&bytes[1..(1 + end - start)]),
+            _ => data[start..end].copy_from_slice(&bytes[..(end - start)]),
+        }
+    }
+
+    #[inline(always)]
+    fn mask() -> u32 {
+        (1 << BITS) - 1
+    }
+
+    #[inline(always)]
+    fn bit_order() -> EndianKind {
+        match E::KIND {
+            EndianKind::Native if cfg!(target_endian = "big") => EndianKind::Big,
+            EndianKind::Native => EndianKind::Little,
+            kind => kind,
+        }
+    }
+
+    // An element with up to 16 bits spans at most 3 bytes. Load these bytes into a u32 and
+    // return it with the shift needed to get the element into the least significant bits.
+    #[inline(always)]
+    fn window(storage: &[u8], index: usize) -> (u32, usize) {
+        assert!(
+            index < N,
+            "Index {} is out of bounds for a PackedArray with {} elements",
+            index,
+            N
+        );
+        let data = &storage[OFFSET_..(OFFSET_ + Self::BYTE_LEN)];
+        let start = index * BITS / 8;
+        let bit_in_byte = index * BITS % 8;
+        let mut bytes = [0; 4];
+        let end = usize::min(start + 3, Self::BYTE_LEN);
+        match Self::bit_order() {
+            EndianKind::Big => {
+                bytes[1..(1 + end - start)].copy_from_slice(&data[start..end]);
+                (u32::from_be_bytes(bytes), 24 - bit_in_byte - BITS)
+            }
+            _ => {
+                bytes[..(end - start)].copy_from_slice(&data[start..end]);
+                (u32::from_le_bytes(bytes), bit_in_byte)
+            }
+        }
+    }
+}
+
+impl<'a, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    FieldSliceAccess<'a> for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    type SliceType = &'a [u8];
+    type MutSliceType = &'a mut [u8];
+
+    /// Borrow the raw bytes of the field with read access using the [Field] API.
+    /// See also [FieldSliceAccess::data].
+    #[inline(always)]
+    fn data(storage: &'a [u8]) -> &'a [u8] {
+        &storage[Self::OFFSET..(Self::OFFSET + Self::BYTE_LEN)]
+    }
+
+    /// Borrow the raw bytes of the field with write access using the [Field] API.
+    /// See also [FieldSliceAccess::data_mut].
+    #[inline(always)]
+    fn data_mut(storage: &'a mut [u8]) -> &'a mut [u8] {
+        &mut storage[Self::OFFSET..(Self::OFFSET + Self::BYTE_LEN)]
+    }
+}
+
+impl<E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize> Field
+    for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = Some(Self::BYTE_LEN);
+}
+
+impl<E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    #[inline(always)]
+    fn byte_len(_storage: &[u8]) -> usize {
+        Self::BYTE_LEN
+    }
+}
+
+impl<'a, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    StorageToFieldView<&'a [u8]> for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    StorageToFieldView<&'a mut [u8]> for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    StorageIntoFieldView<S> for PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(big, BigEndian, {
+        header: u8,
+        samples: PackedArray<12, 5>,
+        footer: u8,
+    });
+
+    binary_layout!(little, LittleEndian, {
+        header: u8,
+        samples: PackedArray<12, 5>,
+        footer: u8,
+    });
+
+    binary_layout!(odd_sizes, LittleEndian, {
+        bits: PackedArray<1, 10>,
+        triples: PackedArray<3, 3>,
+        words: PackedArray<16, 2>,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(Some(8), big::samples::SIZE);
+        assert_eq!(9, big::footer::OFFSET);
+        assert_eq!(5, big::samples::LEN);
+        assert_eq!(Some(2), odd_sizes::bits::SIZE);
+        assert_eq!(Some(2), odd_sizes::triples::SIZE);
+        assert_eq!(Some(4), odd_sizes::words::SIZE);
+    }
+
+    #[test]
+    fn big_endian_bit_order() {
+        let mut storage = [0xFF; 10];
+        for i in 0..5 {
+            big::samples::set(&mut storage, i, 0x111 * i as u16);
+        }
+        assert_eq!(
+            [0xFF, 0x00, 0x01, 0x11, 0x22, 0x23, 0x33, 0x44, 0x4F, 0xFF],
+            storage
+        );
+        for i in 0..5 {
+            assert_eq!(0x111 * i as u16, big::samples::get(&storage, i));
+        }
+    }
+
+    #[test]
+    fn little_endian_bit_order() {
+        let mut storage = [0xFF; 10];
+        for i in 0..5 {
+            little::samples::set(&mut storage, i, 0x111 * i as u16);
+        }
+        assert_eq!(
+            [0xFF, 0x00, 0x10, 0x11, 0x22, 0x32, 0x33, 0x44, 0xF4, 0xFF],
+            storage
+        );
+        for i in 0..5 {
+            assert_eq!(0x111 * i as u16, little::samples::get(&storage, i));
+        }
+        assert_eq!(&storage[1..9], little::samples::data(&storage));
+    }
+
+    #[test]
+    fn odd_sizes() {
+        let mut storage = [0; 8];
+        for i in 0..10 {
+            odd_sizes::bits::set(&mut storage, i, (i % 3 == 0) as u16);
+        }
+        for i in 0..3 {
+            odd_sizes::triples::set(&mut storage, i, 7 - i as u16);
+        }
+        odd_sizes::words::set(&mut storage, 0, 0xFFFF);
+        odd_sizes::words::set(&mut storage, 1, 0x1234);
+        assert_eq!(
+            [0b0100_1001, 0b10, 0b0111_0111, 0b1, 0xFF, 0xFF, 0x34, 0x12],
+            storage
+        );
+        for i in 0..10 {
+            assert_eq!((i % 3 == 0) as u16, odd_sizes::bits::get(&storage, i));
+        }
+        for i in 0..3 {
+            assert_eq!(7 - i as u16, odd_sizes::triples::get(&storage, i));
+        }
+        assert_eq!(0xFFFF, odd_sizes::words::get(&storage, 0));
+        assert_eq!(0x1234, odd_sizes::words::get(&storage, 1));
+    }
+
+    #[test]
+    fn view_api() {
+        let mut storage = [0; 10];
+        let mut view = big::View::new(&mut storage);
+        view.samples_mut().set(4, 0xFED);
+        assert_eq!(0xFED, view.samples().get(4));
+        assert_eq!(0xFED, view.into_samples().get(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 5 is out of bounds for a PackedArray with 5 elements")]
+    fn index_out_of_bounds() {
+        big::samples::get(&[0; 10], 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value 4096 doesn't fit into 12 bits")]
+    fn value_too_large() {
+        big::samples::set(&mut [0; 10], 0, 0x1000);
+    }
+}
@@ -0,0 +1,119 @@
+use core::str::FromStr;
+
+use super::primitive::FieldCopyAccess;
+use super::probe::FieldProbe;
+
+/// This error is returned by the `from_fields_str` function generated by [binary_layout!](crate::binary_layout!)
+/// if the text can't be parsed into a message of the layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseFieldsError {
+    /// A comma separated part of the text isn't of the form `name=value`.
+    MissingEquals {
+        /// The part of the text without `=`
+        pair: String,
+    },
+    /// The layout doesn't have a field with this name.
+    UnknownField {
+        /// The name given in the text
+        name: String,
+    },
+    /// The field can't be parsed from text, e.g. because it is a byte array or a nested layout.
+    UnsupportedField {
+        /// The name of the field
+        name: &'static str,
+    },
+    /// The value can't be parsed into the type of the field, or the parsed value can't be written to the field.
+    InvalidValue {
+        /// The name of the field
+        name: &'static str,
+        /// The value given in the text
+        value: String,
+    },
+}
+
+impl core::fmt::Display for ParseFieldsError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingEquals { pair } => write!(fmt, "Expected name=value but got '{}'", pair),
+            Self::UnknownField { name } => {
+                write!(fmt, "The layout doesn't have a field '{}'", name)
+            }
+            Self::UnsupportedField { name } => {
+                write!(fmt, "The field '{}' can't be parsed from text", name)
+            }
+            Self::InvalidValue { name, value } => {
+                write!(fmt, "Invalid value '{}' for the field '{}'", value, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseFieldsError {}
+
+/// Internal function, don't use!
+/// Used by the `from_fields_str` function generated by [binary_layout!](crate::binary_layout!) to split the text
+/// into `name=value` pairs. `parse_field` returns `None` if the layout doesn't have a field with the given name.
+pub fn parse_fields(
+    fields: &str,
+    mut parse_field: impl FnMut(&str, &str) -> Option<Result<(), ParseFieldsError>>,
+) -> Result<(), ParseFieldsError> {
+    for pair in fields
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let (name, value) =
+            pair.split_once('=')
+                .ok_or_else(|| ParseFieldsError::MissingEquals {
+                    pair: pair.to_string(),
+                })?;
+        let (name, value) = (name.trim(), value.trim());
+        parse_field(name, value).unwrap_or_else(|| {
+            Err(ParseFieldsError::UnknownField {
+                name: name.to_string(),
+            })
+        })?;
+    }
+    Ok(())
+}
+
+// Used by the `from_fields_str` function generated by [binary_layout!](crate::binary_layout!) to parse a field
+// if its type implements [FromStr] and to reject it otherwise, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait ParseCopyField {
+    /// Internal function, don't use!
+    fn parse_into(
+        &self,
+        storage: &mut [u8],
+        name: &'static str,
+        value: &str,
+    ) -> Result<(), ParseFieldsError>;
+}
+
+impl<F: FieldCopyAccess> ParseCopyField for &FieldProbe<F>
+where
+    F::HighLevelType: FromStr,
+{
+    #[inline]
+    fn parse_into(
+        &self,
+        storage: &mut [u8],
+        name: &'static str,
+        value: &str,
+    ) -> Result<(), ParseFieldsError> {
+        let invalid_value = || ParseFieldsError::InvalidValue {
+            name,
+            value: value.to_string(),
+        };
+        let parsed = value.parse().map_err(|_| invalid_value())?;
+        F::try_write(storage, parsed).map_err(|_| invalid_value())
+    }
+}
+
+probe_fallback!(ParseOtherField {
+    fn parse_into(&self, storage: &mut [u8], name: &'static str, value: &str) -> Result<(), ParseFieldsError> {
+        Err(ParseFieldsError::UnsupportedField { name })
+    }
+});
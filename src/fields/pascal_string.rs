@@ -0,0 +1,286 @@
+use core::marker::PhantomData;
+use core::str::Utf8Error;
+
+use super::primitive::{FieldReadExt, FieldView, FieldWriteExt, PrimitiveField};
+use super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::Endianness;
+
+/// Field type `PascalString<L>`:
+/// This field type stores a UTF-8 encoded string prefixed with its length in bytes, stored as an integer of type `L`.
+/// `L` can be [u8], [u16], [u32] or [u64], and the length prefix is stored in the byte order of the layout.
+///
+/// The field takes up as many bytes as the length prefix says, so it has a dynamic size and, like open ended byte arrays,
+/// can only occur as the last field of a layout. The [Field] and [FieldView] APIs offer [try_as_str](PrimitiveField::try_as_str),
+/// [as_str](PrimitiveField::as_str) and, with the `std` feature, [to_str_lossy](PrimitiveField::to_str_lossy) to read the string,
+/// and [write_str](PrimitiveField::write_str) to write both the length prefix and the string.
+/// Like for [Utf8](crate::Utf8) fields, `try_new` checks that the field contains valid UTF-8.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(user, BigEndian, {
+///   id: u32,
+///   name: PascalString<u16>,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 16];
+/// user::name::write_str(&mut storage, "Ferris");
+/// assert_eq!([0, 6, b'F', b'e'], storage[4..8]);
+///
+/// let view = user::try_new(&storage[..user::required_len(8)]).unwrap();
+/// assert_eq!("Ferris", view.name().as_str());
+/// assert_eq!(12, user::byte_len(&storage));
+/// # }
+/// ```
+pub struct PascalString<L> {
+    _p: PhantomData<L>,
+}
+
+/// Implemented for the integer types that can be used as the length prefix of a [PascalString], i.e. [u8], [u16], [u32] and [u64].
+pub trait PascalStringLength {
+    /// The largest length that can be stored in the length prefix
+    const MAX_LEN: usize;
+
+    /// Read the length prefix from the beginning of `storage`.
+    /// Lengths that don't fit into a [usize] are returned as [usize::MAX].
+    fn read_len<E: Endianness>(storage: &[u8]) -> usize;
+
+    /// Write the length prefix to the beginning of `storage`. `len` must not be larger than [PascalStringLength::MAX_LEN].
+    fn write_len<E: Endianness>(storage: &mut [u8], len: usize);
+}
+
+macro_rules! impl_pascal_string_length {
+    ($($type: ty),*) => {
+        $(
+            impl PascalStringLength for $type {
+                const MAX_LEN: usize = <$type>::MAX as usize;
+
+                #[inline]
+                fn read_len<E: Endianness>(storage: &[u8]) -> usize {
+                    let len = PrimitiveField::<$type, E, 0>::read(storage);
+                    usize::try_from(len).unwrap_or(usize::MAX)
+                }
+
+                #[inline]
+                fn write_len<E: Endianness>(storage: &mut [u8], len: usize) {
+                    PrimitiveField::<$type, E, 0>::write(storage, len as $type)
+                }
+            }
+        )*
+    };
+}
+
+impl_pascal_string_length!(u8, u16, u32, u64);
+
+impl<L: PascalStringLength, E: Endianness, const OFFSET_: usize>
+    PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    const PREFIX_LEN: usize = core::mem::size_of::<L>();
+
+    /// Read the length of the string in bytes from the length prefix using the [Field] API.
+    #[inline]
+    pub fn len(storage: &[u8]) -> usize {
+        L::read_len::<E>(&storage[Self::OFFSET..])
+    }
+
+    /// Return whether the string is empty using the [Field] API.
+    #[inline]
+    pub fn is_empty(storage: &[u8]) -> bool {
+        Self::len(storage) == 0
+    }
+
+    #[inline]
+    fn bytes(storage: &[u8]) -> &[u8] {
+        let start = Self::OFFSET + Self::PREFIX_LEN;
+        &storage[start..(start + Self::len(storage))]
+    }
+
+    /// Borrow the string using the [Field] API, or return an error if it isn't valid UTF-8.
+    #[inline]
+    pub fn try_as_str(storage: &[u8]) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(Self::bytes(storage))
+    }
+
+    /// Borrow the string using the [Field] API.
+    ///
+    /// # Panics
+    /// Panics if the field isn't valid UTF-8. This can't happen for views created with `try_new`,
+    /// unless the storage was modified through other means than [write_str](PrimitiveField::write_str) afterwards.
+    #[inline]
+    pub fn as_str(storage: &[u8]) -> &str {
+        Self::try_as_str(storage).expect("Field doesn't contain valid UTF-8")
+    }
+
+    /// Read the string using the [Field] API, replacing invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`,
+    /// see [String::from_utf8_lossy]. This borrows the string if it is valid UTF-8.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_str_lossy(storage: &[u8]) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(Self::bytes(storage))
+    }
+
+    /// Write a string to the field using the [Field] API, setting the length prefix to its length in bytes.
+    /// The field then takes up the length prefix and the string, so the storage must be long enough for both.
+    ///
+    /// # Panics
+    /// Panics if the length of the string doesn't fit into the length prefix or if the storage is too short.
+    #[inline]
+    pub fn write_str(storage: &mut [u8], value: &str) {
+        assert!(
+            value.len() <= L::MAX_LEN,
+            "String with {} bytes is too long for a length prefix with a maximum of {}",
+            value.len(),
+            L::MAX_LEN
+        );
+        let start = Self::OFFSET + Self::PREFIX_LEN;
+        storage[start..(start + value.len())].copy_from_slice(value.as_bytes());
+        L::write_len::<E>(&mut storage[Self::OFFSET..], value.len());
+    }
+}
+
+impl<L: PascalStringLength, E: Endianness, const OFFSET_: usize> Field
+    for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = None;
+}
+
+impl<L: PascalStringLength, E: Endianness, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    const MIN_LEN: usize = Self::PREFIX_LEN;
+
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        Self::PREFIX_LEN + Self::len(storage)
+    }
+}
+
+impl<'a, L: PascalStringLength, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, L: PascalStringLength, E: Endianness, const OFFSET_: usize>
+    StorageToFieldView<&'a mut [u8]> for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, L: PascalStringLength, E: Endianness, const OFFSET_: usize>
+    StorageIntoFieldView<S> for PrimitiveField<PascalString<L>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        id: u8,
+        name: PascalString<u16>,
+    });
+
+    binary_layout!(short, BigEndian, {
+        name: PascalString<u8>,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, layout::name::OFFSET);
+        assert_eq!(None, layout::name::SIZE);
+        assert_eq!(None, layout::SIZE);
+        assert_eq!(3, layout::required_len(0));
+        assert_eq!(10, layout::required_len(9));
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut storage = [0xFF; 10];
+        layout::name::write_str(&mut storage, "héllo");
+        assert_eq!(
+            [0xFF, 6, 0, b'h', 0xC3, 0xA9, b'l', b'l', b'o', 0xFF],
+            storage
+        );
+        assert_eq!(6, layout::name::len(&storage));
+        assert!(!layout::name::is_empty(&storage));
+        assert_eq!("héllo", layout::name::as_str(&storage));
+        assert_eq!(Ok("héllo"), layout::name::try_as_str(&storage));
+
+        let view = layout::View::new(&storage);
+        assert_eq!(9, layout::byte_len(&storage));
+        assert_eq!("héllo", view.name().as_str());
+
+        short::name::write_str(&mut storage, "");
+        assert_eq!(0, storage[0]);
+        assert!(short::name::is_empty(&storage));
+        assert_eq!("", short::name::as_str(&storage));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_str_lossy() {
+        let mut storage = [0; 9];
+        layout::name::write_str(&mut storage, "héllo");
+        assert_eq!("héllo", layout::name::to_str_lossy(&storage));
+        assert_eq!("\u{FFFD}(", short::name::to_str_lossy(&[2, 0xC3, 0x28]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_through_view() {
+        let mut storage = vec![0; 8];
+        let mut view = short::View::new(&mut storage);
+        view.name_mut().write_str("abc");
+        assert_eq!(3, view.name().len());
+        assert_eq!(4, short::byte_len(&storage));
+        assert_eq!(vec![3, b'a', b'b', b'c', 0, 0, 0, 0], storage);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let storage = [2, 0xC3, 0x28];
+        assert!(short::name::try_as_str(&storage).is_err());
+        assert_eq!(
+            Err(LayoutError::InvalidUtf8 { offset: 0 }),
+            short::try_new(&storage[..]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn given_too_short_storage_then_try_new_fails() {
+        assert!(short::try_new(&[][..]).is_err());
+        assert!(short::try_new(&[3, b'a', b'b'][..]).is_err());
+        assert!(short::try_new(&[2, b'a', b'b'][..]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "too long for a length prefix")]
+    fn given_too_long_string_then_write_panics() {
+        let mut storage = [0; 300];
+        short::name::write_str(&mut storage, core::str::from_utf8(&[b'a'; 256]).unwrap());
+    }
+}
@@ -74,6 +74,15 @@ macro_rules! impl_field_traits {
             const SIZE: Option<usize> = Some(core::mem::size_of::<$type>());
         }
 
+        impl<E: Endianness, const OFFSET_: usize> FieldByteLen
+            for PrimitiveField<$type, E, OFFSET_>
+        {
+            #[inline(always)]
+            fn byte_len(_storage: &[u8]) -> usize {
+                core::mem::size_of::<$type>()
+            }
+        }
+
         impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
             for PrimitiveField<$type, E, OFFSET_>
         {
@@ -110,6 +119,8 @@ macro_rules! impl_field_traits {
 }
 
 mod primitive_float;
+#[cfg(feature = "half")]
+mod primitive_half;
 mod primitive_int;
 mod primitive_nonzero_int;
 mod primitive_unit;
@@ -0,0 +1,197 @@
+use core::convert::Infallible;
+
+use super::{FieldCopyAccess, PrimitiveField};
+use crate::endianness::{EndianKind, Endianness};
+use crate::fields::primitive::view::FieldView;
+use crate::fields::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+
+// The `half` types are stored as their raw 16 bit pattern, so NaN payloads, infinities and subnormals round trip unchanged.
+macro_rules! half_field {
+    ($type:ident) => {
+        impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<half::$type, E, OFFSET_> {
+            /// See [FieldCopyAccess::ReadError]
+            type ReadError = Infallible;
+            /// See [FieldCopyAccess::WriteError]
+            type WriteError = Infallible;
+            /// See [FieldCopyAccess::HighLevelType]
+            type HighLevelType = half::$type;
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the `half::", stringify!($type), "` field from a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_half_field: half::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &[u8]) -> half::", stringify!($type), " {
+                    let read: half::", stringify!($type), " = my_layout::some_half_field::try_read(storage_data).unwrap();
+                    read
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_read(storage: &[u8]) -> Result<half::$type, Infallible> {
+                    let value: [u8; 2] = storage[Self::OFFSET..(Self::OFFSET + 2)].try_into().unwrap();
+                    let bits = match E::KIND {
+                        EndianKind::Big => u16::from_be_bytes(value),
+                        EndianKind::Little => u16::from_le_bytes(value),
+                        EndianKind::Native => u16::from_ne_bytes(value),
+                    };
+                    Ok(half::$type::from_bits(bits))
+                }
+            }
+
+            doc_comment::doc_comment! {
+                concat! {"
+                Write the `half::", stringify!($type), "` field to a given data region, assuming the defined layout, using the [Field] API.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(my_layout, LittleEndian, {
+                    //... other fields ...
+                    some_half_field: half::", stringify!($type), "
+                    //... other fields ...
+                });
+
+                fn func(storage_data: &mut [u8]) {
+                    my_layout::some_half_field::try_write(storage_data, half::", stringify!($type), "::from_f32(10.0)).unwrap();
+                }
+                ```
+                "},
+                #[inline(always)]
+                fn try_write(storage: &mut [u8], value: half::$type) -> Result<(), Infallible> {
+                    let bits = value.to_bits();
+                    let value = match E::KIND {
+                        EndianKind::Big => bits.to_be_bytes(),
+                        EndianKind::Little => bits.to_le_bytes(),
+                        EndianKind::Native => bits.to_ne_bytes(),
+                    };
+                    storage[Self::OFFSET..(Self::OFFSET + 2)].copy_from_slice(&value);
+                    Ok(())
+                }
+            }
+        }
+
+        impl_field_traits!(half::$type);
+    };
+}
+
+half_field!(f16);
+half_field!(bf16);
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+    use crate::prelude::*;
+    use crate::PrimitiveField;
+    use half::{bf16, f16};
+
+    macro_rules! test_half {
+        ($type:ident, $values:expr) => {
+            test_half!(@case, $type, $values, little, LittleEndian, from_le_bytes);
+            test_half!(@case, $type, $values, big, BigEndian, from_be_bytes);
+            test_half!(@case, $type, $values, native, NativeEndian, from_ne_bytes);
+        };
+        (@case, $type:ident, $values:expr, $endian:ident, $endian_type:ty, $endian_fn:ident) => {
+            $crate::internal::paste! {
+                #[test]
+                fn [<test_ $type _ $endian endian_metadata>]() {
+                    type Field1 = PrimitiveField<$type, $endian_type, 5>;
+                    type Field2 = PrimitiveField<$type, $endian_type, 123>;
+
+                    assert_eq!(Some(2), Field1::SIZE);
+                    assert_eq!(5, Field1::OFFSET);
+                    assert_eq!(Some(2), Field2::SIZE);
+                    assert_eq!(123, Field2::OFFSET);
+                }
+
+                #[test]
+                fn [<test_ $type _ $endian endian_round_trip>]() {
+                    type Field1 = PrimitiveField<$type, $endian_type, 5>;
+
+                    let mut storage = [0; 1024];
+                    for value in $values {
+                        Field1::write(&mut storage, value);
+                        // Compare the bits because NaN != NaN
+                        assert_eq!(value.to_bits(), Field1::read(&storage).to_bits());
+                        assert_eq!(value.to_bits(), u16::$endian_fn(storage[5..7].try_into().unwrap()));
+                    }
+                }
+
+                #[test]
+                fn [<test_ $type _ $endian endian_view>]() {
+                    binary_layout!(layout, $endian_type, {
+                        field1: $type,
+                        field2: $type,
+                    });
+
+                    let mut storage = [0; 4];
+                    let mut view = layout::View::new(&mut storage[..]);
+                    view.field1_mut().write($type::INFINITY);
+                    view.field2_mut().write($type::NAN);
+                    assert_eq!($type::INFINITY, view.field1().read());
+                    assert!(view.field2().read().is_nan());
+                }
+            }
+        };
+    }
+
+    test_half!(
+        f16,
+        [
+            f16::ZERO,
+            f16::NEG_ZERO,
+            f16::ONE,
+            f16::from_f32(-1234.5),
+            f16::MAX,
+            f16::MIN,
+            f16::MIN_POSITIVE,
+            // Subnormals
+            f16::MIN_POSITIVE_SUBNORMAL,
+            f16::MAX_SUBNORMAL,
+            -f16::MIN_POSITIVE_SUBNORMAL,
+            f16::INFINITY,
+            f16::NEG_INFINITY,
+            // Quiet and signaling NaN with payloads
+            f16::NAN,
+            f16::from_bits(0x7E01),
+            f16::from_bits(0x7C01),
+            f16::from_bits(0xFD55),
+        ]
+    );
+
+    test_half!(
+        bf16,
+        [
+            bf16::ZERO,
+            bf16::NEG_ZERO,
+            bf16::ONE,
+            bf16::from_f32(-1234.5),
+            bf16::MAX,
+            bf16::MIN,
+            bf16::MIN_POSITIVE,
+            // Subnormals
+            bf16::MIN_POSITIVE_SUBNORMAL,
+            bf16::MAX_SUBNORMAL,
+            -bf16::MIN_POSITIVE_SUBNORMAL,
+            bf16::INFINITY,
+            bf16::NEG_INFINITY,
+            // Quiet and signaling NaN with payloads
+            bf16::NAN,
+            bf16::from_bits(0x7FC1),
+            bf16::from_bits(0x7F81),
+            bf16::from_bits(0xFFA5),
+        ]
+    );
+}
@@ -1,9 +1,9 @@
 use core::convert::Infallible;
 
 use super::{FieldCopyAccess, PrimitiveField};
-use crate::endianness::{EndianKind, Endianness};
+use crate::endianness::{BigEndian, EndianKind, Endianness, LittleEndian, NativeEndian};
 use crate::fields::primitive::view::FieldView;
-use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::fields::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
 
 macro_rules! int_field {
     ($type:ty) => {
@@ -84,6 +84,50 @@ macro_rules! int_field {
         }
 
         impl_field_traits!($type);
+
+        int_field_read_const!($type, BigEndian, from_be_bytes);
+        int_field_read_const!($type, LittleEndian, from_le_bytes);
+        int_field_read_const!($type, NativeEndian, from_ne_bytes);
+    };
+}
+
+// Const fns can't have trait bounds on our MSRV, so `read_const` is implemented for each endianness separately
+macro_rules! int_field_read_const {
+    ($type:ty, $endian:ty, $from_bytes:ident) => {
+        impl<const OFFSET_: usize> PrimitiveField<$type, $endian, OFFSET_> {
+            doc_comment::doc_comment! {
+                concat! {"
+                Read the integer field from a given data region in a const context, e.g. to turn the fields of a configuration
+                table that is known at compile time into constants. Reading beyond the end of the data region is a compile time
+                error when evaluated in a const context and panics otherwise.
+
+                # Example:
+
+                ```
+                use binary_layout::prelude::*;
+
+                binary_layout!(config, ", stringify!($endian), ", {
+                    version: u8,
+                    value: ", stringify!($type), ",
+                });
+
+                const CONFIG: [u8; 1 + core::mem::size_of::<", stringify!($type), ">()] = [1; 1 + core::mem::size_of::<", stringify!($type), ">()];
+                const VALUE: ", stringify!($type), " = config::value::read_const(&CONFIG);
+                assert_eq!(config::value::read(&CONFIG), VALUE);
+                ```
+                "},
+                #[inline(always)]
+                pub const fn read_const(storage: &[u8]) -> $type {
+                    let mut bytes = [0; core::mem::size_of::<$type>()];
+                    let mut i = 0;
+                    while i < core::mem::size_of::<$type>() {
+                        bytes[i] = storage[OFFSET_ + i];
+                        i += 1;
+                    }
+                    <$type>::$from_bytes(bytes)
+                }
+            }
+        }
     };
 }
 
@@ -234,4 +278,27 @@ mod tests {
     test_int!(u32, 4, 10u32.pow(8), (10u32.pow(7)));
     test_int!(u64, 8, 10u64.pow(15), (10u64.pow(14)));
     test_int!(u128, 16, 10u128.pow(30), (10u128.pow(28)));
+
+    #[test]
+    fn read_const() {
+        binary_layout!(big, BigEndian, {
+            a: u16,
+            b: i32,
+        });
+        binary_layout!(little, LittleEndian, {
+            a: u16,
+            b: i32,
+        });
+
+        const STORAGE: [u8; 6] = [0x01, 0x02, 0xFF, 0xFF, 0xFF, 0xFE];
+        const BIG_A: u16 = big::a::read_const(&STORAGE);
+        const BIG_B: i32 = big::b::read_const(&STORAGE);
+        const LITTLE_A: u16 = little::a::read_const(&STORAGE);
+        const LITTLE_B: i32 = little::b::read_const(&STORAGE);
+
+        assert_eq!(0x0102, BIG_A);
+        assert_eq!(-2, BIG_B);
+        assert_eq!(0x0201, LITTLE_A);
+        assert_eq!(-16_777_217, LITTLE_B);
+    }
 }
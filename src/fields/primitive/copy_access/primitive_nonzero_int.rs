@@ -3,7 +3,7 @@ use core::convert::Infallible;
 use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::{EndianKind, Endianness};
 use crate::fields::primitive::view::FieldView;
-use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::fields::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
 
 macro_rules! nonzero_int_field {
     ($type:ty, $zero_type:ty) => {
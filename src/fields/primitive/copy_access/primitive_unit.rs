@@ -3,7 +3,7 @@ use core::convert::Infallible;
 use super::{FieldCopyAccess, PrimitiveField};
 use crate::endianness::Endianness;
 use crate::fields::primitive::view::FieldView;
-use crate::fields::{Field, StorageIntoFieldView, StorageToFieldView};
+use crate::fields::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
 
 impl<E: Endianness, const OFFSET_: usize> FieldCopyAccess for PrimitiveField<(), E, OFFSET_> {
     /// See [FieldCopyAccess::ReadError]
@@ -31,6 +31,42 @@ pub trait FieldReadExt: Field {
     /// }
     /// ```
     fn read(storage: &[u8]) -> Self::HighLevelType;
+
+    /// Read the field from a given data region and convert it into a wider type `T`, using the [Field] API.
+    ///
+    /// The conversion uses [From], so only lossless conversions compile, e.g. `u16` into `u64` but not `u64` into `u16`.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   some_integer_field: u16,
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let read: u64 = my_layout::some_integer_field::read_as::<u64>(storage_data);
+    /// }
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   some_integer_field: u32,
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   // fails to compile because converting u32 into u16 can lose data
+    ///   let read: u16 = my_layout::some_integer_field::read_as::<u16>(storage_data);
+    /// }
+    /// ```
+    #[inline(always)]
+    fn read_as<T: From<Self::HighLevelType>>(storage: &[u8]) -> T {
+        T::from(Self::read(storage))
+    }
 }
 
 /// This extension trait adds a [FieldWriteExt::write] method to any type
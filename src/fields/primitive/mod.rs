@@ -9,7 +9,7 @@ mod view;
 
 pub use copy_access::{FieldCopyAccess, FieldReadExt, FieldWriteExt, NonZeroIsZeroError};
 pub use nested_access::{BorrowingNestedView, NestedViewInfo, OwningNestedView};
-pub use slice_access::FieldSliceAccess;
+pub use slice_access::{ComputedLength, ComputedSlice, FieldSliceAccess};
 pub use view::FieldView;
 
 /// A [PrimitiveField] is a [Field](crate::Field) that directly represents a primitive type like [u8], [i16], ...
@@ -44,6 +44,33 @@ pub use view::FieldView;
 ///   // equivalent: data_slice[18..22].copy_from_slice(&[1, 2, 3, 4, 5]);
 /// }
 /// ```
+///
+/// # Using fields directly
+/// The [binary_layout!](crate::binary_layout!) macro generates [PrimitiveField] types for you, but you can also name them yourself,
+/// e.g. to compose fields in generic code. A [PrimitiveField] is a marker type that is never instantiated.
+/// Its type parameters define everything about the field:
+/// - `T` is the type stored in the field, e.g. [u32] or `[u8; 4]`.
+/// - `E` is the endianness marker, i.e. [BigEndian](crate::BigEndian), [LittleEndian](crate::LittleEndian) or [NativeEndian](crate::NativeEndian).
+///   It only affects multi byte types like integers and floats, byte arrays are stored as they are.
+/// - `OFFSET_` is the offset of the field in bytes from the start of the storage.
+///   Fields don't have to be aligned and nothing stops fields from overlapping, so it's up to you to pick offsets
+///   that match your data format, usually the offset of the previous field plus its size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// type Version = PrimitiveField<u16, BigEndian, 0>;
+/// type Length = PrimitiveField<u32, BigEndian, 2>;
+/// // The offset of a field can be computed from the previous field
+/// type Payload = PrimitiveField<[u8], BigEndian, { Length::OFFSET + 4 }>;
+///
+/// let mut storage = [0; 10];
+/// Version::write(&mut storage, 3);
+/// Length::write(&mut storage, 4);
+/// Payload::data_mut(&mut storage).copy_from_slice(&[1, 2, 3, 4]);
+/// assert_eq!([0, 3, 0, 0, 0, 4, 1, 2, 3, 4], storage);
+/// assert_eq!(4, Length::read(&storage));
+/// ```
 pub struct PrimitiveField<T: ?Sized, E: Endianness, const OFFSET_: usize> {
     _p1: PhantomData<T>,
     _p2: PhantomData<E>,
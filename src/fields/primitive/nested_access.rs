@@ -1,7 +1,7 @@
-use super::super::{StorageIntoFieldView, StorageToFieldView};
+use super::super::{FieldByteLen, StorageIntoFieldView, StorageToFieldView};
 use super::{Endianness, PrimitiveField};
 use crate::utils::data::Data;
-use crate::Field;
+use crate::{Field, LayoutError};
 
 // Nesting generally works by having the binary_layout! macro implement [OwningNestedView], [BorrowingNestedView]
 // and [NestedViewInfo] for a marker type "NestedLayout" it creates in the layout's generated code.
@@ -35,70 +35,79 @@ pub trait BorrowingNestedView<S> {
 pub trait NestedViewInfo {
     /// Size of the nested field
     const SIZE: Option<usize>;
+
+    /// Minimum number of bytes of the nested field, i.e. the size of all its statically sized fields
+    const MIN_SIZE: usize;
+
+    /// Number of bytes the nested field takes up in the given storage.
+    /// The storage only contains the space of the subfield.
+    fn byte_len(storage: &[u8]) -> usize;
+
+    /// Check the contents of the fields of the nested layout, e.g. that `Utf8<N>` fields contain valid UTF-8.
+    /// The storage only contains the space of the subfield.
+    fn validate(storage: &[u8]) -> Result<(), LayoutError>;
 }
 
-// TODO FieldNestedAccess may be useful for the field API, but commented out for now since the field API doesn't support nesting yet
-// /// This trait is implemented for fields with "nested access",
-// /// i.e. fields that represent other layouts that are nested within
-// /// this layout.
-// pub trait FieldNestedAccess<'a, S>: Field {
-//     /// A view type for the nested field that owns its storage
-//     type OwningView;
-
-//     /// A view type for the nested field that immutably borrows its storage
-//     type BorrowedView;
-
-//     /// A view type for the nested field that mutably borrows its storage
-//     type BorrowedViewMut;
-
-//     fn into_view(storage: Data<S>) -> Self::OwningView;
-//     fn view(storage: &'a [u8]) -> Self::BorrowedView;
-//     fn view_mut(storage: &'a mut [u8]) -> Self::BorrowedViewMut;
-// }
-
-// impl<'a, S, T, E, const OFFSET_: usize> FieldNestedAccess<'a, S> for PrimitiveField<T, E, OFFSET_>
-// where
-//     S: AsRef<[u8]>,
-//     T: OwningNestedView<Data<S>>
-//         + BorrowingNestedView<&'a [u8]>
-//         + BorrowingNestedView<&'a mut [u8]>,
-//     E: Endianness,
-//     Self: Field,
-// {
-//     type OwningView = <T as OwningNestedView<Data<S>>>::View;
-//     type BorrowedView = <T as BorrowingNestedView<&'a [u8]>>::View;
-//     type BorrowedViewMut = <T as BorrowingNestedView<&'a mut [u8]>>::View;
-
-//     #[inline(always)]
-//     fn into_view(storage: Data<S>) -> Self::OwningView {
-//         let data = if let Some(size) = Self::SIZE {
-//             Data::from(storage).into_subregion(Self::OFFSET..(Self::OFFSET + size))
-//         } else {
-//             Data::from(storage).into_subregion(Self::OFFSET..)
-//         };
-//         T::into_view(data)
-//     }
-
-//     #[inline(always)]
-//     fn view(storage: &'a [u8]) -> Self::BorrowedView {
-//         let data = if let Some(size) = Self::SIZE {
-//             &storage[Self::OFFSET..(Self::OFFSET + size)]
-//         } else {
-//             &storage[Self::OFFSET..]
-//         };
-//         T::view(data)
-//     }
-
-//     #[inline(always)]
-//     fn view_mut(storage: &'a mut [u8]) -> Self::BorrowedViewMut {
-//         let data = if let Some(size) = Self::SIZE {
-//             &mut storage[Self::OFFSET..(Self::OFFSET + size)]
-//         } else {
-//             &mut storage[Self::OFFSET..]
-//         };
-//         T::view(data)
-//     }
-// }
+/// Field API for nested fields, i.e. fields that represent other layouts that are nested within this layout.
+///
+/// These functions return the same `View` type that `View::new` returns for the nested layout,
+/// so nested views and top-level views can be used identically.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(icmp_header, BigEndian, {
+///   packet_type: u8,
+///   code: u8,
+///   checksum: u16,
+/// });
+/// binary_layout!(icmp_packet, BigEndian, {
+///   header: icmp_header::NestedView,
+///   data_section: [u8],
+/// });
+///
+/// fn header(packet: &[u8]) -> icmp_header::View<&[u8]> {
+///   icmp_packet::header::view(packet)
+/// }
+///
+/// # fn main() {
+/// let mut storage = [0; 1024];
+/// icmp_packet::header::view_mut(&mut storage).code_mut().write(5);
+/// assert_eq!(5, header(&storage).code().read());
+/// assert_eq!(5, icmp_packet::header::into_view(storage).code().read());
+/// # }
+/// ```
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> PrimitiveField<N, E, OFFSET_> {
+    /// Return a view to the nested layout, borrowing the storage with read access.
+    /// The returned view borrows the storage for the lifetime `'a` and can be returned from functions taking the storage.
+    #[inline(always)]
+    pub fn view<'a>(storage: &'a [u8]) -> <N as BorrowingNestedView<&'a [u8]>>::View
+    where
+        N: BorrowingNestedView<&'a [u8]>,
+    {
+        <Self as StorageToFieldView<&'a [u8]>>::view(storage)
+    }
+
+    /// Return a view to the nested layout, borrowing the storage with write access.
+    /// The returned view borrows the storage for the lifetime `'a` and can be returned from functions taking the storage.
+    #[inline(always)]
+    pub fn view_mut<'a>(storage: &'a mut [u8]) -> <N as BorrowingNestedView<&'a mut [u8]>>::View
+    where
+        N: BorrowingNestedView<&'a mut [u8]>,
+    {
+        <Self as StorageToFieldView<&'a mut [u8]>>::view(storage)
+    }
+
+    /// Return a view to the nested layout that takes ownership of the storage, e.g. a `Vec<u8>`.
+    #[inline(always)]
+    pub fn into_view<S: AsRef<[u8]>>(storage: S) -> <N as OwningNestedView<Data<S>>>::View
+    where
+        N: OwningNestedView<Data<S>>,
+    {
+        <Self as StorageIntoFieldView<S>>::into_view(storage)
+    }
+}
 
 impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> Field
     for PrimitiveField<N, E, OFFSET_>
@@ -111,6 +120,20 @@ impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> Field
     const SIZE: Option<usize> = N::SIZE;
 }
 
+impl<N: NestedViewInfo, E: Endianness, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<N, E, OFFSET_>
+{
+    const MIN_LEN: usize = N::MIN_SIZE;
+
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        match N::SIZE {
+            Some(size) => size,
+            None => N::byte_len(storage.get(Self::OFFSET..).unwrap_or(&[])),
+        }
+    }
+}
+
 impl<'a, N: BorrowingNestedView<&'a [u8]>, E: Endianness, const OFFSET_: usize>
     StorageToFieldView<&'a [u8]> for PrimitiveField<N, E, OFFSET_>
 where
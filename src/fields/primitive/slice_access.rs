@@ -1,6 +1,7 @@
 use core::convert::TryFrom;
+use core::marker::PhantomData;
 
-use super::super::{Field, StorageIntoFieldView, StorageToFieldView};
+use super::super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
 use super::PrimitiveField;
 use crate::endianness::Endianness;
 use crate::utils::data::Data;
@@ -106,6 +107,12 @@ impl<E: Endianness, const OFFSET_: usize> Field for PrimitiveField<[u8], E, OFFS
     /// See [Field::SIZE]
     const SIZE: Option<usize> = None;
 }
+impl<E: Endianness, const OFFSET_: usize> FieldByteLen for PrimitiveField<[u8], E, OFFSET_> {
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        storage.len().saturating_sub(Self::OFFSET)
+    }
+}
 impl<'a, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
     for PrimitiveField<[u8], E, OFFSET_>
 {
@@ -202,6 +209,14 @@ impl<E: Endianness, const N: usize, const OFFSET_: usize> Field
     /// See [Field::SIZE]
     const SIZE: Option<usize> = Some(N);
 }
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<[u8; N], E, OFFSET_>
+{
+    #[inline(always)]
+    fn byte_len(_storage: &[u8]) -> usize {
+        N
+    }
+}
 impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
     for PrimitiveField<[u8; N], E, OFFSET_>
 {
@@ -235,6 +250,127 @@ impl<S: AsRef<[u8]>, E: Endianness, const N: usize, const OFFSET_: usize> Storag
     }
 }
 
+/// Internal trait. Don't use this in user code.
+/// The [binary_layout!](crate::binary_layout!) macro implements this for a marker type it
+/// generates for each `[u8; expr(...)]` field, and the implementation evaluates the length
+/// expression given in the layout definition.
+pub trait ComputedLength {
+    /// Compute the length of the field, based on the storage of the whole layout.
+    fn len(storage: &[u8]) -> usize;
+}
+
+/// Internal type. Don't use this in user code.
+/// This is the field type the [binary_layout!](crate::binary_layout!) macro uses for
+/// `[u8; expr(...)]` fields, see [byte arrays with computed length](crate#byte-arrays-with-computed-length-u8-expr).
+pub struct ComputedSlice<L: ComputedLength> {
+    _p: PhantomData<L>,
+}
+
+/// Field type `[u8; expr(...)]`:
+/// This field represents a [byte array with computed length](crate#byte-arrays-with-computed-length-u8-expr).
+/// In this impl, we define accessors for such fields.
+impl<'a, L: ComputedLength, E: Endianness, const OFFSET_: usize> FieldSliceAccess<'a>
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    type SliceType = &'a [u8];
+    type MutSliceType = &'a mut [u8];
+
+    /// Borrow the data in the byte array with read access using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///     //... other fields ...
+    ///     data_len: u16,
+    ///     data: [u8; expr(|v| v.data_len().read() as usize)],
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///     let data: &[u8] = my_layout::data::data(storage_data);
+    /// }
+    /// ```
+    #[inline(always)]
+    fn data(storage: &'a [u8]) -> &'a [u8] {
+        let len = L::len(storage);
+        &storage[Self::OFFSET..(Self::OFFSET + len)]
+    }
+
+    /// Borrow the data in the byte array with write access using the [Field] API.
+    ///
+    /// # Example:
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///     //... other fields ...
+    ///     data_len: u16,
+    ///     data: [u8; expr(|v| v.data_len().read() as usize)],
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///     let data: &mut [u8] = my_layout::data::data_mut(storage_data);
+    /// }
+    /// ```
+    #[inline(always)]
+    fn data_mut(storage: &'a mut [u8]) -> &'a mut [u8] {
+        let len = L::len(storage);
+        &mut storage[Self::OFFSET..(Self::OFFSET + len)]
+    }
+}
+impl<L: ComputedLength, E: Endianness, const OFFSET_: usize> Field
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = None;
+}
+impl<L: ComputedLength, E: Endianness, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        L::len(storage)
+    }
+}
+impl<'a, L: ComputedLength, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    type View = &'a [u8];
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::data(storage)
+    }
+}
+
+impl<'a, L: ComputedLength, E: Endianness, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    type View = &'a mut [u8];
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::data_mut(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, L: ComputedLength, E: Endianness, const OFFSET_: usize> StorageIntoFieldView<S>
+    for PrimitiveField<ComputedSlice<L>, E, OFFSET_>
+{
+    type View = Data<S>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        let len = L::len(storage.as_ref());
+        Data::from(storage).into_subregion(Self::OFFSET..(Self::OFFSET + len))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::float_cmp)]
@@ -1,6 +1,13 @@
+use core::convert::Infallible;
 use core::marker::PhantomData;
+use core::str::Utf8Error;
 
-use crate::{Field, FieldCopyAccess, FieldReadExt, FieldWriteExt};
+use crate::fields::layout_as_array::{ElementReadError, ElementWriteError};
+use crate::{
+    Endianness, Field, FieldCopyAccess, FieldReadExt, FieldWriteExt, LayoutAs, LayoutAsArray,
+    LayoutAsSaturating, PackedArray, PascalString, PascalStringLength, PrimitiveField, Utf8,
+    WrappedField,
+};
 
 /// A field view represents the field metadata stored in a [Field] plus it stores the underlying
 /// storage data it operates on, either as a reference to a slice `&[u8]`, `&mut [u8]`, or as
@@ -41,16 +48,63 @@ use crate::{Field, FieldCopyAccess, FieldReadExt, FieldWriteExt};
 ///   // equivalent: data_slice[18..22].copy_from_slice(&[1, 2, 3, 4, 5]);
 /// }
 /// ```
+///
+/// # Generic code
+/// A [FieldView] is generic over the storage `S` and the field `F`, so you can write functions that work with views of any field.
+/// The type you read and write through the view is the high level type of the field, e.g. [FieldCopyAccess::HighLevelType] for primitive fields.
+/// You can also create a [FieldView] yourself with [FieldView::new] if you have a storage and know the field type.
+///
+/// ```
+/// use binary_layout::{prelude::*, FieldView};
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   counter: u16,
+///   id: core::num::NonZeroU32,
+/// });
+///
+/// // Works for any field that can be read, e.g. integers, floats, NonZero types or wrapped fields
+/// fn read_or_default<S: AsRef<[u8]>, F: FieldCopyAccess>(view: &FieldView<S, F>) -> F::HighLevelType
+/// where
+///   F::HighLevelType: Default,
+/// {
+///   view.try_read().unwrap_or_default()
+/// }
+///
+/// let storage = [5, 0, 0, 0, 0, 0];
+/// let view = my_layout::View::new(&storage);
+/// assert_eq!(5, read_or_default(&view.counter()));
+///
+/// let counter = FieldView::<_, my_layout::counter>::new(&storage);
+/// assert_eq!(5, read_or_default(&counter));
+/// assert!(my_layout::id::try_read(&storage).is_err());
+/// ```
 pub struct FieldView<S, F: Field> {
     storage: S,
     _p: PhantomData<F>,
 }
 
 impl<S, F: Field> FieldView<S, F> {
-    /// Create a new view for a field over a given storage.
-    /// You probably shouldn't call this directly but should instead call
-    /// `your_layout::View::new()`, which is generated by the
-    /// [binary_layout!](crate::binary_layout!) macro for you.
+    /// Create a new view for the field `F` over a given storage. The storage is the storage of the whole layout,
+    /// i.e. the view accesses the field at its [Field::OFFSET] within the storage.
+    ///
+    /// Usually, you get field views from the accessors of `your_layout::View`, which is generated by the
+    /// [binary_layout!](crate::binary_layout!) macro for you. Creating them directly is useful in code that is generic over fields.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FieldView};
+    ///
+    /// binary_layout!(my_layout, BigEndian, {
+    ///   first: u8,
+    ///   second: u16,
+    /// });
+    ///
+    /// let mut storage = [0; 3];
+    /// let mut view = FieldView::<_, my_layout::second>::new(&mut storage);
+    /// view.write(0x0102);
+    /// assert_eq!(0x0102, view.read());
+    /// assert_eq!([0, 1, 2], storage);
+    /// ```
     #[inline(always)]
     pub fn new(storage: S) -> Self {
         Self {
@@ -58,6 +112,12 @@ impl<S, F: Field> FieldView<S, F> {
             _p: PhantomData,
         }
     }
+
+    /// This destroys the view and returns the underlying storage back to you.
+    #[inline(always)]
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
 }
 impl<S: AsRef<[u8]>, F: FieldReadExt> FieldView<S, F> {
     /// Read the field from a given data region, assuming the defined layout, using the [FieldView] API.
@@ -81,6 +141,29 @@ impl<S: AsRef<[u8]>, F: FieldReadExt> FieldView<S, F> {
     pub fn read(&self) -> F::HighLevelType {
         F::read(self.storage.as_ref())
     }
+
+    /// Read the field from a given data region and convert it into a wider type `T`, using the [FieldView] API.
+    /// See [FieldReadExt::read_as].
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   some_integer_field: u16
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &[u8]) {
+    ///   let view = my_layout::View::new(storage_data);
+    ///   let read: u64 = view.some_integer_field().read_as::<u64>();
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn read_as<T: From<F::HighLevelType>>(&self) -> T {
+        F::read_as(self.storage.as_ref())
+    }
 }
 impl<S: AsMut<[u8]>, F: FieldWriteExt> FieldView<S, F> {
     /// Write the field to a given data region, assuming the defined layout, using the [FieldView] API.
@@ -105,6 +188,42 @@ impl<S: AsMut<[u8]>, F: FieldWriteExt> FieldView<S, F> {
         F::write(self.storage.as_mut(), v)
     }
 }
+impl<S: AsRef<[u8]> + AsMut<[u8]>, F: FieldReadExt> FieldView<S, F>
+where
+    F: FieldWriteExt<HighLevelType = <F as FieldReadExt>::HighLevelType>,
+{
+    /// Modify the field in place by reading it, passing the value to a function, and writing the result back, using the [FieldView] API.
+    ///
+    /// Fields can be unaligned and stored in a different endianness than the one of the system, which is why this
+    /// library can't hand out `&mut` references to integer fields. An `as_mut() -> Option<&mut T>` returning a reference
+    /// only for aligned native endian fields isn't possible either, because it would need to reinterpret the bytes of the
+    /// storage as `T`, which needs unsafe code and this crate is `#![forbid(unsafe_code)]`. This is the alternative for
+    /// read-modify-write operations like incrementing a counter. For fixed size fields, the compiler turns it into a plain load and store.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::prelude::*;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   //... other fields ...
+    ///   counter: u32
+    ///   //... other fields ...
+    /// });
+    ///
+    /// fn func(storage_data: &mut [u8]) {
+    ///   let mut view = my_layout::View::new(storage_data);
+    ///   view.counter_mut().update(|counter| counter + 1);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn update(
+        &mut self,
+        f: impl FnOnce(<F as FieldReadExt>::HighLevelType) -> <F as FieldReadExt>::HighLevelType,
+    ) {
+        let value = F::read(self.storage.as_ref());
+        F::write(self.storage.as_mut(), f(value))
+    }
+}
 impl<S: AsRef<[u8]>, F: FieldCopyAccess> FieldView<S, F> {
     /// Read the field from a given data region, assuming the defined layout, using the [FieldView] API.
     ///
@@ -157,3 +276,157 @@ impl<S: AsMut<[u8]>, F: FieldCopyAccess> FieldView<S, F> {
         F::try_write(self.storage.as_mut(), v)
     }
 }
+impl<S: AsMut<[u8]>, U, T: LayoutAsSaturating<U>, F: FieldWriteExt<HighLevelType = U>>
+    FieldView<S, WrappedField<U, T, F>>
+{
+    /// Write the field to a given data region, assuming the defined layout, using the [FieldView] API.
+    /// Values that are out of range are clamped to the closest representable value, see [LayoutAsSaturating].
+    #[inline(always)]
+    pub fn write_saturating(&mut self, v: T) {
+        WrappedField::<U, T, F>::write_saturating(self.storage.as_mut(), v)
+    }
+}
+impl<S: AsRef<[u8]>, E: Endianness, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<Utf8<N>, E, OFFSET_>>
+{
+    /// Borrow the field as a string using the [FieldView] API, or return an error if it isn't valid UTF-8.
+    /// See [PrimitiveField::try_as_str].
+    #[inline]
+    pub fn try_as_str(&self) -> Result<&str, Utf8Error> {
+        PrimitiveField::<Utf8<N>, E, OFFSET_>::try_as_str(self.storage.as_ref())
+    }
+
+    /// Borrow the field as a string using the [FieldView] API. See [PrimitiveField::as_str].
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        PrimitiveField::<Utf8<N>, E, OFFSET_>::as_str(self.storage.as_ref())
+    }
+}
+
+impl<S: AsMut<[u8]>, E: Endianness, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<Utf8<N>, E, OFFSET_>>
+{
+    /// Write a string to the field using the [FieldView] API. See [PrimitiveField::write_str].
+    #[inline]
+    pub fn write_str(&mut self, value: &str) {
+        PrimitiveField::<Utf8<N>, E, OFFSET_>::write_str(self.storage.as_mut(), value)
+    }
+}
+impl<S: AsRef<[u8]>, L: PascalStringLength, E: Endianness, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<PascalString<L>, E, OFFSET_>>
+{
+    /// Read the length of the string in bytes using the [FieldView] API. See [PrimitiveField::len].
+    #[inline]
+    pub fn len(&self) -> usize {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::len(self.storage.as_ref())
+    }
+
+    /// Return whether the string is empty using the [FieldView] API. See [PrimitiveField::is_empty].
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::is_empty(self.storage.as_ref())
+    }
+
+    /// Borrow the string using the [FieldView] API, or return an error if it isn't valid UTF-8.
+    /// See [PrimitiveField::try_as_str].
+    #[inline]
+    pub fn try_as_str(&self) -> Result<&str, Utf8Error> {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::try_as_str(self.storage.as_ref())
+    }
+
+    /// Borrow the string using the [FieldView] API. See [PrimitiveField::as_str].
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::as_str(self.storage.as_ref())
+    }
+
+    /// Read the string using the [FieldView] API, replacing invalid UTF-8 sequences. See [PrimitiveField::to_str_lossy].
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_str_lossy(&self) -> std::borrow::Cow<'_, str> {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::to_str_lossy(self.storage.as_ref())
+    }
+}
+
+impl<S: AsMut<[u8]>, L: PascalStringLength, E: Endianness, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<PascalString<L>, E, OFFSET_>>
+{
+    /// Write a string and its length to the field using the [FieldView] API. See [PrimitiveField::write_str].
+    #[inline]
+    pub fn write_str(&mut self, value: &str) {
+        PrimitiveField::<PascalString<L>, E, OFFSET_>::write_str(self.storage.as_mut(), value)
+    }
+}
+impl<S: AsRef<[u8]>, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>>
+{
+    /// Read the element with the given index using the [FieldView] API. See [PrimitiveField::get].
+    #[inline]
+    pub fn get(&self, index: usize) -> u16 {
+        PrimitiveField::<PackedArray<BITS, N>, E, OFFSET_>::get(self.storage.as_ref(), index)
+    }
+}
+
+impl<S: AsMut<[u8]>, E: Endianness, const BITS: usize, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<PackedArray<BITS, N>, E, OFFSET_>>
+{
+    /// Write the element with the given index using the [FieldView] API. See [PrimitiveField::set].
+    #[inline]
+    pub fn set(&mut self, index: usize, value: u16) {
+        PrimitiveField::<PackedArray<BITS, N>, E, OFFSET_>::set(self.storage.as_mut(), index, value)
+    }
+}
+
+impl<S: AsRef<[u8]>, U, T, E: Endianness, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>>
+where
+    T: LayoutAs<U>,
+    PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U>,
+{
+    /// Read the element with the given index using the [FieldView] API. See [PrimitiveField::try_get].
+    #[inline]
+    pub fn try_get(&self, index: usize) -> Result<T, ElementReadError<U, T, E>> {
+        PrimitiveField::<LayoutAsArray<U, T, N>, E, OFFSET_>::try_get(self.storage.as_ref(), index)
+    }
+
+    /// Read the element with the given index using the [FieldView] API. See [PrimitiveField::get].
+    #[inline]
+    pub fn get(&self, index: usize) -> T
+    where
+        T: LayoutAs<U, ReadError = Infallible>,
+        PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U, ReadError = Infallible>,
+    {
+        PrimitiveField::<LayoutAsArray<U, T, N>, E, OFFSET_>::get(self.storage.as_ref(), index)
+    }
+}
+
+impl<S: AsMut<[u8]>, U, T, E: Endianness, const N: usize, const OFFSET_: usize>
+    FieldView<S, PrimitiveField<LayoutAsArray<U, T, N>, E, OFFSET_>>
+where
+    T: LayoutAs<U>,
+    PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U>,
+{
+    /// Write the element with the given index using the [FieldView] API. See [PrimitiveField::try_set].
+    #[inline]
+    pub fn try_set(&mut self, index: usize, value: T) -> Result<(), ElementWriteError<U, T, E>> {
+        PrimitiveField::<LayoutAsArray<U, T, N>, E, OFFSET_>::try_set(
+            self.storage.as_mut(),
+            index,
+            value,
+        )
+    }
+
+    /// Write the element with the given index using the [FieldView] API. See [PrimitiveField::set].
+    #[inline]
+    pub fn set(&mut self, index: usize, value: T)
+    where
+        T: LayoutAs<U, WriteError = Infallible>,
+        PrimitiveField<U, E, 0>: FieldCopyAccess<HighLevelType = U, WriteError = Infallible>,
+    {
+        PrimitiveField::<LayoutAsArray<U, T, N>, E, OFFSET_>::set(
+            self.storage.as_mut(),
+            index,
+            value,
+        )
+    }
+}
@@ -0,0 +1,18 @@
+use core::marker::PhantomData;
+
+/// Internal type, don't use!
+/// Used by the functions generated by [binary_layout!](crate::binary_layout!) to handle a field depending on which traits it implements,
+/// e.g. to print it with [Debug](core::fmt::Debug) if it can be read and to skip it otherwise. Each use of the probe defines a set of
+/// traits with the same method and implements them for the probe behind decreasing levels of references. The generated code calls the method
+/// on the probe behind the maximum number of references, so method resolution prefers the trait with the most references whose bounds the field
+/// satisfies, and falls back to the trait defined with `probe_fallback!` otherwise.
+pub struct FieldProbe<F> {
+    _p: PhantomData<F>,
+}
+
+impl<F> Default for FieldProbe<F> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self { _p: PhantomData }
+    }
+}
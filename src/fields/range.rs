@@ -0,0 +1,189 @@
+use core::convert::Infallible;
+use core::ops::Range;
+
+use super::composite::LayoutAsComposite;
+
+/// This error is thrown when reading a [CheckedRange] whose stored start is after its stored end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRangeError(pub(crate) ());
+
+impl core::fmt::Display for InvalidRangeError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "InvalidRangeError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidRangeError {}
+
+/// A [Range] can be stored as its start followed by its end, e.g. `region: Range<u32> as (u32, u32)`
+/// for formats with start/end tables. Reading and writing don't check that `start <= end`, see [CheckedRange] for that.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+/// use core::ops::Range;
+///
+/// binary_layout!(section, LittleEndian, {
+///   region: Range<u32> as (u32, u32),
+///   flags: u8,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 9];
+/// section::region::write(&mut storage, 16..64);
+/// assert_eq!([16, 0, 0, 0, 64, 0, 0, 0, 0], storage);
+/// assert_eq!(16..64, section::region::read(&storage));
+/// # }
+/// ```
+impl<T> LayoutAsComposite<T, T> for Range<T> {
+    type ReadError = Infallible;
+    type WriteError = Infallible;
+
+    #[inline(always)]
+    fn try_read(start: T, end: T) -> Result<Self, Infallible> {
+        Ok(start..end)
+    }
+
+    #[inline(always)]
+    fn try_write(v: Self) -> Result<(T, T), Infallible> {
+        Ok((v.start, v.end))
+    }
+}
+
+/// A [Range] whose start is never after its end, stored as its start followed by its end, e.g. `region: CheckedRange<u32> as (u32, u32)`.
+///
+/// Reading a stored range whose start is after its end throws an [InvalidRangeError], so you need to use
+/// [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read) to read these fields. Since a [CheckedRange] can only be
+/// created from a valid range, writing can't fail. Use [Range] as the field type to read ranges without checking them.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, CheckedRange, InvalidRangeError};
+///
+/// binary_layout!(section, BigEndian, {
+///   region: CheckedRange<u16> as (u16, u16),
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 4];
+/// section::region::write(&mut storage, CheckedRange::new(2..10).unwrap());
+/// assert_eq!(2..10, section::region::try_read(&storage).unwrap().into_inner());
+///
+/// assert!(CheckedRange::new(10..2).is_err());
+/// let storage = [0, 10, 0, 2];
+/// assert!(section::region::try_read(&storage).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckedRange<T>(Range<T>);
+
+impl<T: PartialOrd> CheckedRange<T> {
+    /// Create a [CheckedRange] from a range, or return an [InvalidRangeError] if its start is after its end.
+    #[inline]
+    pub fn new(range: Range<T>) -> Result<Self, InvalidRangeError> {
+        if range.start <= range.end {
+            Ok(Self(range))
+        } else {
+            Err(InvalidRangeError(()))
+        }
+    }
+}
+
+impl<T> CheckedRange<T> {
+    /// Return the range.
+    #[inline]
+    pub fn range(&self) -> &Range<T> {
+        &self.0
+    }
+
+    /// Destroy the [CheckedRange] and return the range.
+    #[inline]
+    pub fn into_inner(self) -> Range<T> {
+        self.0
+    }
+}
+
+impl<T: PartialOrd> LayoutAsComposite<T, T> for CheckedRange<T> {
+    type ReadError = InvalidRangeError;
+    type WriteError = Infallible;
+
+    #[inline]
+    fn try_read(start: T, end: T) -> Result<Self, InvalidRangeError> {
+        Self::new(start..end)
+    }
+
+    #[inline(always)]
+    fn try_write(v: Self) -> Result<(T, T), Infallible> {
+        Ok((v.0.start, v.0.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::WrappedFieldError;
+
+    binary_layout!(layout, BigEndian, {
+        header: u8,
+        unchecked: Range<u32> as (u32, u32),
+        checked: CheckedRange<i16> as (i16, i16),
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, layout::unchecked::OFFSET);
+        assert_eq!(Some(8), layout::unchecked::SIZE);
+        assert_eq!(9, layout::checked::OFFSET);
+        assert_eq!(Some(4), layout::checked::SIZE);
+    }
+
+    #[test]
+    fn unchecked() {
+        let mut storage = [0; 13];
+        layout::unchecked::write(&mut storage, 0x0102..0x0A0B0C0D);
+        assert_eq!([0, 0, 0, 1, 2, 0x0A, 0x0B, 0x0C, 0x0D], storage[..9]);
+        assert_eq!(0x0102..0x0A0B0C0D, layout::unchecked::read(&storage));
+
+        // Not checked, so ranges with the start after the end can be read and written
+        layout::unchecked::write(&mut storage, Range { start: 5, end: 3 });
+        assert_eq!(
+            Range { start: 5, end: 3 },
+            layout::unchecked::read(&storage)
+        );
+    }
+
+    #[test]
+    fn checked() {
+        let mut storage = [0; 13];
+        layout::checked::write(&mut storage, CheckedRange::new(-2..-2).unwrap());
+        assert_eq!([0xFF, 0xFE, 0xFF, 0xFE], storage[9..]);
+        assert_eq!(
+            &(-2..-2),
+            layout::checked::try_read(&storage).unwrap().range()
+        );
+
+        storage[9..].copy_from_slice(&[0, 1, 0xFF, 0xFF]);
+        assert!(matches!(
+            layout::checked::try_read(&storage),
+            Err(WrappedFieldError::LayoutAsError(InvalidRangeError(())))
+        ));
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            Ok(1..3),
+            CheckedRange::new(1..3).map(CheckedRange::into_inner)
+        );
+        assert_eq!(
+            Ok(3..3),
+            CheckedRange::new(3..3).map(CheckedRange::into_inner)
+        );
+        assert_eq!(
+            Err(InvalidRangeError(())),
+            CheckedRange::new(Range { start: 4, end: 3 })
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use core::convert::TryInto;
+
+use super::{
+    primitive::{FieldCopyAccess, FieldReadExt, PrimitiveField},
+    wrapped::{LayoutAs, WrappedField},
+    Field,
+};
+use crate::endianness::Endianness;
+
+/// This extension trait allows reading a field together with the raw bytes it is stored as, e.g. to debug a custom
+/// [LayoutAs] implementation by logging both the decoded value and the bytes it was decoded from.
+///
+/// It is implemented for primitive fields with copy access, e.g. integers and floats, and for fields wrapping them
+/// with [WrappedField], i.e. fields declared as `MyType as u32` in a [binary_layout!](crate::binary_layout!) layout.
+/// The raw bytes are returned as an array with the size of the field, in the order they are stored in, so they aren't
+/// affected by the endianness of the layout.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldReadWithRaw};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   flags: u8,
+///   value: u32,
+/// });
+///
+/// # fn main() {
+/// let storage = [0xFF, 0, 0, 1, 2];
+/// let (value, raw) = my_layout::value::read_with_raw(&storage);
+/// assert_eq!(0x0102, value);
+/// assert_eq!([0, 0, 1, 2], raw);
+/// # }
+/// ```
+pub trait FieldReadWithRaw: Field {
+    /// The raw bytes of the field, a `[u8; N]` array with `N` being the size of the field.
+    type RawBytes;
+
+    /// Return a copy of the raw bytes the field is stored as.
+    ///
+    /// # Panics
+    /// This panics if `storage` is too short for the field, like [FieldReadExt::read].
+    fn raw_bytes(storage: &[u8]) -> Self::RawBytes;
+
+    /// Read the field and return the decoded value together with the raw bytes it is stored as.
+    /// See [FieldReadWithRaw].
+    #[inline(always)]
+    fn read_with_raw(storage: &[u8]) -> (<Self as FieldReadExt>::HighLevelType, Self::RawBytes)
+    where
+        Self: FieldReadExt,
+    {
+        (Self::read(storage), Self::raw_bytes(storage))
+    }
+
+    /// Try to read the field and return the result together with the raw bytes it is stored as.
+    /// This is for fields whose [FieldCopyAccess::try_read] can throw errors and returns the raw bytes
+    /// even if decoding them failed.
+    ///
+    /// # Example
+    /// ```
+    /// use binary_layout::{prelude::*, FieldReadWithRaw};
+    /// use core::num::NonZeroU16;
+    ///
+    /// binary_layout!(my_layout, LittleEndian, {
+    ///   id: NonZeroU16,
+    /// });
+    ///
+    /// # fn main() {
+    /// let (value, raw) = my_layout::id::try_read_with_raw(&[0, 0]);
+    /// assert!(value.is_err());
+    /// assert_eq!([0, 0], raw);
+    /// # }
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn try_read_with_raw(
+        storage: &[u8],
+    ) -> (
+        Result<<Self as FieldCopyAccess>::HighLevelType, Self::ReadError>,
+        Self::RawBytes,
+    )
+    where
+        Self: FieldCopyAccess,
+    {
+        (Self::try_read(storage), Self::raw_bytes(storage))
+    }
+}
+
+macro_rules! impl_read_with_raw {
+    ($($type: ty),*) => {
+        $(
+            impl<E: Endianness, const OFFSET_: usize> FieldReadWithRaw for PrimitiveField<$type, E, OFFSET_> {
+                type RawBytes = [u8; core::mem::size_of::<$type>()];
+
+                #[inline(always)]
+                fn raw_bytes(storage: &[u8]) -> Self::RawBytes {
+                    storage[OFFSET_..(OFFSET_ + core::mem::size_of::<$type>())]
+                        .try_into()
+                        .unwrap()
+                }
+            }
+        )*
+    };
+}
+
+impl_read_with_raw!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+impl_read_with_raw!(
+    core::num::NonZeroI8,
+    core::num::NonZeroI16,
+    core::num::NonZeroI32,
+    core::num::NonZeroI64,
+    core::num::NonZeroI128,
+    core::num::NonZeroU8,
+    core::num::NonZeroU16,
+    core::num::NonZeroU32,
+    core::num::NonZeroU64,
+    core::num::NonZeroU128
+);
+
+impl<U, T: LayoutAs<U>, F: FieldReadWithRaw> FieldReadWithRaw for WrappedField<U, T, F> {
+    type RawBytes = F::RawBytes;
+
+    #[inline(always)]
+    fn raw_bytes(storage: &[u8]) -> Self::RawBytes {
+        F::raw_bytes(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::{InvalidDiscriminantError, Scaled, WrappedFieldError};
+
+    crate::layout_as_enum! {
+        #[layout_as(u8)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Mode {
+            Off = 0,
+            On = 1,
+        }
+    }
+
+    binary_layout!(layout, LittleEndian, {
+        int: i16,
+        float: f32,
+        nonzero: core::num::NonZeroU32,
+        mode: Mode as u8,
+        scaled: Scaled<u16, 1, 10> as u16,
+    });
+
+    #[test]
+    fn primitive() {
+        let mut storage = [0; 13];
+        layout::int::write(&mut storage, -2);
+        layout::float::write(&mut storage, 1.5);
+        assert_eq!((-2, [0xFE, 0xFF]), layout::int::read_with_raw(&storage));
+        assert_eq!(
+            (1.5, [0x00, 0x00, 0xC0, 0x3F]),
+            layout::float::read_with_raw(&storage)
+        );
+    }
+
+    #[test]
+    fn fallible() {
+        let mut storage = [0; 13];
+        let (value, raw) = layout::nonzero::try_read_with_raw(&storage);
+        assert!(matches!(value, Err(NonZeroIsZeroError(_))));
+        assert_eq!([0; 4], raw);
+
+        storage[6..10].copy_from_slice(&[1, 2, 0, 0]);
+        let (value, raw) = layout::nonzero::try_read_with_raw(&storage);
+        assert_eq!(0x0201, value.unwrap().get());
+        assert_eq!([1, 2, 0, 0], raw);
+    }
+
+    #[test]
+    fn wrapped() {
+        let mut storage = [0; 13];
+        storage[10] = 1;
+        let (value, raw) = layout::mode::try_read_with_raw(&storage);
+        assert_eq!(Mode::On, value.unwrap());
+        assert_eq!([1], raw);
+
+        storage[10] = 7;
+        let (value, raw) = layout::mode::try_read_with_raw(&storage);
+        assert!(matches!(
+            value,
+            Err(WrappedFieldError::LayoutAsError(InvalidDiscriminantError {
+                value: 7
+            }))
+        ));
+        assert_eq!([7], raw);
+    }
+
+    #[test]
+    fn wrapped_infallible() {
+        let mut storage = [0; 13];
+        storage[11..13].copy_from_slice(&[0x10, 0x00]);
+        let (value, raw) = layout::scaled::read_with_raw(&storage);
+        assert_eq!(1.6, value.value());
+        assert_eq!([0x10, 0x00], raw);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_short() {
+        layout::int::raw_bytes(&[0]);
+    }
+}
@@ -0,0 +1,296 @@
+use core::convert::{Infallible, TryFrom};
+use core::marker::PhantomData;
+
+use crate::{IntegerOutOfRangeError, LayoutAs, LayoutAsSaturating};
+
+/// A value stored as an integer scaled by the rational factor `DEN / NUM`, i.e. the stored integer `raw` means the value
+/// `raw * NUM / DEN`. This covers many sensor and telemetry encodings, e.g. a percentage stored in units of 1/100 percent
+/// as `Scaled<u16, 1, 100> as u16`. Use it in a [binary_layout!](crate::binary_layout!) layout with the same integer type
+/// for `RAW` and the type after `as`.
+///
+/// Reading converts the stored integer into an [f64], so integers with more than 53 bits of precision may be rounded.
+/// Writing divides the value by the scale factor and rounds it to the nearest integer, with ties rounded away from zero.
+/// Values whose rounded integer doesn't fit into `RAW`, as well as `NaN`, throw an [IntegerOutOfRangeError], so you need to use
+/// [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) or `write_saturating` to write these fields.
+/// `NUM` and `DEN` must not be zero, otherwise the field fails to compile when it is accessed.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, Scaled};
+///
+/// binary_layout!(telemetry, LittleEndian, {
+///   // 0 to 10000 means 0% to 100%
+///   battery: Scaled<u16, 1, 100> as u16,
+///   // in units of 0.5 degrees celsius
+///   temperature: Scaled<i8, 1, 2> as i8,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 3];
+/// telemetry::battery::try_write(&mut storage, Scaled::new(87.5)).unwrap();
+/// telemetry::temperature::try_write(&mut storage, Scaled::new(-3.5)).unwrap();
+/// assert_eq!([0x2E, 0x22, 0xF9], storage);
+/// assert_eq!(87.5, telemetry::battery::read(&storage).value());
+/// assert_eq!(-3.5, telemetry::temperature::read(&storage).value());
+///
+/// assert!(telemetry::battery::try_write(&mut storage, Scaled::new(700.0)).is_err());
+/// telemetry::battery::write_saturating(&mut storage, Scaled::new(700.0));
+/// assert_eq!(655.35, telemetry::battery::read(&storage).value());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Scaled<RAW, const NUM: i64, const DEN: i64> {
+    value: f64,
+    _raw: PhantomData<RAW>,
+}
+
+impl<RAW, const NUM: i64, const DEN: i64> Scaled<RAW, NUM, DEN> {
+    /// Fails to evaluate if the scale factor is zero or infinite
+    const NONZERO_FACTOR: () = assert!(
+        NUM != 0 && DEN != 0,
+        "Error: NUM and DEN of Scaled<RAW, NUM, DEN> must not be zero"
+    );
+
+    /// Create a [Scaled] holding the given value.
+    #[inline]
+    pub const fn new(value: f64) -> Self {
+        Self {
+            value,
+            _raw: PhantomData,
+        }
+    }
+
+    /// Return the value.
+    #[inline]
+    pub const fn value(self) -> f64 {
+        self.value
+    }
+
+    #[inline]
+    fn from_raw(raw: f64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NONZERO_FACTOR;
+        Self::new(raw * NUM as f64 / DEN as f64)
+    }
+
+    // The stored integer for this value, rounded to the nearest integer with ties away from zero,
+    // or None for NaN. Values out of the range of i128 saturate.
+    #[inline]
+    fn rounded_raw(self) -> Option<i128> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NONZERO_FACTOR;
+        let raw = self.value * DEN as f64 / NUM as f64;
+        if raw.is_nan() {
+            return None;
+        }
+        // f64::round isn't available without std
+        let truncated = raw as i128;
+        let fraction = raw - truncated as f64;
+        if fraction >= 0.5 {
+            Some(truncated.saturating_add(1))
+        } else if fraction <= -0.5 {
+            Some(truncated.saturating_sub(1))
+        } else {
+            Some(truncated)
+        }
+    }
+}
+
+/// An angle in degrees stored as an [i32] in units of 1e-7 degrees, the way many GPS and mapping formats store latitude and longitude.
+/// This is a [Scaled] value, so use it as `ScaledDegrees as i32` in a [binary_layout!](crate::binary_layout!) layout.
+/// It covers the whole range of latitudes and longitudes, i.e. -180 to 180 degrees, with a resolution of about 1 cm on the surface of the earth.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, ScaledDegrees};
+///
+/// binary_layout!(position, LittleEndian, {
+///   latitude: ScaledDegrees as i32,
+///   longitude: ScaledDegrees as i32,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 8];
+/// position::latitude::try_write(&mut storage, ScaledDegrees::new(52.5200066)).unwrap();
+/// position::longitude::try_write(&mut storage, ScaledDegrees::new(13.404954)).unwrap();
+/// assert_eq!(525200066, i32::from_le_bytes([storage[0], storage[1], storage[2], storage[3]]));
+/// assert_eq!(52.5200066, position::latitude::read(&storage).value());
+/// assert_eq!(13.404954, position::longitude::read(&storage).value());
+/// # }
+/// ```
+pub type ScaledDegrees = Scaled<i32, 1, 10_000_000>;
+
+macro_rules! impl_scaled {
+    ($($type: ty),*) => {
+        $(
+            impl<const NUM: i64, const DEN: i64> LayoutAs<$type> for Scaled<$type, NUM, DEN> {
+                type ReadError = Infallible;
+                type WriteError = IntegerOutOfRangeError;
+
+                #[inline]
+                fn try_read(v: $type) -> Result<Self, Infallible> {
+                    Ok(Self::from_raw(v as f64))
+                }
+
+                #[inline]
+                fn try_write(v: Self) -> Result<$type, IntegerOutOfRangeError> {
+                    v.rounded_raw()
+                        .and_then(|raw| <$type>::try_from(raw).ok())
+                        .ok_or(IntegerOutOfRangeError(()))
+                }
+            }
+
+            impl<const NUM: i64, const DEN: i64> LayoutAsSaturating<$type> for Scaled<$type, NUM, DEN> {
+                #[inline]
+                fn write_saturating(v: Self) -> $type {
+                    match v.rounded_raw() {
+                        None => 0,
+                        Some(raw) if raw < <$type>::MIN as i128 => <$type>::MIN,
+                        Some(raw) if raw > <$type>::MAX as i128 => <$type>::MAX,
+                        Some(raw) => raw as $type,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_scaled!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::WrappedFieldError;
+
+    binary_layout!(layout, LittleEndian, {
+        percent: Scaled<u16, 1, 100> as u16,
+        signed: Scaled<i8, 1, 2> as i8,
+        coarse: Scaled<u32, 1000, 3> as u32,
+        negative: Scaled<i16, -1, 10> as i16,
+    });
+
+    #[test]
+    fn read() {
+        let mut storage = [0; 9];
+        storage[0..2].copy_from_slice(&10000u16.to_le_bytes());
+        storage[2] = (-7i8) as u8;
+        storage[3..7].copy_from_slice(&3u32.to_le_bytes());
+        storage[7..9].copy_from_slice(&25i16.to_le_bytes());
+        assert_eq!(100.0, layout::percent::read(&storage).value());
+        assert_eq!(-3.5, layout::signed::read(&storage).value());
+        assert_eq!(1000.0, layout::coarse::read(&storage).value());
+        assert_eq!(-2.5, layout::negative::read(&storage).value());
+    }
+
+    #[test]
+    fn write_rounds_to_nearest() {
+        let mut storage = [0; 9];
+        let cases: [(f64, u16); 5] = [
+            (0.0, 0),
+            (12.3449, 1234),
+            (12.3451, 1235),
+            (12.346, 1235),
+            (655.35, 65535),
+        ];
+        for (value, raw) in cases {
+            layout::percent::try_write(&mut storage, Scaled::new(value)).unwrap();
+            assert_eq!(raw, u16::from_le_bytes([storage[0], storage[1]]));
+        }
+        layout::signed::try_write(&mut storage, Scaled::new(-1.25)).unwrap();
+        assert_eq!(-3, storage[2] as i8);
+        layout::signed::try_write(&mut storage, Scaled::new(1.25)).unwrap();
+        assert_eq!(3, storage[2] as i8);
+        layout::negative::try_write(&mut storage, Scaled::new(-2.5)).unwrap();
+        assert_eq!(25, i16::from_le_bytes([storage[7], storage[8]]));
+    }
+
+    #[test]
+    fn write_out_of_range() {
+        let mut storage = [0; 9];
+        for value in [-0.01, 655.36, 1e300, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                layout::percent::try_write(&mut storage, Scaled::new(value)),
+                Err(WrappedFieldError::LayoutAsError(IntegerOutOfRangeError(())))
+            ));
+        }
+        assert!(layout::signed::try_write(&mut storage, Scaled::new(64.0)).is_err());
+        assert!(layout::signed::try_write(&mut storage, Scaled::new(-64.25)).is_err());
+        layout::signed::try_write(&mut storage, Scaled::new(-64.0)).unwrap();
+        assert_eq!(i8::MIN, storage[2] as i8);
+    }
+
+    #[test]
+    fn write_saturating() {
+        let mut storage = [0; 9];
+        layout::percent::write_saturating(&mut storage, Scaled::new(1000.0));
+        assert_eq!(u16::MAX, u16::from_le_bytes([storage[0], storage[1]]));
+        layout::percent::write_saturating(&mut storage, Scaled::new(-5.0));
+        assert_eq!(0, u16::from_le_bytes([storage[0], storage[1]]));
+        layout::signed::write_saturating(&mut storage, Scaled::new(f64::NEG_INFINITY));
+        assert_eq!(i8::MIN, storage[2] as i8);
+        layout::signed::write_saturating(&mut storage, Scaled::new(f64::NAN));
+        assert_eq!(0, storage[2]);
+    }
+
+    #[test]
+    fn degrees_poles_and_date_line() {
+        binary_layout!(position, LittleEndian, {
+            latitude: ScaledDegrees as i32,
+            longitude: ScaledDegrees as i32,
+        });
+        let mut storage = [0; 8];
+        let cases: [(f64, f64, i32, i32); 4] = [
+            (90.0, 180.0, 900_000_000, 1_800_000_000),
+            (-90.0, -180.0, -900_000_000, -1_800_000_000),
+            (89.9999999, 179.9999999, 899_999_999, 1_799_999_999),
+            (-89.9999999, -179.9999999, -899_999_999, -1_799_999_999),
+        ];
+        for (latitude, longitude, raw_latitude, raw_longitude) in cases {
+            position::latitude::try_write(&mut storage, ScaledDegrees::new(latitude)).unwrap();
+            position::longitude::try_write(&mut storage, ScaledDegrees::new(longitude)).unwrap();
+            assert_eq!(
+                raw_latitude,
+                i32::from_le_bytes(storage[0..4].try_into().unwrap())
+            );
+            assert_eq!(
+                raw_longitude,
+                i32::from_le_bytes(storage[4..8].try_into().unwrap())
+            );
+            assert_eq!(latitude, position::latitude::read(&storage).value());
+            assert_eq!(longitude, position::longitude::read(&storage).value());
+        }
+    }
+
+    #[test]
+    fn degrees_round_trip() {
+        binary_layout!(angle, LittleEndian, {
+            value: ScaledDegrees as i32,
+        });
+        let mut storage = [0; 4];
+        let raws = (-1_800_000_000..=1_800_000_000).step_by(999_983).chain([
+            -1_800_000_000,
+            1_800_000_000,
+            -1,
+            0,
+            1,
+        ]);
+        for raw in raws {
+            storage.copy_from_slice(&i32::to_le_bytes(raw));
+            let value = angle::value::read(&storage);
+            angle::value::try_write(&mut storage, value).unwrap();
+            assert_eq!(raw, i32::from_le_bytes(storage));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut storage = [0; 9];
+        for raw in 0..=u16::MAX {
+            storage[0..2].copy_from_slice(&raw.to_le_bytes());
+            let value = layout::percent::read(&storage);
+            layout::percent::try_write(&mut storage, value).unwrap();
+            assert_eq!(raw, u16::from_le_bytes([storage[0], storage[1]]));
+        }
+    }
+}
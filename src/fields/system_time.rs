@@ -0,0 +1,343 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::LayoutAs;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Seconds between 1601-01-01 00:00:00 UTC (the FILETIME epoch) and the unix epoch
+const FILETIME_UNIX_OFFSET_SECS: i64 = 11_644_473_600;
+/// FILETIME counts in intervals of 100ns
+const FILETIME_TICKS_PER_SEC: u64 = 10_000_000;
+const FILETIME_NANOS_PER_TICK: u32 = 100;
+
+/// Seconds between 1900-01-01 00:00:00 UTC (the NTP epoch) and the unix epoch
+const NTP_UNIX_OFFSET_SECS: i64 = 2_208_988_800;
+/// Number of seconds in an NTP era
+const NTP_ERA_SECS: i64 = 1 << 32;
+
+/// This error is thrown when a timestamp can't be converted because it is out of the range
+/// the stored format or [SystemTime] can represent.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimestampOutOfRangeError(pub(crate) ());
+
+impl core::fmt::Display for TimestampOutOfRangeError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "TimestampOutOfRangeError")
+    }
+}
+
+impl std::error::Error for TimestampOutOfRangeError {}
+
+/// Build a [SystemTime] from seconds relative to the unix epoch and a positive number of nanoseconds after that.
+fn from_unix(secs: i64, nanos: u32) -> Result<SystemTime, TimestampOutOfRangeError> {
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs.unsigned_abs()))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))
+    };
+    time.and_then(|time| time.checked_add(Duration::from_nanos(u64::from(nanos))))
+        .ok_or(TimestampOutOfRangeError(()))
+}
+
+/// Split a [SystemTime] into whole seconds relative to the unix epoch (rounded down) and the nanoseconds after that.
+fn to_unix(time: SystemTime) -> Result<(i64, u32), TimestampOutOfRangeError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => {
+            let secs = i64::try_from(after.as_secs()).map_err(|_| TimestampOutOfRangeError(()))?;
+            Ok((secs, after.subsec_nanos()))
+        }
+        Err(before) => {
+            let before = before.duration();
+            let secs = i64::try_from(before.as_secs()).map_err(|_| TimestampOutOfRangeError(()))?;
+            if before.subsec_nanos() == 0 {
+                Ok((-secs, 0))
+            } else {
+                Ok((-secs - 1, NANOS_PER_SEC - before.subsec_nanos()))
+            }
+        }
+    }
+}
+
+/// A Windows `FILETIME` timestamp, i.e. a point in time stored as the number of 100ns intervals since 1601-01-01 00:00:00 UTC.
+/// Use it as `FileTime as u64` in a [binary_layout!](crate::binary_layout!) layout. Windows stores these in little endian byte order.
+/// This is only available with the `std` feature.
+///
+/// Precision below 100ns is lost when writing. Times before 1601 or times that [SystemTime] can't represent
+/// throw a [TimestampOutOfRangeError], so you need to use [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read)
+/// and [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) for these fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FileTime};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// binary_layout!(file_info, LittleEndian, {
+///   created: FileTime as u64,
+///   size: u64,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 16];
+/// let time = UNIX_EPOCH + Duration::new(1_000_000_000, 500);
+/// file_info::created::try_write(&mut storage, FileTime(time)).unwrap();
+/// assert_eq!(126_444_736_000_000_005u64.to_le_bytes(), storage[..8]);
+/// assert_eq!(time, file_info::created::try_read(&storage).unwrap().0);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileTime(pub SystemTime);
+
+impl LayoutAs<u64> for FileTime {
+    type ReadError = TimestampOutOfRangeError;
+    type WriteError = TimestampOutOfRangeError;
+
+    fn try_read(v: u64) -> Result<Self, Self::ReadError> {
+        // v / FILETIME_TICKS_PER_SEC is at most u64::MAX / 10^7, which fits into an i64
+        let secs = (v / FILETIME_TICKS_PER_SEC) as i64 - FILETIME_UNIX_OFFSET_SECS;
+        let nanos = (v % FILETIME_TICKS_PER_SEC) as u32 * FILETIME_NANOS_PER_TICK;
+        from_unix(secs, nanos).map(FileTime)
+    }
+
+    fn try_write(v: Self) -> Result<u64, Self::WriteError> {
+        let (secs, nanos) = to_unix(v.0)?;
+        let secs = secs
+            .checked_add(FILETIME_UNIX_OFFSET_SECS)
+            .and_then(|secs| u64::try_from(secs).ok())
+            .ok_or(TimestampOutOfRangeError(()))?;
+        secs.checked_mul(FILETIME_TICKS_PER_SEC)
+            .and_then(|ticks| ticks.checked_add(u64::from(nanos / FILETIME_NANOS_PER_TICK)))
+            .ok_or(TimestampOutOfRangeError(()))
+    }
+}
+
+/// A 64 bit NTP timestamp, i.e. a point in time stored as 32 bit seconds since 1900-01-01 00:00:00 UTC
+/// followed by 32 bit fractions of a second. Use it as `NtpTimestamp as u64` in a [binary_layout!](crate::binary_layout!) layout.
+/// NTP stores these in big endian byte order. This is only available with the `std` feature.
+///
+/// The 32 bit seconds wrap around in 2036. Following [RFC 4330](https://www.rfc-editor.org/rfc/rfc4330#section-3), timestamps with the
+/// most significant bit set are interpreted as 1968-2036 and timestamps without it as 2036-2104.
+/// Writing times outside of that range throws a [TimestampOutOfRangeError], so you need to use
+/// [FieldCopyAccess::try_write](crate::FieldCopyAccess::try_write) for these fields. Reading can also fail if [SystemTime] can't represent the time.
+///
+/// The fraction has a resolution of about 233 picoseconds, so nanosecond precision is kept when writing and reading a value.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, NtpTimestamp};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// binary_layout!(ntp_packet, BigEndian, {
+///   header: [u8; 40],
+///   transmit_timestamp: NtpTimestamp as u64,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 48];
+/// let time = UNIX_EPOCH + Duration::new(1_000_000_000, 500_000_000);
+/// ntp_packet::transmit_timestamp::try_write(&mut storage, NtpTimestamp(time)).unwrap();
+/// assert_eq!([0xBF, 0x45, 0x48, 0x80, 0x80, 0x00, 0x00, 0x00], storage[40..]);
+/// assert_eq!(time, ntp_packet::transmit_timestamp::try_read(&storage).unwrap().0);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NtpTimestamp(pub SystemTime);
+
+impl LayoutAs<u64> for NtpTimestamp {
+    type ReadError = TimestampOutOfRangeError;
+    type WriteError = TimestampOutOfRangeError;
+
+    fn try_read(v: u64) -> Result<Self, Self::ReadError> {
+        let secs = i64::from((v >> 32) as u32);
+        let fraction = u64::from(v as u32);
+        let secs = if secs & 0x8000_0000 != 0 {
+            secs
+        } else {
+            secs + NTP_ERA_SECS
+        };
+        // Round to the closest nanosecond so that writing and reading a value gives back the same value
+        let nanos = ((fraction * u64::from(NANOS_PER_SEC) + (1 << 31)) >> 32) as u32;
+        from_unix(secs - NTP_UNIX_OFFSET_SECS, nanos).map(NtpTimestamp)
+    }
+
+    fn try_write(v: Self) -> Result<u64, Self::WriteError> {
+        let (secs, nanos) = to_unix(v.0)?;
+        let secs = secs
+            .checked_add(NTP_UNIX_OFFSET_SECS)
+            .ok_or(TimestampOutOfRangeError(()))?;
+        let secs = if (NTP_ERA_SECS / 2..NTP_ERA_SECS).contains(&secs) {
+            secs
+        } else if (NTP_ERA_SECS..NTP_ERA_SECS + NTP_ERA_SECS / 2).contains(&secs) {
+            secs - NTP_ERA_SECS
+        } else {
+            return Err(TimestampOutOfRangeError(()));
+        };
+        let fraction = (u64::from(nanos) << 32) / u64::from(NANOS_PER_SEC);
+        Ok(((secs as u64) << 32) | fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::WrappedFieldError;
+
+    binary_layout!(layout, BigEndian, {
+        file_time: FileTime as u64,
+        ntp: NtpTimestamp as u64,
+    });
+
+    fn unix(secs: i64, nanos: u32) -> SystemTime {
+        from_unix(secs, nanos).unwrap()
+    }
+
+    #[test]
+    fn unix_conversion() {
+        for (secs, nanos) in [(0, 0), (1, 5), (-1, 0), (-1, 999_999_999), (-100, 1)] {
+            assert_eq!((secs, nanos), to_unix(unix(secs, nanos)).unwrap());
+        }
+        assert_eq!(UNIX_EPOCH - Duration::new(0, 1), unix(-1, 999_999_999));
+    }
+
+    #[test]
+    fn file_time() {
+        assert_eq!(
+            Ok(116_444_736_000_000_000),
+            FileTime::try_write(FileTime(UNIX_EPOCH))
+        );
+        assert_eq!(
+            Ok(FileTime(UNIX_EPOCH)),
+            FileTime::try_read(116_444_736_000_000_000)
+        );
+        assert_eq!(
+            Ok(FileTime(unix(-11_644_473_600, 0))),
+            FileTime::try_read(0)
+        );
+        assert_eq!(
+            Ok(0),
+            FileTime::try_write(FileTime(unix(-11_644_473_600, 0)))
+        );
+        // 2000-01-01 00:00:00 UTC
+        assert_eq!(
+            Ok(125_911_584_000_000_000),
+            FileTime::try_write(FileTime(unix(946_684_800, 0)))
+        );
+        // Precision below 100ns is truncated
+        assert_eq!(
+            Ok(116_444_736_000_000_001),
+            FileTime::try_write(FileTime(unix(0, 199)))
+        );
+        assert_eq!(
+            Ok(116_444_735_999_999_999),
+            FileTime::try_write(FileTime(unix(-1, 999_999_900)))
+        );
+        assert_eq!(
+            Ok(FileTime(unix(-1, 999_999_900))),
+            FileTime::try_read(116_444_735_999_999_999)
+        );
+    }
+
+    #[test]
+    fn given_time_before_1601_then_file_time_write_fails() {
+        assert_eq!(
+            Err(TimestampOutOfRangeError(())),
+            FileTime::try_write(FileTime(unix(-11_644_473_601, 0)))
+        );
+        assert_eq!(
+            Err(TimestampOutOfRangeError(())),
+            FileTime::try_write(FileTime(unix(-11_644_473_601, 999_999_999)))
+        );
+    }
+
+    #[test]
+    fn ntp() {
+        assert_eq!(
+            Ok((NTP_UNIX_OFFSET_SECS as u64) << 32),
+            NtpTimestamp::try_write(NtpTimestamp(UNIX_EPOCH))
+        );
+        assert_eq!(
+            Ok(NtpTimestamp(UNIX_EPOCH)),
+            NtpTimestamp::try_read((NTP_UNIX_OFFSET_SECS as u64) << 32)
+        );
+        // Half a second
+        assert_eq!(
+            Ok(NtpTimestamp(unix(0, 500_000_000))),
+            NtpTimestamp::try_read(((NTP_UNIX_OFFSET_SECS as u64) << 32) | 0x8000_0000)
+        );
+        // Nanoseconds survive a roundtrip
+        for nanos in [0, 1, 2, 3, 123_456_789, 999_999_999] {
+            let time = NtpTimestamp(unix(1_000_000_000, nanos));
+            assert_eq!(
+                Ok(time),
+                NtpTimestamp::try_read(NtpTimestamp::try_write(time).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn ntp_era_rollover() {
+        // 2036-02-07 06:28:16 UTC is the start of NTP era 1
+        let era1 = unix(NTP_ERA_SECS - NTP_UNIX_OFFSET_SECS, 0);
+        assert_eq!(Ok(0), NtpTimestamp::try_write(NtpTimestamp(era1)));
+        assert_eq!(Ok(NtpTimestamp(era1)), NtpTimestamp::try_read(0));
+        assert_eq!(
+            Ok(NtpTimestamp(era1 - Duration::from_secs(1))),
+            NtpTimestamp::try_read(0xFFFF_FFFF_0000_0000)
+        );
+        // 1968-01-20 03:14:08 UTC is the earliest representable time
+        let earliest = unix(NTP_ERA_SECS / 2 - NTP_UNIX_OFFSET_SECS, 0);
+        assert_eq!(
+            Ok(0x8000_0000_0000_0000),
+            NtpTimestamp::try_write(NtpTimestamp(earliest))
+        );
+        assert_eq!(
+            Ok(NtpTimestamp(earliest)),
+            NtpTimestamp::try_read(0x8000_0000_0000_0000)
+        );
+        // 2104-02-26 09:42:23 UTC is the latest representable second
+        let latest = unix(
+            NTP_ERA_SECS + NTP_ERA_SECS / 2 - 1 - NTP_UNIX_OFFSET_SECS,
+            0,
+        );
+        assert_eq!(
+            Ok(NtpTimestamp(latest)),
+            NtpTimestamp::try_read(0x7FFF_FFFF_0000_0000)
+        );
+        assert_eq!(
+            Ok(0x7FFF_FFFF_0000_0000),
+            NtpTimestamp::try_write(NtpTimestamp(latest))
+        );
+    }
+
+    #[test]
+    fn given_time_out_of_ntp_range_then_write_fails() {
+        let earliest = unix(NTP_ERA_SECS / 2 - NTP_UNIX_OFFSET_SECS, 0);
+        assert_eq!(
+            Err(TimestampOutOfRangeError(())),
+            NtpTimestamp::try_write(NtpTimestamp(earliest - Duration::new(0, 1)))
+        );
+        let latest = unix(NTP_ERA_SECS + NTP_ERA_SECS / 2 - NTP_UNIX_OFFSET_SECS, 0);
+        assert_eq!(
+            Err(TimestampOutOfRangeError(())),
+            NtpTimestamp::try_write(NtpTimestamp(latest))
+        );
+    }
+
+    #[test]
+    fn layout() {
+        let mut storage = [0; 16];
+        let time = unix(1_234_567_890, 100);
+        layout::file_time::try_write(&mut storage, FileTime(time)).unwrap();
+        layout::ntp::try_write(&mut storage, NtpTimestamp(time)).unwrap();
+        assert_eq!(time, layout::file_time::try_read(&storage).unwrap().0);
+        assert_eq!(time, layout::ntp::try_read(&storage).unwrap().0);
+
+        let result = layout::ntp::try_write(&mut storage, NtpTimestamp(unix(-3_000_000_000, 0)));
+        assert!(matches!(
+            result,
+            Err(WrappedFieldError::LayoutAsError(TimestampOutOfRangeError(
+                ()
+            )))
+        ));
+    }
+}
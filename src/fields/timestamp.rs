@@ -0,0 +1,153 @@
+use ::chrono::{DateTime, TimeZone, Utc};
+use core::convert::Infallible;
+
+use crate::LayoutAs;
+
+/// This error is thrown when trying to read a timestamp that is out of the range [DateTime] can represent.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidTimestampError {
+    /// The value that was read from the storage
+    pub value: i64,
+}
+
+impl core::fmt::Display for InvalidTimestampError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "InvalidTimestampError: {}", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidTimestampError {}
+
+/// A point in time stored as the number of seconds since the unix epoch (1970-01-01 00:00:00 UTC).
+/// Use it as `UnixSeconds as i64` in a [binary_layout!](crate::binary_layout!) layout.
+/// This is only available with the `chrono` feature.
+///
+/// Sub-second precision is lost when writing. Reading a value that is out of the range [DateTime] can represent
+/// throws an [InvalidTimestampError], so you need to use [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read) to read these fields.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, UnixSeconds};
+/// use chrono::{TimeZone, Utc};
+///
+/// binary_layout!(log_entry, LittleEndian, {
+///   timestamp: UnixSeconds as i64,
+///   message: [u8],
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 16];
+/// let mut view = log_entry::View::new(&mut storage);
+/// let time = Utc.with_ymd_and_hms(2023, 7, 1, 12, 30, 0).unwrap();
+/// view.timestamp_mut().write(UnixSeconds(time));
+/// assert_eq!(1688214600i64.to_le_bytes(), storage[..8]);
+/// assert_eq!(time, log_entry::timestamp::try_read(&storage).unwrap().0);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixSeconds(pub DateTime<Utc>);
+
+impl LayoutAs<i64> for UnixSeconds {
+    type ReadError = InvalidTimestampError;
+    type WriteError = Infallible;
+
+    fn try_read(v: i64) -> Result<Self, Self::ReadError> {
+        Utc.timestamp_opt(v, 0)
+            .single()
+            .map(UnixSeconds)
+            .ok_or(InvalidTimestampError { value: v })
+    }
+
+    fn try_write(v: Self) -> Result<i64, Self::WriteError> {
+        Ok(v.0.timestamp())
+    }
+}
+
+/// A point in time stored as the number of milliseconds since the unix epoch (1970-01-01 00:00:00 UTC).
+/// Use it as `UnixMillis as i64` in a [binary_layout!](crate::binary_layout!) layout.
+/// This is only available with the `chrono` feature.
+///
+/// Sub-millisecond precision is lost when writing. Reading a value that is out of the range [DateTime] can represent
+/// throws an [InvalidTimestampError], so you need to use [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read) to read these fields.
+/// See [UnixSeconds] for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixMillis(pub DateTime<Utc>);
+
+impl LayoutAs<i64> for UnixMillis {
+    type ReadError = InvalidTimestampError;
+    type WriteError = Infallible;
+
+    fn try_read(v: i64) -> Result<Self, Self::ReadError> {
+        Utc.timestamp_millis_opt(v)
+            .single()
+            .map(UnixMillis)
+            .ok_or(InvalidTimestampError { value: v })
+    }
+
+    fn try_write(v: Self) -> Result<i64, Self::WriteError> {
+        Ok(v.0.timestamp_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::WrappedFieldError;
+
+    binary_layout!(layout, BigEndian, {
+        seconds: UnixSeconds as i64,
+        millis: UnixMillis as i64,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(Some(16), layout::SIZE);
+        assert_eq!(8, layout::millis::OFFSET);
+    }
+
+    #[test]
+    fn read_write() {
+        let mut storage = [0; 16];
+        let time = Utc.timestamp_opt(1_000_000_000, 123_456_789).unwrap();
+        layout::seconds::write(&mut storage, UnixSeconds(time));
+        layout::millis::write(&mut storage, UnixMillis(time));
+        assert_eq!(1_000_000_000i64.to_be_bytes(), storage[..8]);
+        assert_eq!(1_000_000_000_123i64.to_be_bytes(), storage[8..]);
+        assert_eq!(
+            Utc.timestamp_opt(1_000_000_000, 0).unwrap(),
+            layout::seconds::try_read(&storage).unwrap().0
+        );
+        assert_eq!(
+            Utc.timestamp_opt(1_000_000_000, 123_000_000).unwrap(),
+            layout::millis::try_read(&storage).unwrap().0
+        );
+    }
+
+    #[test]
+    fn negative() {
+        let mut storage = [0; 16];
+        let time = Utc.timestamp_opt(-86_400, 0).unwrap();
+        layout::seconds::write(&mut storage, UnixSeconds(time));
+        layout::millis::write(&mut storage, UnixMillis(time));
+        assert_eq!(time, layout::seconds::try_read(&storage).unwrap().0);
+        assert_eq!(time, layout::millis::try_read(&storage).unwrap().0);
+    }
+
+    #[test]
+    fn given_out_of_range_value_then_read_fails() {
+        let mut storage = [0; 16];
+        storage[..8].copy_from_slice(&i64::MAX.to_be_bytes());
+        assert!(matches!(
+            layout::seconds::try_read(&storage),
+            Err(WrappedFieldError::LayoutAsError(InvalidTimestampError {
+                value: i64::MAX
+            })),
+        ));
+        assert_eq!(
+            Err(InvalidTimestampError { value: i64::MIN }),
+            <UnixSeconds as LayoutAs<i64>>::try_read(i64::MIN)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use super::{Field, FieldByteLen};
+
+/// This extension trait allows copying the bytes a field takes up in a storage into a new `Vec<u8>`, e.g. to forward them.
+///
+/// For byte array fields, this is the same as `field::data(storage).to_vec()`. It also works for fields that don't offer
+/// [FieldSliceAccess::data](crate::FieldSliceAccess::data), e.g. integer fields or custom field types, and returns the bytes
+/// as they're stored, i.e. in the byte order of the layout. For fields with a dynamic size, it copies the bytes
+/// the field currently takes up, e.g. the rest of the storage for an open ended byte array.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldToVec};
+///
+/// binary_layout!(packet, BigEndian, {
+///   id: u16,
+///   payload: [u8],
+/// });
+///
+/// # fn main() {
+/// let storage = [0x01, 0x02, 10, 11, 12];
+/// assert_eq!(vec![0x01, 0x02], packet::id::to_vec(&storage));
+/// assert_eq!(vec![10, 11, 12], packet::payload::to_vec(&storage));
+/// # }
+/// ```
+pub trait FieldToVec: Field {
+    /// Copy the bytes of the field in `storage` into a new `Vec<u8>`.
+    #[inline]
+    fn to_vec(storage: &[u8]) -> Vec<u8>
+    where
+        Self: FieldByteLen,
+    {
+        let len = <Self as FieldByteLen>::byte_len(storage);
+        storage[Self::OFFSET..(Self::OFFSET + len)].to_vec()
+    }
+}
+
+impl<F: Field> FieldToVec for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(inner, LittleEndian, {
+        a: u8,
+        b: u8,
+    });
+
+    binary_layout!(layout, LittleEndian, {
+        int: u32,
+        flag: bool as u8,
+        array: [u8; 2],
+        nested: inner::NestedView,
+        len: u8,
+        computed: [u8; expr(|v| v.len().read() as usize)],
+    });
+
+    binary_layout!(open_ended, BigEndian, {
+        int: i16,
+        tail: [u8],
+    });
+
+    #[test]
+    fn copy_fields() {
+        let storage = [1, 2, 3, 4, 5, 6, 7, 8, 9, 2, 10, 11, 12];
+        assert_eq!(vec![1, 2, 3, 4], layout::int::to_vec(&storage));
+        assert_eq!(vec![5], layout::flag::to_vec(&storage));
+        assert_eq!(vec![1, 2], open_ended::int::to_vec(&storage));
+    }
+
+    #[test]
+    fn slice_fields() {
+        let storage = [1, 2, 3, 4, 5, 6, 7, 8, 9, 2, 10, 11, 12];
+        assert_eq!(vec![6, 7], layout::array::to_vec(&storage));
+        assert_eq!(vec![8, 9], layout::nested::to_vec(&storage));
+        assert_eq!(vec![10, 11], layout::computed::to_vec(&storage));
+        assert_eq!(
+            vec![3, 4, 5, 6, 7, 8, 9, 2, 10, 11, 12],
+            open_ended::tail::to_vec(&storage)
+        );
+        assert_eq!(Vec::<u8>::new(), open_ended::tail::to_vec(&storage[..2]));
+    }
+}
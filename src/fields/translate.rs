@@ -0,0 +1,144 @@
+use core::any::Any;
+
+use super::primitive::{FieldCopyAccess, FieldSliceAccess};
+use super::probe::FieldProbe;
+
+/// This trait is implemented by the `View` generated by [binary_layout!](crate::binary_layout!) for layouts with a `#[derive(Translate)]` attribute and allows [translate]
+/// to read the fields of a message. It can't be implemented outside of this crate in a meaningful way.
+pub trait TranslateSource {
+    /// Internal function, don't use!
+    #[doc(hidden)]
+    fn field_values(&self) -> Vec<(&'static str, Box<dyn Any>)>;
+}
+
+/// This trait is implemented by the `View` generated by [binary_layout!](crate::binary_layout!) for layouts with a `#[derive(Translate)]` attribute and storages with write access
+/// and allows [translate] to write the fields of a message. It can't be implemented outside of this crate in a meaningful way.
+pub trait TranslateTarget {
+    /// Internal function, don't use!
+    #[doc(hidden)]
+    fn write_field_value(&mut self, name: &str, value: &dyn Any) -> bool;
+}
+
+/// Copy the fields that two layouts have in common from a message of one layout to a message of the other one,
+/// e.g. to translate messages between two versions of a protocol. Returns the number of fields that were copied.
+/// Both layouts need a `#[derive(Translate)]` attribute.
+///
+/// A field of `src` is copied to the field of `dst` with the same name if both fields are compatible:
+/// - Fields that are read by copying, e.g. integer fields, `NonZeroU32` fields or custom types declared with `as`,
+///   are compatible if they are read as the same type. The value is read from `src` and written to `dst`, so the
+///   layouts can differ in the endianness and in the offsets of the fields. Values are not converted, e.g. a `u16`
+///   field isn't copied to a `u32` field.
+/// - Byte arrays are compatible if they have the same length in both messages.
+///
+/// Other fields, e.g. nested layouts, aren't copied. Fields that fail to read from `src`, e.g. a `NonZeroU32` field storing zero,
+/// or to write to `dst` aren't copied either. Fields of `dst` that aren't copied keep their current value.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, translate};
+///
+/// binary_layout!(#[derive(Translate)] message_v1, BigEndian, {
+///   id: u16,
+///   flags: u8,
+///   name: [u8; 4],
+/// });
+///
+/// binary_layout!(#[derive(Translate)] message_v2, LittleEndian, {
+///   version: u8,
+///   id: u16,
+///   name: [u8; 4],
+///   flags: u8,
+///   timeout: u32,
+/// });
+///
+/// # fn main() {
+/// let v1 = message_v1::View::new([0x01, 0x02, 0x80, b'p', b'i', b'n', b'g']);
+/// let mut v2 = message_v2::View::new([0; 12]);
+/// v2.version_mut().write(2);
+/// assert_eq!(3, translate(&v1, &mut v2));
+/// assert_eq!([2, 0x02, 0x01, b'p', b'i', b'n', b'g', 0x80, 0, 0, 0, 0], v2.into_storage());
+/// # }
+/// ```
+pub fn translate<Src: TranslateSource + ?Sized, Dst: TranslateTarget + ?Sized>(
+    src: &Src,
+    dst: &mut Dst,
+) -> usize {
+    src.field_values()
+        .into_iter()
+        .filter(|(name, value)| dst.write_field_value(name, value.as_ref()))
+        .count()
+}
+
+// Used by the [TranslateSource] and [TranslateTarget] implementations generated by [binary_layout!](crate::binary_layout!)
+// to read and write a field depending on how it can be accessed, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait TranslateCopyField {
+    /// Internal function, don't use!
+    fn read_value(&self, storage: &[u8]) -> Option<Box<dyn Any>>;
+
+    /// Internal function, don't use!
+    fn write_value(&self, storage: &mut [u8], value: &dyn Any) -> bool;
+}
+
+impl<F: FieldCopyAccess> TranslateCopyField for &&FieldProbe<F>
+where
+    F::HighLevelType: Clone + 'static,
+{
+    #[inline]
+    fn read_value(&self, storage: &[u8]) -> Option<Box<dyn Any>> {
+        let value = F::try_read(storage).ok()?;
+        Some(Box::new(value))
+    }
+
+    #[inline]
+    fn write_value(&self, storage: &mut [u8], value: &dyn Any) -> bool {
+        match value.downcast_ref::<F::HighLevelType>() {
+            Some(value) => F::try_write(storage, value.clone()).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Internal trait, don't use!
+pub trait TranslateSliceField {
+    /// Internal function, don't use!
+    fn read_value(&self, storage: &[u8]) -> Option<Box<dyn Any>>;
+
+    /// Internal function, don't use!
+    fn write_value(&self, storage: &mut [u8], value: &dyn Any) -> bool;
+}
+
+impl<F> TranslateSliceField for &FieldProbe<F>
+where
+    F: for<'a> FieldSliceAccess<'a>,
+    for<'a> <F as FieldSliceAccess<'a>>::SliceType: AsRef<[u8]>,
+    for<'a> <F as FieldSliceAccess<'a>>::MutSliceType: AsMut<[u8]>,
+{
+    #[inline]
+    fn read_value(&self, storage: &[u8]) -> Option<Box<dyn Any>> {
+        Some(Box::new(F::data(storage).as_ref().to_vec()))
+    }
+
+    #[inline]
+    fn write_value(&self, storage: &mut [u8], value: &dyn Any) -> bool {
+        let mut data = F::data_mut(storage);
+        let data = data.as_mut();
+        match value.downcast_ref::<Vec<u8>>() {
+            Some(value) if value.len() == data.len() => {
+                data.copy_from_slice(value);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+probe_fallback!(TranslateOtherField {
+    fn read_value(&self, storage: &[u8]) -> Option<Box<dyn Any>> {
+        None
+    }
+    fn write_value(&self, storage: &mut [u8], value: &dyn Any) -> bool {
+        false
+    }
+});
@@ -0,0 +1,247 @@
+use core::convert::TryFrom;
+use core::str::Utf8Error;
+
+use super::primitive::{FieldSliceAccess, FieldView, PrimitiveField};
+use super::{Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView};
+use crate::endianness::Endianness;
+
+/// Field type `Utf8<N>`:
+/// This field type reserves `N` bytes of storage for a UTF-8 encoded string.
+/// Strings shorter than `N` bytes are padded with zero bytes.
+///
+/// Unlike a plain `[u8; N]` field, the layout knows that this field holds text and `try_new`,
+/// generated by [binary_layout!](crate::binary_layout!), checks that the field contains valid UTF-8,
+/// including fields of nested layouts. Formats with lots of text can then validate a message once when
+/// creating the view and use [as_str](PrimitiveField::as_str) afterwards without handling errors.
+/// Views created with `View::new` aren't validated.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(user, LittleEndian, {
+///   id: u32,
+///   name: Utf8<16>,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 20];
+/// user::name::write_str(&mut storage, "Ferris");
+/// let view = user::try_new(&storage).unwrap();
+/// assert_eq!("Ferris", view.name().as_str().trim_end_matches('\0'));
+///
+/// storage[4] = 0xFF;
+/// assert_eq!(
+///     Err(LayoutError::InvalidUtf8 { offset: 4 }),
+///     user::try_new(&storage).map(|_| ()),
+/// );
+/// # }
+/// ```
+pub struct Utf8<const N: usize> {
+    _p: (),
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> PrimitiveField<Utf8<N>, E, OFFSET_> {
+    /// Borrow the field as a string using the [Field] API, or return an error if it isn't valid UTF-8.
+    /// The string includes the zero bytes used as padding.
+    #[inline]
+    pub fn try_as_str(storage: &[u8]) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(&storage[Self::OFFSET..(Self::OFFSET + N)])
+    }
+
+    /// Borrow the field as a string using the [Field] API. The string includes the zero bytes used as padding.
+    ///
+    /// # Panics
+    /// Panics if the field isn't valid UTF-8. This can't happen for views created with `try_new`,
+    /// unless the storage was modified through other means than [write_str](PrimitiveField::write_str) afterwards.
+    #[inline]
+    pub fn as_str(storage: &[u8]) -> &str {
+        Self::try_as_str(storage).expect("Field doesn't contain valid UTF-8")
+    }
+
+    /// Write a string to the field using the [Field] API, padding it with zero bytes.
+    ///
+    /// # Panics
+    /// Panics if the string is longer than `N` bytes.
+    #[inline]
+    pub fn write_str(storage: &mut [u8], value: &str) {
+        assert!(
+            value.len() <= N,
+            "String with {} bytes doesn't fit into a field of {} bytes",
+            value.len(),
+            N
+        );
+        let data = &mut storage[Self::OFFSET..(Self::OFFSET + N)];
+        let (text, padding) = data.split_at_mut(value.len());
+        text.copy_from_slice(value.as_bytes());
+        padding.fill(0);
+    }
+}
+
+impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> FieldSliceAccess<'a>
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    type SliceType = &'a [u8; N];
+    type MutSliceType = &'a mut [u8; N];
+
+    /// Borrow the raw bytes of the field with read access using the [Field] API.
+    /// See also [FieldSliceAccess::data].
+    #[inline(always)]
+    fn data(storage: &'a [u8]) -> &'a [u8; N] {
+        <&[u8; N]>::try_from(&storage[Self::OFFSET..(Self::OFFSET + N)]).unwrap()
+    }
+
+    /// Borrow the raw bytes of the field with write access using the [Field] API.
+    /// See also [FieldSliceAccess::data_mut].
+    #[inline(always)]
+    fn data_mut(storage: &'a mut [u8]) -> &'a mut [u8; N] {
+        <&mut [u8; N]>::try_from(&mut storage[Self::OFFSET..(Self::OFFSET + N)]).unwrap()
+    }
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> Field
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    /// See [Field::Endian]
+    type Endian = E;
+    /// See [Field::OFFSET]
+    const OFFSET: usize = OFFSET_;
+    /// See [Field::SIZE]
+    const SIZE: Option<usize> = Some(N);
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> FieldByteLen
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    #[inline(always)]
+    fn byte_len(_storage: &[u8]) -> usize {
+        N
+    }
+}
+
+impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a [u8]>
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    type View = FieldView<&'a [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<'a, E: Endianness, const N: usize, const OFFSET_: usize> StorageToFieldView<&'a mut [u8]>
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    type View = FieldView<&'a mut [u8], Self>;
+
+    #[inline(always)]
+    fn view(storage: &'a mut [u8]) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+impl<S: AsRef<[u8]>, E: Endianness, const N: usize, const OFFSET_: usize> StorageIntoFieldView<S>
+    for PrimitiveField<Utf8<N>, E, OFFSET_>
+{
+    type View = FieldView<S, Self>;
+
+    #[inline(always)]
+    fn into_view(storage: S) -> Self::View {
+        Self::View::new(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        id: u8,
+        name: Utf8<8>,
+        tail: [u8],
+    });
+
+    binary_layout!(outer, LittleEndian, {
+        header: u16,
+        inner: layout::NestedView,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(1, layout::name::OFFSET);
+        assert_eq!(Some(8), layout::name::SIZE);
+        assert_eq!(9, layout::tail::OFFSET);
+    }
+
+    #[test]
+    fn field_api() {
+        let mut storage = [0xFF; 10];
+        assert!(layout::name::try_as_str(&storage).is_err());
+
+        layout::name::write_str(&mut storage, "äbc");
+        assert_eq!([0xFF, 0xC3, 0xA4, b'b', b'c', 0, 0, 0, 0, 0xFF], storage);
+        assert_eq!(Ok("äbc\0\0\0\0"), layout::name::try_as_str(&storage));
+        assert_eq!("äbc\0\0\0\0", layout::name::as_str(&storage));
+        assert_eq!(&storage[1..9], layout::name::data(&storage));
+
+        layout::name::write_str(&mut storage, "12345678");
+        assert_eq!("12345678", layout::name::as_str(&storage));
+    }
+
+    #[test]
+    fn view_api() {
+        let mut storage = [0; 10];
+        let mut view = layout::View::new(&mut storage);
+        view.name_mut().write_str("hello");
+        assert_eq!("hello\0\0\0", view.name().as_str());
+        assert_eq!(Ok("hello\0\0\0"), view.name().try_as_str());
+        assert_eq!("hello\0\0\0", view.into_name().as_str());
+    }
+
+    #[test]
+    #[should_panic(expected = "String with 9 bytes doesn't fit into a field of 8 bytes")]
+    fn write_too_long() {
+        let mut storage = [0; 10];
+        layout::name::write_str(&mut storage, "123456789");
+    }
+
+    #[test]
+    #[should_panic(expected = "Field doesn't contain valid UTF-8")]
+    fn as_str_invalid() {
+        let storage = [0xFF; 10];
+        layout::name::as_str(&storage);
+    }
+
+    #[test]
+    fn try_new_validates() {
+        let mut storage = [0; 12];
+        layout::name::write_str(&mut storage, "ok");
+        storage[10] = 0xFF; // invalid UTF-8 in the byte array isn't checked
+        assert!(layout::try_new(&storage).is_ok());
+
+        // A multi byte character that is cut off at the end of the field
+        storage[8] = 0xC3;
+        assert_eq!(
+            Err(LayoutError::InvalidUtf8 { offset: 1 }),
+            layout::try_new(&storage).map(|_| ())
+        );
+        // View::new doesn't validate
+        assert!(layout::View::new(&storage).name().try_as_str().is_err());
+    }
+
+    #[test]
+    fn try_new_validates_nested_layouts() {
+        let mut storage = [0; 14];
+        outer::inner::view_mut(&mut storage)
+            .name_mut()
+            .write_str("ok");
+        assert!(outer::try_new(&storage).is_ok());
+
+        storage[5] = 0xFF;
+        assert_eq!(
+            Err(LayoutError::InvalidUtf8 { offset: 3 }),
+            outer::try_new(&storage).map(|_| ())
+        );
+    }
+}
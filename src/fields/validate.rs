@@ -0,0 +1,62 @@
+use super::pascal_string::{PascalString, PascalStringLength};
+use super::primitive::{NestedViewInfo, PrimitiveField};
+use super::probe::FieldProbe;
+use super::utf8::Utf8;
+use super::Field;
+use crate::endianness::Endianness;
+use crate::LayoutError;
+
+// Used by the `try_new` function generated by [binary_layout!](crate::binary_layout!) to validate the contents of
+// fields that have a validation, e.g. [Utf8] fields, and to skip all other fields, see [FieldProbe].
+
+/// Internal trait, don't use!
+pub trait ValidateCheckedField {
+    /// Internal function, don't use!
+    fn validate(&self, storage: &[u8]) -> Result<(), LayoutError>;
+}
+
+impl<E: Endianness, const N: usize, const OFFSET_: usize> ValidateCheckedField
+    for &FieldProbe<PrimitiveField<Utf8<N>, E, OFFSET_>>
+{
+    #[inline]
+    fn validate(&self, storage: &[u8]) -> Result<(), LayoutError> {
+        match PrimitiveField::<Utf8<N>, E, OFFSET_>::try_as_str(storage) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(LayoutError::InvalidUtf8 { offset: OFFSET_ }),
+        }
+    }
+}
+
+impl<L: PascalStringLength, E: Endianness, const OFFSET_: usize> ValidateCheckedField
+    for &FieldProbe<PrimitiveField<PascalString<L>, E, OFFSET_>>
+{
+    #[inline]
+    fn validate(&self, storage: &[u8]) -> Result<(), LayoutError> {
+        match PrimitiveField::<PascalString<L>, E, OFFSET_>::try_as_str(storage) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(LayoutError::InvalidUtf8 { offset: OFFSET_ }),
+        }
+    }
+}
+
+impl<V: NestedViewInfo, E: Endianness, const OFFSET_: usize> ValidateCheckedField
+    for &FieldProbe<PrimitiveField<V, E, OFFSET_>>
+{
+    #[inline]
+    fn validate(&self, storage: &[u8]) -> Result<(), LayoutError> {
+        V::validate(&storage[PrimitiveField::<V, E, OFFSET_>::OFFSET..]).map_err(
+            |error| match error {
+                LayoutError::InvalidUtf8 { offset } => LayoutError::InvalidUtf8 {
+                    offset: OFFSET_ + offset,
+                },
+                error => error,
+            },
+        )
+    }
+}
+
+probe_fallback!(ValidateOtherField {
+    fn validate(&self, storage: &[u8]) -> Result<(), LayoutError> {
+        Ok(())
+    }
+});
@@ -7,8 +7,8 @@ use thiserror::Error;
 use crate::utils::infallible::IsInfallible;
 
 use super::{
-    primitive::{FieldCopyAccess, FieldView},
-    Field, StorageIntoFieldView, StorageToFieldView,
+    primitive::{FieldCopyAccess, FieldView, FieldWriteExt},
+    Field, FieldByteLen, StorageIntoFieldView, StorageToFieldView,
 };
 
 /// Implementing the [LayoutAs] trait for a custom type allows that custom type to be used
@@ -42,6 +42,34 @@ use super::{
 ///
 /// # fn main() {}
 /// ```
+///
+/// The type after `as` has to match the type parameter of the [LayoutAs] implementation. Otherwise, compiling the layout fails,
+/// and one of the errors points at the field and mentions the unsatisfied bound of `field_type_must_implement_LayoutAs_for_the_type_after_as`.
+///
+/// ```compile_fail
+/// use binary_layout::{prelude::*, LayoutAs};
+/// use core::convert::Infallible;
+///
+/// struct MyIdType(u64);
+/// impl LayoutAs<u64> for MyIdType {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///   fn try_read(v: u64) -> Result<MyIdType, Infallible> {
+///     Ok(MyIdType(v))
+///   }
+///
+///   fn try_write(v: MyIdType) -> Result<u64, Infallible> {
+///     Ok(v.0)
+///   }
+/// }
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   // fails to compile because MyIdType doesn't implement LayoutAs<u32>
+///   field: MyIdType as u32,
+/// });
+///
+/// # fn main() {}
+/// ```
 pub trait LayoutAs<U>: Sized {
     /// See [FieldCopyAccess::ReadError].
     ///
@@ -65,6 +93,73 @@ pub trait LayoutAs<U>: Sized {
     fn try_write(v: Self) -> Result<U, Self::WriteError>;
 }
 
+/// Implement the [LayoutAsSaturating] trait in addition to [LayoutAs] for custom types whose values can be out of the range
+/// that can be stored in the layout, e.g. integers with a custom width or fixed-point numbers. It makes
+/// [WrappedField::write_saturating] and [FieldView::write_saturating](crate::FieldView::write_saturating) available for the type.
+///
+/// Protocols handle out-of-range values differently, so these are the overflow policies you can offer for a custom type:
+/// - [FieldCopyAccess::try_write] calls [LayoutAs::try_write], which should throw an error for values that are out of range.
+/// - [FieldWriteExt::write] is only available if [LayoutAs::WriteError] is [Infallible]. In this case, [LayoutAs::try_write] has to
+///   handle out-of-range values itself, e.g. by wrapping them around.
+/// - `write_saturating` calls [LayoutAsSaturating::write_saturating], which should clamp values that are out of range to the closest representable value.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, LayoutAs, LayoutAsSaturating};
+///
+/// // A temperature in degrees celsius, stored as a fixed-point number in units of 1/100 degrees
+/// #[derive(Debug, PartialEq)]
+/// struct Celsius(f32);
+///
+/// #[derive(Debug)]
+/// struct OutOfRange;
+///
+/// impl LayoutAs<i16> for Celsius {
+///   type ReadError = core::convert::Infallible;
+///   type WriteError = OutOfRange;
+///
+///   fn try_read(v: i16) -> Result<Celsius, Self::ReadError> {
+///     Ok(Celsius(f32::from(v) / 100.0))
+///   }
+///
+///   fn try_write(v: Celsius) -> Result<i16, OutOfRange> {
+///     let centi = (v.0 * 100.0).round();
+///     if centi >= f32::from(i16::MIN) && centi <= f32::from(i16::MAX) {
+///       Ok(centi as i16)
+///     } else {
+///       Err(OutOfRange)
+///     }
+///   }
+/// }
+///
+/// impl LayoutAsSaturating<i16> for Celsius {
+///   fn write_saturating(v: Celsius) -> i16 {
+///     // float to int casts saturate
+///     (v.0 * 100.0).round() as i16
+///   }
+/// }
+///
+/// binary_layout!(sensor, LittleEndian, {
+///   temperature: Celsius as i16,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 2];
+/// assert!(sensor::temperature::try_write(&mut storage, Celsius(500.0)).is_err());
+///
+/// sensor::temperature::write_saturating(&mut storage, Celsius(500.0));
+/// assert_eq!(Celsius(327.67), sensor::temperature::read(&storage));
+///
+/// sensor::View::new(&mut storage).temperature_mut().write_saturating(Celsius(-21.5));
+/// assert_eq!(Celsius(-21.5), sensor::temperature::read(&storage));
+/// # }
+/// ```
+pub trait LayoutAsSaturating<U>: LayoutAs<U> {
+    /// Implement this to define how the custom type is converted into the underlying type, clamping values
+    /// that are out of range to the closest value the underlying type can represent.
+    fn write_saturating(v: Self) -> U;
+}
+
 /// The error being thrown when reading or writing fields that use custom data types implemented via [LayoutAs].
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Error))]
@@ -172,6 +267,38 @@ impl IsInfallible for WrappedFieldError<Infallible, Infallible> {}
 /// #   func(&mut storage);
 /// # }
 /// ```
+///
+/// # Using fields directly
+/// The `field: MyIdType as u64` syntax of [binary_layout!](crate::binary_layout!) generates a `WrappedField<u64, MyIdType, PrimitiveField<u64, E, OFFSET>>`.
+/// You can also name this type yourself. `U` is the type stored in the layout, `T` is the custom type converted from/to it
+/// and `F` is the field storing the `U` value, which defines the endianness and the offset, see [PrimitiveField](crate::PrimitiveField#using-fields-directly).
+///
+/// ```
+/// use binary_layout::{prelude::*, LayoutAs};
+/// use core::convert::Infallible;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct MyIdType(u64);
+/// impl LayoutAs<u64> for MyIdType {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///
+///   fn try_read(v: u64) -> Result<MyIdType, Infallible> {
+///     Ok(MyIdType(v))
+///   }
+///
+///   fn try_write(v: MyIdType) -> Result<u64, Infallible> {
+///     Ok(v.0)
+///   }
+/// }
+///
+/// type Id = WrappedField<u64, MyIdType, PrimitiveField<u64, LittleEndian, 2>>;
+///
+/// let mut storage = [0; 10];
+/// Id::write(&mut storage, MyIdType(5));
+/// assert_eq!([0, 0, 5, 0, 0, 0, 0, 0, 0, 0], storage);
+/// assert_eq!(MyIdType(5), Id::read(&storage));
+/// ```
 pub struct WrappedField<U, T: LayoutAs<U>, F: Field> {
     _p1: PhantomData<U>,
     _p2: PhantomData<T>,
@@ -187,6 +314,15 @@ impl<U, T: LayoutAs<U>, F: Field> Field for WrappedField<U, T, F> {
     const SIZE: Option<usize> = F::SIZE;
 }
 
+impl<U, T: LayoutAs<U>, F: FieldByteLen> FieldByteLen for WrappedField<U, T, F> {
+    const MIN_LEN: usize = F::MIN_LEN;
+
+    #[inline(always)]
+    fn byte_len(storage: &[u8]) -> usize {
+        F::byte_len(storage)
+    }
+}
+
 impl<
         'a,
         U,
@@ -299,6 +435,15 @@ impl<U, T: LayoutAs<U>, F: FieldCopyAccess<HighLevelType = U>> FieldCopyAccess
     }
 }
 
+impl<U, T: LayoutAsSaturating<U>, F: FieldWriteExt<HighLevelType = U>> WrappedField<U, T, F> {
+    /// Write the field to a given data region, assuming the defined layout, using the [Field] API.
+    /// Values that are out of range are clamped to the closest representable value, see [LayoutAsSaturating].
+    #[inline(always)]
+    pub fn write_saturating(storage: &mut [u8], v: T) {
+        F::write(storage, <T as LayoutAsSaturating<U>>::write_saturating(v))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::float_cmp)]
@@ -618,4 +763,69 @@ mod tests {
             assert_eq!(0, u8::from_le_bytes((&storage[5..6]).try_into().unwrap()));
         }
     }
+
+    mod saturating {
+        use super::*;
+        use crate::{FieldView, LayoutAsSaturating, WrappedFieldError};
+
+        // A value in the range 0..=1000, stored as a u16
+        #[derive(Debug, PartialEq, Eq)]
+        struct Limited(u32);
+        #[derive(Debug, PartialEq, Eq)]
+        struct OutOfRange;
+        impl LayoutAs<u16> for Limited {
+            type ReadError = Infallible;
+            type WriteError = OutOfRange;
+
+            fn try_read(v: u16) -> Result<Self, Infallible> {
+                Ok(Self(u32::from(v)))
+            }
+
+            fn try_write(v: Self) -> Result<u16, OutOfRange> {
+                if v.0 <= 1000 {
+                    Ok(v.0 as u16)
+                } else {
+                    Err(OutOfRange)
+                }
+            }
+        }
+        impl LayoutAsSaturating<u16> for Limited {
+            fn write_saturating(v: Self) -> u16 {
+                v.0.min(1000) as u16
+            }
+        }
+
+        type Field1 = WrappedField<u16, Limited, PrimitiveField<u16, BigEndian, 5>>;
+
+        #[test]
+        fn given_value_in_range_then_writes_value() {
+            let mut storage = [0; 1024];
+            Field1::write_saturating(&mut storage, Limited(500));
+            assert_eq!(Limited(500), Field1::read(&storage));
+            assert_eq!(&500u16.to_be_bytes(), &storage[5..7]);
+        }
+
+        #[test]
+        fn given_value_out_of_range_then_clamps() {
+            let mut storage = [0; 1024];
+            assert!(matches!(
+                Field1::try_write(&mut storage, Limited(70000)),
+                Err(WrappedFieldError::LayoutAsError(OutOfRange)),
+            ));
+            assert_eq!(Limited(0), Field1::read(&storage));
+
+            Field1::write_saturating(&mut storage, Limited(70000));
+            assert_eq!(Limited(1000), Field1::read(&storage));
+        }
+
+        #[test]
+        fn view() {
+            let mut storage = [0; 1024];
+            let mut view = FieldView::<_, Field1>::new(&mut storage);
+            view.write_saturating(Limited(2000));
+            assert_eq!(Limited(1000), view.read());
+            view.write_saturating(Limited(20));
+            assert_eq!(Limited(20), view.read());
+        }
+    }
 }
@@ -0,0 +1,160 @@
+use super::{
+    primitive::{FieldSliceAccess, FieldWriteExt, PrimitiveField},
+    Field,
+};
+
+/// This error is thrown by [FieldWriteFromIter::write_from_iter] when the iterator yields more values than fit into the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayFullError(pub(crate) ());
+
+impl core::fmt::Display for ArrayFullError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "ArrayFullError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArrayFullError {}
+
+/// This extension trait allows filling byte array fields with a sequence of numbers, e.g. a table generated programmatically.
+///
+/// This library doesn't have array fields of types other than [u8] (see the crate level docs for why), but numeric arrays
+/// can be stored in a byte array field like `[u8; 16]` holding four [u32] values. [FieldWriteFromIter::write_from_iter]
+/// encodes each value with the endianness of the layout and writes the values back to back.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, FieldWriteFromIter};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   count: u8,
+///   table: [u8; 8],
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 9];
+/// let written = my_layout::table::write_from_iter(&mut storage, (1..=4).map(|i: u16| i * 100)).unwrap();
+/// my_layout::count::write(&mut storage, written as u8);
+/// assert_eq!([4, 0, 100, 0, 200, 1, 44, 1, 144], storage);
+/// # }
+/// ```
+pub trait FieldWriteFromIter: Field {
+    /// Write the values yielded by `iter` into the field, each one encoded as a `T` with the endianness of the layout.
+    ///
+    /// - If the iterator yields fewer values than the field holds, the values are written to the beginning of the field,
+    ///   the remaining bytes of the field are left unchanged, and the number of values written is returned.
+    /// - If the iterator yields more values than the field holds, the field is filled, the first value that doesn't fit is
+    ///   dropped, and [ArrayFullError] is returned. The iterator isn't advanced any further.
+    ///
+    /// If the length of the field isn't a multiple of the size of `T`, the remaining bytes at the end can't hold a value
+    /// and are left unchanged.
+    fn write_from_iter<'a, T, I>(storage: &'a mut [u8], iter: I) -> Result<usize, ArrayFullError>
+    where
+        Self: FieldSliceAccess<'a>,
+        <Self as FieldSliceAccess<'a>>::MutSliceType: AsMut<[u8]>,
+        PrimitiveField<T, Self::Endian, 0>: FieldWriteExt<HighLevelType = T>,
+        I: IntoIterator<Item = T>,
+    {
+        let element_size = <PrimitiveField<T, Self::Endian, 0> as Field>::SIZE
+            .expect("Error: write_from_iter can only write values with a static size");
+        let mut data = Self::data_mut(storage);
+        let mut chunks = data.as_mut().chunks_exact_mut(element_size);
+        let mut written = 0;
+        for value in iter {
+            match chunks.next() {
+                Some(chunk) => {
+                    PrimitiveField::<T, Self::Endian, 0>::write(chunk, value);
+                    written += 1;
+                }
+                None => return Err(ArrayFullError(())),
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl<F: Field> FieldWriteFromIter for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    binary_layout!(layout, LittleEndian, {
+        len: u8,
+        table: [u8; 8],
+        tail: [u8],
+    });
+
+    #[test]
+    fn fewer_values() {
+        let mut storage = [0xFF; 9];
+        assert_eq!(
+            Ok(2),
+            layout::table::write_from_iter(&mut storage, [1u16, 2])
+        );
+        assert_eq!([0xFF, 1, 0, 2, 0, 0xFF, 0xFF, 0xFF, 0xFF], storage);
+    }
+
+    #[test]
+    fn exact_number_of_values() {
+        let mut storage = [0; 9];
+        assert_eq!(
+            Ok(2),
+            layout::table::write_from_iter(&mut storage, [1.5f32, -2.0])
+        );
+        assert_eq!(&1.5f32.to_le_bytes(), &storage[1..5]);
+        assert_eq!(&(-2.0f32).to_le_bytes(), &storage[5..9]);
+    }
+
+    #[test]
+    fn more_values() {
+        let mut storage = [0; 9];
+        let mut iter = 1u32..;
+        assert_eq!(
+            Err(ArrayFullError(())),
+            layout::table::write_from_iter(&mut storage, &mut iter)
+        );
+        assert_eq!([0, 1, 0, 0, 0, 2, 0, 0, 0], storage);
+        assert_eq!(Some(4), iter.next());
+    }
+
+    #[test]
+    fn remaining_bytes() {
+        let mut storage = [0; 9];
+        assert_eq!(
+            Err(ArrayFullError(())),
+            layout::table::write_from_iter(&mut storage, [1u32 << 24, 2, 3].iter().copied())
+        );
+        assert_eq!(
+            Ok(2),
+            layout::table::write_from_iter(&mut storage, [0x0102_0304u32, 0x0506_0708])
+        );
+        assert_eq!([0, 4, 3, 2, 1, 8, 7, 6, 5], storage);
+
+        let mut storage = [0; 9];
+        assert_eq!(
+            Err(ArrayFullError(())),
+            layout::table::write_from_iter(&mut storage, [1u64 << 56, 2])
+        );
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 0, 1], storage);
+        assert_eq!(
+            Ok(0),
+            layout::table::write_from_iter(&mut storage, core::iter::empty::<u128>())
+        );
+        assert_eq!(
+            Err(ArrayFullError(())),
+            layout::table::write_from_iter(&mut storage, [0u128])
+        );
+    }
+
+    #[test]
+    fn open_ended() {
+        let mut storage = [0; 15];
+        assert_eq!(
+            Ok(3),
+            layout::tail::write_from_iter(&mut storage, [1i16, -1, 2])
+        );
+        assert_eq!([1, 0, 0xFF, 0xFF, 2, 0], storage[9..]);
+    }
+}
@@ -7,6 +7,7 @@
 //! All accessors access the underlying packet data directly.
 //!
 //! This crate is `#[no_std]` compatible.
+//! It supports Rust 1.59 and newer, except for [assert_layout_matches!], which needs Rust 1.77 or newer.
 //!
 //! # Example
 //! ```
@@ -86,6 +87,9 @@
 //!    This is not the API used in the example above, see [trait@Field] for an API example.
 //! 2. The [struct@FieldView] API that wraps a slice of storage data and remembers it in a `View` object, allowing access to the fields without having to pass in the packed data slice each time. This is the API used in the example above. See [struct@FieldView] for another example.
 //!
+//! With the `derive` feature enabled, layouts can also be defined by annotating a struct with `#[derive(BinaryLayout)]` instead of using the [binary_layout!] macro.
+//! This generates the same module with the same APIs, see `BinaryLayout` for details.
+//!
 //! ## Supported field types
 //!
 //! ### Primitive integer types
@@ -93,9 +97,12 @@
 //! - [i8](https://doc.rust-lang.org/stable/core/primitive.i8.html), [i16](https://doc.rust-lang.org/stable/core/primitive.i16.html), [i32](https://doc.rust-lang.org/stable/core/primitive.i32.html), [i64](https://doc.rust-lang.org/stable/core/primitive.i64.html), [i128](https://doc.rust-lang.org/stable/core/primitive.i128.html)
 //!
 //! For these fields, the [trait@Field] API offers [FieldReadExt::read], [FieldWriteExt::write], [FieldCopyAccess::try_read], [FieldCopyAccess::try_write] and the [struct@FieldView] API offers [FieldView::read] and [FieldView::write].
+//! Integer fields additionally offer a `const fn read_const` in the [trait@Field] API, e.g. `const VALUE: u32 = my_layout::value::read_const(&CONFIG);`,
+//! to read fields from data that is known at compile time into constants.
 //!
 //! ### Primitive float types
 //! - [f32](https://doc.rust-lang.org/core/primitive.f32.html), [f64](https://doc.rust-lang.org/core/primitive.f64.html)
+//! - With the `half` feature enabled, [half::f16](https://docs.rs/half/latest/half/struct.f16.html) and [half::bf16](https://docs.rs/half/latest/half/struct.bf16.html). The raw 16 bit pattern is stored, so NaN payloads and subnormals round trip unchanged.
 //!
 //! ### Non-zero primitive integer types
 //! - [NonZeroU8](https://doc.rust-lang.org/core/num/struct.NonZeroU8.html), [NonZeroU16](https://doc.rust-lang.org/core/num/struct.NonZeroU16.html), [NonZeroU32](https://doc.rust-lang.org/core/num/struct.NonZeroU32.html), [NonZeroU64](https://doc.rust-lang.org/core/num/struct.NonZeroU64.html), [NonZeroU128](https://doc.rust-lang.org/core/num/struct.NonZeroU128.html)
@@ -109,6 +116,34 @@
 //! Note that not only `0u8` and `1u8` are valid boolean values and not all [u32](https://doc.rust-lang.org/stable/core/primitive.u32.html) values are valid unicode code points.
 //! Reading invalid values will throw an error. Because of this, [FieldReadExt::read] and [FieldView::read] are not available for those types and you need to use [FieldCopyAccess::try_read] and [FieldView::try_read].
 //!
+//! ### Timestamps
+//! With the `chrono` feature enabled, `UnixSeconds as i64` and `UnixMillis as i64` fields store a `chrono::DateTime<Utc>`
+//! as the number of seconds or milliseconds since the unix epoch.
+//! Stored values can be out of the range `chrono` can represent. Reading those will throw an error, so [FieldReadExt::read] and [FieldView::read]
+//! are not available for those types and you need to use [FieldCopyAccess::try_read] and [FieldView::try_read].
+//!
+//! With the `std` feature enabled, `FileTime as u64` (Windows `FILETIME`) and `NtpTimestamp as u64` (64 bit NTP timestamps) fields
+//! store a [std::time::SystemTime]. Reading and writing them can fail for times out of range of the respective format.
+//!
+//! ### Sign-magnitude and excess-K integers
+//! `SignMagnitude<i16> as u16` fields store a signed integer with the sign in the most significant bit, and `ExcessK<i16, 127> as u8`
+//! fields store a signed integer `v` as the unsigned integer `v + 127`. Not all values can be represented in these encodings,
+//! so you need to use [FieldCopyAccess::try_write] or `write_saturating`, see [SignMagnitude] and [ExcessK].
+//!
+//! ### Scaled values
+//! `Scaled<u16, 1, 100> as u16` fields store an [f64] value as an integer scaled by a rational factor, e.g. a percentage in units of 1/100 percent.
+//! Reading returns the stored integer times `NUM / DEN`. Writing rounds to the nearest integer and throws an error if it doesn't fit,
+//! so you need to use [FieldCopyAccess::try_write] or `write_saturating`, see [struct@Scaled].
+//! For latitudes and longitudes stored in units of 1e-7 degrees, use `ScaledDegrees as i32`, see [ScaledDegrees].
+//!
+//! With the `num-rational` feature enabled, `FixedRatio<i32, 100> as i32` fields store an exact `num_rational::Ratio<i64>` as an integer
+//! in units of `1 / DENOM`, e.g. prices in cents. Writing a value that isn't a multiple of `1 / DENOM` or doesn't fit throws an error,
+//! so you need to use [FieldCopyAccess::try_write], see `FixedRatio`.
+//!
+//! ### Bit-reversed integers
+//! `BitReversed<u8> as u8` fields store an unsigned integer with its bits in reverse order, e.g. for serial protocols that transmit
+//! the least significant bit first, see [BitReversed].
+//!
 //! ### Primitive Zero-Sized Types (ZSTs)
 //!
 //! ZSTs neither read nor write to the underlying storage, but the appropriate traits are implemented for them to support derive macros which may require all members of a struct to implement or enum to also support the various traits.
@@ -123,9 +158,68 @@
 //! This field has a dynamic size, depending on how large the packet data is.
 //! For these fields, the [trait@Field] API offers [FieldSliceAccess::data], [FieldSliceAccess::data_mut] and the [struct@FieldView] API returns a slice.
 //!
+//! ### Byte arrays with computed length: `[u8; expr(...)]`
+//! This field type has a length that is computed from the values of other fields, for example from a length field stored earlier in the packet.
+//! The length is given as a closure that takes a `&View<&[u8]>` of the layout and returns the length in bytes:
+//! ```
+//! use binary_layout::prelude::*;
+//!
+//! binary_layout!(message, BigEndian, {
+//!   header_len: u16,
+//!   flags: u8,
+//!   // the header_len field counts an 8 byte prefix that isn't part of this layout
+//!   header: [u8; expr(|v| v.header_len().read() as usize - 8)],
+//! });
+//! ```
+//! The closure is evaluated each time the field is accessed, so it always reflects the current values of the fields it depends on.
+//! It should only read fields that come before it in the layout. If the computed length reaches beyond the end of the storage,
+//! accessing the field panics, just like accessing any other field outside of the storage. If your length computation can underflow
+//! or overflow, consider using checked or saturating arithmetic in the closure.
+//!
+//! Like open ended byte arrays, this field has a dynamic size and can only occur as the last field of a layout.
+//! For these fields, the [trait@Field] API offers [FieldSliceAccess::data], [FieldSliceAccess::data_mut] and the [struct@FieldView] API returns a slice.
+//!
+//! ### UTF-8 strings with a fixed number of bytes: `Utf8<N>`
+//! This field type reserves `N` bytes for a UTF-8 encoded string, padded with zero bytes.
+//! The [trait@Field] and [struct@FieldView] APIs offer `try_as_str`, `as_str` and `write_str`, see [struct@Utf8].
+//! The generated `try_new` function checks that all `Utf8<N>` fields of a layout contain valid UTF-8, so `as_str` doesn't panic afterwards.
+//!
+//! ### Length-prefixed UTF-8 strings: `PascalString<L>`
+//! This field type stores a UTF-8 encoded string after its length in bytes, which is stored as an integer of type `L`, e.g. [u16].
+//! The [trait@Field] and [struct@FieldView] APIs offer `try_as_str`, `as_str`, `to_str_lossy` and `write_str`, see [struct@PascalString].
+//! Like byte arrays with computed length, this field has a dynamic size and can only occur as the last field of a layout.
+//!
+//! ### Bit packed arrays: `PackedArray<BITS, N>`
+//! This field type stores `N` unsigned integers with `BITS` bits each (1 to 16 bits) back to back without padding, e.g. 12 bit samples.
+//! The [trait@Field] and [struct@FieldView] APIs offer `get` and `set` to access elements by index, see [struct@PackedArray] for the bit order.
+//!
+//! ### Bit fields: `Bits<O, BYTES, START, LEN>`
+//! This field type stores an unsigned integer with `LEN` bits starting at bit `START` of `BYTES` bytes, e.g. a signal in a CAN frame.
+//! Bits are numbered with the bit order `O`, either [struct@Lsb0] or [struct@Msb0], independently of the byte order of the layout.
+//! Several bit fields sharing the same bytes are declared in a `union { ... }` block, see [struct@Bits] for an example.
+//! Headers specified with RFC style diagrams, which number bits from the most significant bit of the first byte, can declare fields as
+//! `version: bits 0..4` to use the bit numbers of the diagram directly, see [binary_layout!](crate::binary_layout#bit-fields-with-rfc-bit-numbering) for an IPv4 example.
+//!
 //! ### Custom field types
 //! You can define your own custom types as long as they implement the [trait@LayoutAs] trait to define how to convert them from/to a primitive type.
 //!
+//! If values of your custom type can be out of the range that can be stored, you can additionally implement [trait@LayoutAsSaturating]
+//! to offer a `write_saturating` accessor that clamps them instead of throwing an error.
+//!
+//! The [assert_layout_as_roundtrip!] macro generates a test checking that your [trait@LayoutAs] implementation writes back each stored value it reads unchanged.
+//!
+//! For fieldless enums, the [layout_as_enum!] macro implements [trait@LayoutAs] for you and lets you choose the integer type
+//! the discriminant is stored as independently of the `#[repr(...)]` of the enum. A catch-all variant marked with `#[layout_unknown]`, e.g. `Unknown(u8)`,
+//! keeps values that don't match any other variant instead of failing to read them.
+//!
+//! Custom types spanning two adjacent primitive values, e.g. a version stored as a [u32] followed by a [u16], can implement
+//! [trait@LayoutAsComposite] instead and be used as `field: MyType as (u32, u16)`. Start/end pairs can be read as a [Range](core::ops::Range)
+//! with `field: Range<u32> as (u32, u32)`, or as a [CheckedRange] that fails to read if the start is after the end.
+//!
+//! Arrays of a custom type are declared as `field: [MyType; N] as u32`. They store `N` values back to back, each converted with the
+//! [trait@LayoutAs] implementation of the custom type, and offer `get` and `set` (or `try_get` and `try_set`) to access elements by index,
+//! see [struct@LayoutAsArray].
+//!
 //! # Data types maybe supported in the future
 //! These data types aren't supported yet, but they could be added in theory and might be added in future versions.
 //! - bit fields / [bool](https://doc.rust-lang.org/stable/core/primitive.bool.html) stored as 1 bit
@@ -133,7 +227,7 @@
 //! ### Data types with dynamic length
 //! This crate relies on a static layout, it cannot support data types with dynamic length.
 //! In theory, types with dynamic length could be supported if they either
-//! - are the last field of a layout, an already implemented example of this are open ended byte arrays.
+//! - are the last field of a layout, already implemented examples of this are open ended byte arrays and byte arrays with computed length.
 //! - or they may be in the middle of the packet but have a maximal size defined and will always reserve storage for their maximal size, even if smaller.
 //!   This way, the fields after it would still have a constant offset.
 //!
@@ -141,13 +235,31 @@
 //!
 //! ### Strings
 //! For strings, note that even fixed-size UTF-8 strings take a variable number of bytes because of the UTF-8 encoding and that brings all the issues of data types with dynamic length with it.
-//! This is why strings aren't supported yet.
+//! This is why only strings with a fixed number of bytes (`Utf8<N>`) and length-prefixed strings at the end of a layout (`PascalString<L>`) are supported yet.
 //!
 //! ### Fixed-size arrays other than `[u8; N]`
 //! Say we wanted to have a `[u32; N]` field. The API couldn't just return a zero-copy `&[u32; N]` to the caller because that would use the system byte order (i.e. endianness) which might be different from the byte order defined in the packet layout.
 //! To make this cross-platform compatible, we'd have to wrap these slices into our own slice type that enforces the correct byte order and return that from the API.
 //! This complexity is why it wasn't implemented yet, but feel free to open a PR if you need this.
 //!
+//! # Panics
+//! Like indexing into a slice, the accessors of this library panic if the storage is too short for the field accessed.
+//! Code that needs to be free of panics, e.g. `#![no_std]` firmware, can avoid them as follows:
+//! - `View::new` never panics, but the generated `try_new` function additionally checks that the storage is long enough for the layout and returns a [LayoutError] otherwise.
+//!   After a successful `try_new`, the accessors of the `View` don't panic for fields with a static size,
+//!   as long as the view doesn't get access to a shorter storage.
+//! - The [trait@FieldCheckedAccess] trait offers [FieldCheckedAccess::checked_read], [FieldCheckedAccess::checked_write], [FieldCheckedAccess::checked_data]
+//!   and [FieldCheckedAccess::checked_data_mut] for the [trait@Field] API. They check the bounds of the field on each access and return a [LayoutError]
+//!   instead of panicking. Use [FieldCheckedAccess::check_bounds] before [FieldCopyAccess::try_read] or [FieldCopyAccess::try_write] for fields whose reads or writes can fail.
+//!   [FieldCheckedAccess::peek] returns `None` instead, e.g. to read a field from a partially received frame.
+//! - [FieldReadExt::read], [FieldWriteExt::write] and [InfallibleResultExt::infallible_unwrap] never panic for reasons other than the length of the storage,
+//!   they're only available if the underlying operation can't fail.
+//!
+//! Some things can panic regardless of the length of the storage:
+//! - The length closure of [byte arrays with computed length](#byte-arrays-with-computed-length-u8-expr) is user code and panics if it, for example, underflows in a debug build.
+//!   Since the length of these fields depends on the values of other fields, writing to those fields can make a field reach beyond the end of a storage that was long enough before.
+//! - Custom field types call the conversions of their [trait@LayoutAs] implementation, which can panic.
+//!
 //! # Nesting
 //! Layouts can be nested within each other by using the `NestedView` type created by the [binary_layout!] macro for one layout as a field type in another layout.
 //!
@@ -168,6 +280,16 @@
 //! # fn main() {}
 //! ```
 //!
+//! Nested layouts with a static size can be placed anywhere in a layout and the fields following them are placed after them.
+//! Nested layouts without a static size, e.g. because they end in an open ended byte array, can only be used as the last field.
+//!
+//! Besides the accessors on the `View`, nested fields offer `view(&storage)`, `view_mut(&mut storage)` and `into_view(storage)`
+//! functions, e.g. `icmp_packet::header::view(&storage)`. They return the same `icmp_header::View` type you would get from `icmp_header::View::new`.
+//!
+//! To access a field of a nested layout directly, without constructing views for the intermediate layouts, put a `#[flatten(...)]` attribute
+//! on the nested field. For example, `#[flatten(icmp_header::{code})] header: icmp_header::NestedView` generates an `icmp_packet::header_code`
+//! [NestedField] at the absolute offset of the `code` field within an `icmp_packet`, see [flattening nested layouts](binary_layout!#flattening-nested-layouts).
+//!
 //! Nested layouts do not need to have the same endianess.  The following, which
 //! is copied from the complete example at `tests/nested.rs` in this repository,
 //! shows how you can mix different endian layouts together:
@@ -200,27 +322,110 @@
 //! });
 //! # fn main() {}
 //! ```
+//!
+//! # Layout families
+//! Protocols often have many message types that all start with the same header. Instead of nesting the header into each message,
+//! the [binary_layout_family!] macro writes the header fields once and splices them in front of the fields of each message layout,
+//! so the header fields are accessed directly on the message `View`. It also defines a layout for the header on its own,
+//! which can read the header of a storage before you know which message it holds.
+//!
+//! ```
+//! use binary_layout::{prelude::*, binary_layout_family};
+//!
+//! binary_layout_family!(header, BigEndian, {
+//!   message_type: u16,
+//!   len: u16,
+//! }, {
+//!   ping: {
+//!     token: u64,
+//!   },
+//!   data: {
+//!     payload: [u8],
+//!   },
+//! });
+//! # fn main() {}
+//! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
+// The continuation lines of the `#[repr(packed)]` bullets in the crate docs are indented to line up in the README
+#![allow(clippy::doc_overindented_list_items)]
 
+mod byte_reader;
+mod checksum;
+mod cursor;
 mod endianness;
+mod error;
 mod fields;
+mod macro_assert_layout_as_roundtrip;
+mod macro_assert_layout_matches;
 mod macro_binary_layout;
+mod macro_binary_layout_family;
+mod macro_layout_as_enum;
+mod records;
 mod utils;
 
 pub mod example;
 
-pub use endianness::{BigEndian, Endianness, LittleEndian, NativeEndian};
+#[cfg(feature = "derive")]
+pub use binary_layout_derive::BinaryLayout;
+pub use byte_reader::{ByteReader, ReadFromError};
+pub use checksum::{
+    ChecksumAlgorithm, ChecksumMismatchError, ChecksummedView, Crc32, LayoutChecksum,
+};
+pub use cursor::MessageCursor;
+pub use endianness::{read_scalar, BigEndian, EndianKind, Endianness, LittleEndian, NativeEndian};
+pub use error::LayoutError;
+#[cfg(feature = "std")]
+pub use fields::cow::FieldCowAccess;
+#[cfg(feature = "std")]
+pub use fields::diff::FieldDiff;
+#[cfg(feature = "std")]
+pub use fields::fill_from::FieldFillFrom;
+#[cfg(feature = "num-rational")]
+pub use fields::fixed_ratio::FixedRatio;
+#[cfg(feature = "std")]
+pub use fields::parse::ParseFieldsError;
+#[cfg(feature = "std")]
+pub use fields::system_time::{FileTime, NtpTimestamp, TimestampOutOfRangeError};
+#[cfg(feature = "chrono")]
+pub use fields::timestamp::{InvalidTimestampError, UnixMillis, UnixSeconds};
+#[cfg(feature = "std")]
+pub use fields::to_vec::FieldToVec;
+#[cfg(feature = "std")]
+pub use fields::translate::{translate, TranslateSource, TranslateTarget};
 pub use fields::{
+    bits::{BitOrder, Bits, Lsb0, Msb0},
+    c_strings::{CStrings, CStrs, FieldCStrings},
+    checked::FieldCheckedAccess,
+    composite::{CompositeField, LayoutAsComposite},
+    constant_time::FieldConstantTimeEq,
+    copy_field::{copy_field, copy_field_saturating, SaturatingInteger},
+    flags::FieldFlags,
+    hash_values::FieldHashValues,
+    int_encoding::{BitReversed, ExcessK, IntegerOutOfRangeError, SignMagnitude},
+    layout_as_array::LayoutAsArray,
+    magic::{BadMagicError, FieldMagic},
+    min_max::FieldMinMax,
+    nested::NestedField,
+    numeric::NumericField,
+    packed_array::PackedArray,
+    pascal_string::{PascalString, PascalStringLength},
     primitive::{
         FieldCopyAccess, FieldReadExt, FieldSliceAccess, FieldView, FieldWriteExt,
         NonZeroIsZeroError, PrimitiveField,
     },
-    wrapped::{LayoutAs, WrappedField, WrappedFieldError},
+    range::{CheckedRange, InvalidRangeError},
+    read_with_raw::FieldReadWithRaw,
+    scaled::{Scaled, ScaledDegrees},
+    utf8::Utf8,
+    wrapped::{LayoutAs, LayoutAsSaturating, WrappedField, WrappedFieldError},
+    write_from_iter::{ArrayFullError, FieldWriteFromIter},
     Field,
 };
+pub use macro_layout_as_enum::InvalidDiscriminantError;
+pub use records::Records;
 pub use utils::{data::Data, infallible::InfallibleResultExt};
 
 /// Import this to get everything into scope that you need for defining and using layouts.
@@ -231,8 +436,10 @@ pub use utils::{data::Data, infallible::InfallibleResultExt};
 /// ```
 pub mod prelude {
     pub use super::{
-        BigEndian, Field, FieldCopyAccess, FieldReadExt, FieldSliceAccess, FieldWriteExt,
-        InfallibleResultExt, LittleEndian, NativeEndian, NonZeroIsZeroError,
+        BigEndian, CompositeField, Field, FieldCheckedAccess, FieldCopyAccess, FieldReadExt,
+        FieldSliceAccess, FieldWriteExt, InfallibleResultExt, LayoutError, LittleEndian,
+        NativeEndian, NonZeroIsZeroError, PackedArray, PascalString, PrimitiveField, Utf8,
+        WrappedField,
     };
     pub use crate::binary_layout;
     #[allow(deprecated)]
@@ -242,11 +449,52 @@ pub mod prelude {
 /// Internal things that need to be exported so our macros can use them. Don't use directly!
 #[doc(hidden)]
 pub mod internal {
+    pub use crate::__binary_layout_if_arbitrary as if_arbitrary;
+    pub use crate::__binary_layout_if_std as if_std;
+    #[cfg(feature = "std")]
+    pub use crate::byte_reader::read_framed_record;
+    pub use crate::byte_reader::read_record;
+    pub use crate::error::check_storage_len;
+    #[cfg(feature = "arbitrary")]
+    pub use crate::fields::arbitrary_fill::{
+        arbitrary_storage, ArbitraryCopyField, ArbitraryOtherField,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::fields::debug::{DebugCopyField, DebugOtherField};
+    #[cfg(feature = "std")]
+    pub use crate::fields::diff::{DiffCopyField, DiffOtherField, DiffSliceField};
+    pub use crate::fields::display::DisplayField;
+    #[cfg(feature = "std")]
+    pub use crate::fields::into_fields::{FieldValue, NestedFields};
+    pub use crate::fields::nested::FieldPath;
+    #[cfg(feature = "std")]
+    pub use crate::fields::parse::{parse_fields, ParseCopyField, ParseOtherField};
+    pub use crate::fields::probe::FieldProbe;
+    #[cfg(feature = "std")]
+    pub use crate::fields::translate::{
+        TranslateCopyField, TranslateOtherField, TranslateSliceField,
+    };
+    pub use crate::fields::validate::{ValidateCheckedField, ValidateOtherField};
     pub use crate::fields::{
-        primitive::{BorrowingNestedView, NestedViewInfo, OwningNestedView},
-        StorageIntoFieldView, StorageToFieldView,
+        primitive::{
+            BorrowingNestedView, ComputedLength, ComputedSlice, NestedViewInfo, OwningNestedView,
+        },
+        FieldByteLen, StorageIntoFieldView, StorageToFieldView,
+    };
+    pub use crate::macro_assert_layout_as_roundtrip::{
+        assert_layout_as_roundtrip, RoundtripDomain,
+    };
+    pub use crate::macro_binary_layout::{
+        field_index, field_type_must_implement_LayoutAs_for_the_type_after_as, first_gap,
+        option_usize_add, pad_size, pin_offset, static_layout_size, union_end, unwrap_field_size,
+        AssertDynamicTail, AssertPow2Size, DenyGaps, RecordStride,
     };
-    pub use crate::macro_binary_layout::{option_usize_add, unwrap_field_size};
+    #[cfg(feature = "std")]
+    pub use crate::macro_binary_layout::{hex_string, write_chunked};
+    #[cfg(feature = "arbitrary")]
+    pub use arbitrary;
     pub use doc_comment::doc_comment;
     pub use paste::paste;
+    #[cfg(feature = "std")]
+    pub use std::{boxed::Box, io, string::String, vec::Vec};
 }
@@ -0,0 +1,221 @@
+use crate::LayoutAs;
+
+/// This macro generates a test checking that a [LayoutAs](crate::LayoutAs) implementation round-trips, i.e. that each stored
+/// value that can be read as the custom type is written back as the same stored value. This catches broken mappings in custom
+/// field types, e.g. two stored values that are read as the same value or a typo in one of the match arms of a conversion.
+///
+/// The macro is called at module level with the custom type and the underlying type of the [LayoutAs] implementation, e.g.
+/// `assert_layout_as_roundtrip!(MyType, u8)`, and generates a `#[test]` function named `${my_type}_layout_as_${u8}_roundtrip`.
+/// The test reads every value of 8 and 16 bit integer types. For wider integer types, it reads a sample of values, namely all
+/// values that fit into 16 bits, the values close to the minimum and maximum of the type, and the powers of two and their neighbours.
+/// Stored values that fail to read, e.g. invalid discriminants of an enum, are skipped, but values that were read successfully
+/// have to be written successfully and give back the same stored value. Encodings with several representations of the same value,
+/// e.g. negative and positive zero of [SignMagnitude](crate::SignMagnitude), don't round-trip and fail the test.
+///
+/// Generic types can be checked through a type alias, e.g. `type Biased = ExcessK<i16, 127>;` and `assert_layout_as_roundtrip!(Biased, u8)`.
+///
+/// # Example
+/// ```
+/// use binary_layout::{assert_layout_as_roundtrip, LayoutAs};
+/// use core::convert::Infallible;
+///
+/// #[derive(Debug)]
+/// struct Celsius(i16);
+///
+/// impl LayoutAs<u8> for Celsius {
+///   type ReadError = Infallible;
+///   type WriteError = Infallible;
+///
+///   fn try_read(v: u8) -> Result<Celsius, Infallible> {
+///     Ok(Celsius(i16::from(v) - 40))
+///   }
+///
+///   fn try_write(v: Celsius) -> Result<u8, Infallible> {
+///     Ok((v.0 + 40) as u8)
+///   }
+/// }
+///
+/// assert_layout_as_roundtrip!(Celsius, u8);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_layout_as_roundtrip {
+    ($type: ident, $underlying: ident) => {
+        $crate::internal::paste! {
+            #[test]
+            #[allow(non_snake_case)]
+            fn [<$type:snake _layout_as_ $underlying _roundtrip>]() {
+                $crate::internal::assert_layout_as_roundtrip::<$type, $underlying>();
+            }
+        }
+    };
+}
+
+/// Internal trait, don't use!
+/// Implemented for the underlying types that [assert_layout_as_roundtrip!](crate::assert_layout_as_roundtrip!) can check.
+pub trait RoundtripDomain: Sized + Copy + PartialEq + core::fmt::Debug {
+    /// Call `f` with each value of the type that the round trip is checked for
+    fn for_each_value(f: impl FnMut(Self));
+}
+
+macro_rules! impl_roundtrip_domain_exhaustive {
+    ($($type: ty),*) => {
+        $(
+            impl RoundtripDomain for $type {
+                fn for_each_value(f: impl FnMut(Self)) {
+                    (<$type>::MIN..=<$type>::MAX).for_each(f);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_roundtrip_domain_sampled {
+    ($($type: ty: $start_16_bit: expr),*) => {
+        $(
+            impl RoundtripDomain for $type {
+                fn for_each_value(mut f: impl FnMut(Self)) {
+                    (<$type>::MIN..=<$type>::MIN + 255).for_each(&mut f);
+                    (<$type>::MAX - 255..=<$type>::MAX).for_each(&mut f);
+                    // All values that fit into 16 bits, i.e. 0..=u16::MAX for unsigned types and i16::MIN..=i16::MAX for signed types
+                    ($start_16_bit..=$start_16_bit + 65535).for_each(&mut f);
+                    for bit in 0..<$type>::BITS {
+                        let power = (1 as $type).wrapping_shl(bit);
+                        for value in [power.wrapping_sub(1), power, power.wrapping_add(1)] {
+                            f(value);
+                            f(value.wrapping_neg());
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_roundtrip_domain_exhaustive!(u8, i8, u16, i16);
+impl_roundtrip_domain_sampled!(u32: 0, i32: -32768, u64: 0, i64: -32768);
+
+/// Internal function, don't use!
+/// Called by the test generated by [assert_layout_as_roundtrip!](crate::assert_layout_as_roundtrip!)
+pub fn assert_layout_as_roundtrip<T: LayoutAs<U>, U: RoundtripDomain>() {
+    U::for_each_value(|stored| {
+        if let Ok(value) = T::try_read(stored) {
+            match T::try_write(value) {
+                Ok(written) => assert_eq!(
+                    stored, written,
+                    "Reading the stored value {:?} and writing it back gave a different stored value",
+                    stored
+                ),
+                Err(_) => panic!(
+                    "Reading the stored value {:?} succeeded, but writing it back failed",
+                    stored
+                ),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout_as_enum, BitReversed, ExcessK, SignMagnitude};
+    use core::convert::Infallible;
+
+    layout_as_enum! {
+        #[layout_as(u8)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Mode {
+            Off = 0,
+            On = 1,
+            Auto = 7,
+        }
+    }
+
+    type Reversed = BitReversed<u32>;
+    type Biased = ExcessK<i64, 1000>;
+    type Narrow = ExcessK<i8, 200>;
+
+    assert_layout_as_roundtrip!(Mode, u8);
+    assert_layout_as_roundtrip!(Reversed, u32);
+    assert_layout_as_roundtrip!(Biased, u64);
+    assert_layout_as_roundtrip!(Narrow, u16);
+
+    #[test]
+    #[should_panic(
+        expected = "Reading the stored value 32768 and writing it back gave a different stored value"
+    )]
+    fn negative_zero() {
+        assert_layout_as_roundtrip::<SignMagnitude<i16>, u16>();
+    }
+
+    // Reads stored values that only differ in the lowest bit as the same value
+    #[derive(Debug)]
+    struct Lossy(u32);
+
+    impl LayoutAs<u32> for Lossy {
+        type ReadError = Infallible;
+        type WriteError = Infallible;
+
+        fn try_read(v: u32) -> Result<Lossy, Infallible> {
+            Ok(Lossy(v >> 1))
+        }
+
+        fn try_write(v: Lossy) -> Result<u32, Infallible> {
+            Ok(v.0 << 1)
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Reading the stored value 1 and writing it back gave a different stored value"
+    )]
+    fn broken_mapping() {
+        assert_layout_as_roundtrip::<Lossy, u32>();
+    }
+
+    // Writes the maximum value back as an error
+    #[derive(Debug)]
+    struct NoMax(i8);
+
+    impl LayoutAs<i8> for NoMax {
+        type ReadError = Infallible;
+        type WriteError = ();
+
+        fn try_read(v: i8) -> Result<NoMax, Infallible> {
+            Ok(NoMax(v))
+        }
+
+        fn try_write(v: NoMax) -> Result<i8, ()> {
+            if v.0 == i8::MAX {
+                Err(())
+            } else {
+                Ok(v.0)
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Reading the stored value 127 succeeded, but writing it back failed")]
+    fn failing_write() {
+        assert_layout_as_roundtrip::<NoMax, i8>();
+    }
+
+    #[test]
+    fn sampled_values() {
+        let mut values = [0u32; 5];
+        let mut count = 0;
+        u32::for_each_value(|value| {
+            for (expected, seen) in [0, 65535, u32::MAX, 1 << 31, (1 << 31) + 1]
+                .iter()
+                .zip(values.iter_mut())
+            {
+                if value == *expected {
+                    *seen += 1;
+                }
+            }
+            count += 1;
+        });
+        assert!(values.iter().all(|seen| *seen > 0));
+        assert!(count < 70_000);
+    }
+}
@@ -0,0 +1,147 @@
+/// This macro checks at compile time that the fields of a [binary_layout!](crate::binary_layout!) layout
+/// are at the same offsets and have the same sizes as the fields of a `#[repr(C)]` struct.
+///
+/// This is useful if a layout describes the same data as a struct that is passed through FFI,
+/// and catches cases where one of them is changed but the other one isn't.
+/// The fields to check are listed after the struct and need to have the same names in the layout and in the struct.
+/// For each field, the macro emits a const assertion comparing [Field::OFFSET](crate::Field::OFFSET) with
+/// [core::mem::offset_of!] and [Field::SIZE](crate::Field::SIZE) with the size of the struct field.
+/// Open ended fields like `[u8]` don't have a size and never match.
+///
+/// Note that the macro only compares offsets and sizes, it doesn't check that the struct uses the endianness of the layout.
+///
+/// # Minimum Rust version
+/// Unlike the rest of this crate, which supports Rust 1.59, this macro needs Rust 1.77 or newer because it expands to
+/// [core::mem::offset_of!] in the crate calling it. Using it with an older compiler fails to compile.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, assert_layout_matches};
+///
+/// binary_layout!(header, NativeEndian, {
+///   version: u16,
+///   padding: [u8; 2],
+///   len: u32,
+/// });
+///
+/// #[repr(C)]
+/// struct CHeader {
+///   version: u16,
+///   len: u32,
+/// }
+///
+/// assert_layout_matches!(header, CHeader, {
+///   version,
+///   len,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// A mismatch is a compile time error:
+/// ```compile_fail
+/// use binary_layout::{prelude::*, assert_layout_matches};
+///
+/// binary_layout!(header, NativeEndian, {
+///   version: u16,
+///   len: u32,
+/// });
+///
+/// #[repr(C)]
+/// struct CHeader {
+///   version: u16,
+///   len: u32,
+/// }
+///
+/// // fails because `len` is at offset 4 in the struct but at offset 2 in the layout
+/// assert_layout_matches!(header, CHeader, {
+///   version,
+///   len,
+/// });
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_layout_matches {
+    ($($layout: ident)::+, $struct: ty, {
+        $($field: ident),* $(,)?
+    }) => {
+        const _: () = {
+            use $($layout)::+ as layout;
+
+            const fn size_of_field<T, F>(_: fn(&T) -> &F) -> usize {
+                ::core::mem::size_of::<F>()
+            }
+
+            $(
+                assert!(
+                    <layout::$field as $crate::Field>::OFFSET == ::core::mem::offset_of!($struct, $field),
+                    concat!("The offset of `", stringify!($field), "` in the layout doesn't match the offset in `", stringify!($struct), "`"),
+                );
+                assert!(
+                    match <layout::$field as $crate::Field>::SIZE {
+                        ::core::option::Option::Some(size) => size == size_of_field(|s: &$struct| &s.$field),
+                        ::core::option::Option::None => false,
+                    },
+                    concat!("The size of `", stringify!($field), "` in the layout doesn't match the size in `", stringify!($struct), "`"),
+                );
+            )*
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    mod layouts {
+        use crate::prelude::*;
+
+        binary_layout!(nested, LittleEndian, {
+            a: u8,
+            b: u8,
+        });
+
+        binary_layout!(packet, LittleEndian, {
+            kind: u8,
+            padding1: [u8; 3],
+            value: f32,
+            inner: nested::NestedView,
+            padding2: [u8; 6],
+            id: u64,
+            tag: [u8; 4],
+        });
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct Nested {
+        a: u8,
+        b: u8,
+    }
+
+    #[repr(C)]
+    #[allow(dead_code)]
+    struct Packet {
+        kind: u8,
+        value: f32,
+        inner: Nested,
+        id: u64,
+        tag: [u8; 4],
+    }
+
+    assert_layout_matches!(layouts::packet, Packet, {
+        kind,
+        value,
+        inner,
+        id,
+        tag,
+    });
+    assert_layout_matches!(layouts::nested, Nested, { a, b });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(16, core::mem::offset_of!(Packet, id));
+        assert_eq!(16, layouts::packet::id::OFFSET);
+        assert_eq!(Some(2), layouts::packet::inner::SIZE);
+        assert_eq!(2, core::mem::size_of::<Nested>());
+    }
+}
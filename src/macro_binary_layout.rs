@@ -16,9 +16,583 @@
 /// });
 /// ```
 ///
+/// `<<FieldType>>` can be any of the [supported field types](crate#supported-field-types). For a
+/// [byte array with computed length](crate#byte-arrays-with-computed-length-u8-expr), use `[u8; expr(<<closure>>)]`,
+/// where the closure takes a `&View<&[u8]>` and returns the length of the field.
+///
 /// ## Field names
 /// Field names can be any valid Rust identifiers, but it is recommended to avoid names that contain `storage`, `into_` or `_mut`.
 /// This is because the [binary_layout!](crate::binary_layout!) macro creates a [View class with several accessors](#struct-view) for each field that contain those identifier parts.
+/// For the same reason, fields can't be named `new`. All other functions working on a whole layout, e.g. `try_new` or `byte_len`, are
+/// generated as functions of the module instead of methods of the [View](#struct-view), so they don't collide with the accessors of a field.
+///
+/// Reserved words like `type` or `match` can be used as field names by declaring them as raw identifiers, e.g. `r#type: u8`.
+/// The accessors on the [View](#struct-view) are then `r#type()`, `type_mut()` and `into_type()`.
+/// If you don't want raw identifiers in your API, you can [rename the field](#renaming-fields).
+///
+/// ## Field attributes
+/// Doc comments and other outer attributes on a field are forwarded to the generated field type and to
+/// its accessors on the [View](#struct-view). This means you can document the semantics of a field right where it is defined.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(icmp_packet, BigEndian, {
+///   /// The type of the ICMP message, e.g. 8 for an echo request
+///   packet_type: u8,
+///   /// Subtype of the message, the meaning depends on `packet_type`
+///   code: u8,
+///   checksum: u16,
+/// });
+/// ```
+///
+/// ## Field visibility
+/// By default, the generated field types and their accessors on the [View](#struct-view) are `pub`. You can restrict this
+/// by putting a visibility like `pub(crate)` or `pub(super)` in front of the field. Note that the visibility is relative to the
+/// module generated for the layout, i.e. `pub(super)` makes a field accessible only from the module containing the
+/// [binary_layout!](crate::binary_layout!) invocation.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   pub(super) internal_flags: u8,
+///   pub(crate) payload_len: u16,
+///   payload: [u8],
+/// });
+/// ```
+///
+/// ## Renaming fields
+/// The `#[name(...)]` attribute sets the name of the generated field type and its accessors on the [View](#struct-view)
+/// independently of the name the field is declared with. This is useful if the name used in a specification doesn't fit the
+/// naming conventions of your API. The declared name is still used in the generated documentation of the layout.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   #[name(message_type)]
+///   msgType: u8,
+///   payload: [u8],
+/// });
+///
+/// fn func(storage: &[u8]) -> u8 {
+///   my_layout::View::new(storage).message_type().read()
+/// }
+/// ```
+///
+/// ## Checksum fields
+/// The `#[checksum(<<Algorithm>>, from = <<field>>, to = <<field>>)]` attribute marks a field as storing a checksum computed
+/// with the given [ChecksumAlgorithm](crate::ChecksumAlgorithm) over all bytes from the start of the `from` field to the end of the `to` field.
+/// The offsets are taken from the layout, so there's no need to compute them by hand. For a checksum field named `crc`, the
+/// generated module contains a `crc_checksum` type implementing [LayoutChecksum](crate::LayoutChecksum), which offers
+/// `compute`, `verify` and `seal` (storing the recomputed checksum) and can be used with [ChecksummedView](crate::ChecksummedView).
+/// The `to` field can have a dynamic size, e.g. an open ended byte array. Naming fields that don't exist or a `from` field
+/// after the `to` field is a compile time error.
+///
+/// ```
+/// use binary_layout::{prelude::*, ChecksumAlgorithm, Crc32, LayoutChecksum};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   #[checksum(Crc32, from = magic, to = payload)]
+///   crc: u32,
+///   magic: u32,
+///   payload_len: u8,
+///   payload: [u8; expr(|v| v.payload_len().read() as usize)],
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0, 0, 0, 0, 0xCA, 0xFE, 0xBA, 0xBE, 2, 1, 2];
+/// my_layout::crc_checksum::seal(&mut storage);
+/// assert!(my_layout::crc_checksum::verify(&storage).is_ok());
+/// assert_eq!(Crc32::checksum(&storage[4..]), my_layout::crc::read(&storage));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::{prelude::*, Crc32};
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   magic: u32,
+///   payload: [u8; 16],
+///   // fails to compile because `payload` comes after `magic`
+///   #[checksum(Crc32, from = payload, to = magic)]
+///   crc: u32,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ## Default values
+/// The `#[default(<<value>>)]` attribute declares the value a field is reset to by the generated `reset_to_defaults(storage)` function.
+/// It sets each field with a default to its default value and all other bytes used by the layout to zero,
+/// which is useful to reuse a buffer across messages. Defaults can be given for fields that can be written with
+/// [FieldWriteExt::write](crate::FieldWriteExt::write), e.g. integer fields.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, BigEndian, {
+///   #[default(0xCAFE)]
+///   magic: u16,
+///   #[default(1)]
+///   version: u8,
+///   payload: [u8; 2],
+/// });
+///
+/// let mut storage = [0xFF; 6];
+/// my_layout::reset_to_defaults(&mut storage);
+/// assert_eq!(0xCAFE, my_layout::View::new(&storage).magic().read());
+/// assert_eq!([0xCA, 0xFE, 1, 0, 0, 0xFF], storage);
+/// ```
+///
+/// ## Magic numbers
+/// File formats and protocols often start with a magic number that identifies them. The `#[magic(<<value>>)]` attribute declares it
+/// for a field, which can then be checked with the generated `check_magic(storage)` function. It returns
+/// a [LayoutError::BadMagic](crate::LayoutError::BadMagic) with the offset of the first field that doesn't hold its magic number, and
+/// `try_new` and the other functions creating views that validate the storage reject such messages as well. The magic number is also the
+/// [default value](#default-values) of the field, so `reset_to_defaults` writes it. Like defaults, magic numbers can be given for fields
+/// that can be read with [FieldReadExt::read](crate::FieldReadExt::read) and written with [FieldWriteExt::write](crate::FieldWriteExt::write).
+/// To check the bytes of a byte array field, e.g. a `[u8; 4]` chunk type, use [FieldMagic::check_bytes](crate::FieldMagic::check_bytes).
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(archive_header, LittleEndian, {
+///   #[magic(0x0403_4B50)]
+///   signature: u32,
+///   version: u16,
+/// });
+///
+/// let mut storage = [0; 6];
+/// assert_eq!(Err(LayoutError::BadMagic { offset: 0 }), archive_header::check_magic(&storage));
+/// assert!(archive_header::try_new(&storage[..]).is_err());
+///
+/// archive_header::reset_to_defaults(&mut storage);
+/// assert_eq!([0x50, 0x4B, 0x03, 0x04, 0, 0], storage);
+/// assert!(archive_header::check_magic(&storage).is_ok());
+/// ```
+///
+/// ## Versioned fields
+/// Protocols that add fields in newer versions can declare the version a field was added in with a `#[since(<<version>>)]` attribute.
+/// The generated `new_version(storage, version)` function then creates a `VersionedView` over a message of the given version, e.g. one written by an older
+/// peer. Fields added after `version` are treated as absent: their accessors on the `VersionedView` return `None`, and the storage only needs
+/// to be long enough for the fields that exist in `version`. All other fields are accessed through `Some` accessors like on a `View`.
+/// `version_byte_len(storage, version)` returns the number of bytes used by the fields of a version, and `View::from(versioned_view)` turns
+/// a `VersionedView` back into a `View` with access to all fields.
+/// Fields with a `#[since(...)]` attribute must be declared in the order of their versions, so fields added in newer versions are trailing fields,
+/// and the layout needs a `#[derive(VersionedView)]` attribute, see [optional items](#optional-items).
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(VersionedView)] message, LittleEndian, {
+///   id: u16,
+///   #[since(2)]
+///   flags: u8,
+///   #[since(3)]
+///   timestamp: u32,
+/// });
+///
+/// let storage = [1, 0, 0xFF];
+/// let view = message::new_version(&storage[..], 2).unwrap();
+/// assert_eq!(Some(1), view.id().map(|id| id.read()));
+/// assert_eq!(Some(0xFF), view.flags().map(|flags| flags.read()));
+/// assert!(view.timestamp().is_none());
+/// assert_eq!(3, message::version_byte_len(&storage, 2));
+///
+/// assert!(message::new_version(&storage[..], 3).is_err());
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because `flags` is declared after a field of a newer version
+/// binary_layout!(#[derive(VersionedView)] message, LittleEndian, {
+///   id: u16,
+///   #[since(3)]
+///   timestamp: u32,
+///   #[since(2)]
+///   flags: u8,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because the layout doesn't derive `VersionedView`
+/// binary_layout!(message, LittleEndian, {
+///   id: u16,
+///   #[since(2)]
+///   flags: u8,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ## Conditional fields
+/// Fields can be enabled or disabled with `#[cfg(...)]` attributes. A disabled field doesn't take up any space
+/// in the layout, i.e. all fields following it move up and have a smaller [OFFSET](crate::Field::OFFSET).
+/// Keep in mind that this changes the binary format, so the same data can't be read with both configurations.
+/// This is easiest to reason about for trailing fields, which don't change the offsets of any other fields.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(my_layout, LittleEndian, {
+///   version: u8,
+///   #[cfg(feature = "extended_header")]
+///   flags: u32,
+///   payload_len: u16,
+/// });
+///
+/// # #[cfg(not(feature = "extended_header"))]
+/// assert_eq!(1, my_layout::payload_len::OFFSET);
+/// ```
+///
+/// ## Pinning fields to an offset
+/// Formats documented as "field X at byte 0x20" can pin a field to its offset with `field @0x20: T`, instead of declaring
+/// placeholder fields for the bytes in between. The bytes before the pinned field that aren't covered by other fields are skipped,
+/// and the following fields continue after the end of the pinned field. Pinning a field to an offset before the end of the previous field
+/// is a compile time error, as is pinning a field after a field without a static size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(superblock, LittleEndian, {
+///   magic: u32,
+///   block_size @0x10: u32,
+///   num_blocks: u64,
+///   label @0x40: [u8; 16],
+/// });
+///
+/// assert_eq!(0x10, superblock::block_size::OFFSET);
+/// assert_eq!(0x14, superblock::num_blocks::OFFSET);
+/// assert_eq!(0x40, superblock::label::OFFSET);
+/// assert_eq!(Some(0x50), superblock::SIZE);
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because `flags` would overlap with `magic`
+/// binary_layout!(superblock, LittleEndian, {
+///   magic: u32,
+///   flags @0x02: u16,
+/// });
+/// # fn main() {
+/// # let _ = superblock::flags::OFFSET;
+/// # }
+/// ```
+///
+/// The generated module offers a `GAPS` constant listing the byte ranges before pinned fields that don't belong to any field, as `(start, end)` pairs.
+/// To make sure a layout covers a whole documented structure, put a `#[deny_gaps]` attribute in front of the layout name.
+/// It fails to compile if there are gaps, and the compiler error shows the first gap as the parameters of `DenyGaps<START, END>`.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(superblock, LittleEndian, {
+///   magic: u32,
+///   block_size @0x10: u32,
+///   num_blocks: u64,
+///   label @0x40: [u8; 16],
+/// });
+///
+/// assert_eq!(&[(0x04, 0x10), (0x1C, 0x40)], superblock::GAPS);
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because of the gap from 0x04 to 0x10
+/// binary_layout!(#[deny_gaps] superblock, LittleEndian, {
+///   magic: u32,
+///   block_size @0x10: u32,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ## Unions
+/// Formats that store different interpretations of the same bytes, like a C union embedded in a struct, can declare them
+/// in a `union { ... }` block. All fields in the block start at the same offset, and the fields after the block continue
+/// after the end of its largest field. Each field in the block gets its own accessors, and fields in a union block must
+/// have a static size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(register, LittleEndian, {
+///   id: u8,
+///   union {
+///     value: u32,
+///     bytes: [u8; 4],
+///     low: u16,
+///   },
+///   checksum: u8,
+/// });
+///
+/// assert_eq!(1, register::value::OFFSET);
+/// assert_eq!(1, register::low::OFFSET);
+/// assert_eq!(5, register::checksum::OFFSET);
+/// assert_eq!(Some(6), register::SIZE);
+///
+/// let mut storage = [0; 6];
+/// register::value::write(&mut storage, 0x01020304);
+/// assert_eq!(&[4, 3, 2, 1], register::bytes::data(&storage));
+/// assert_eq!(0x0304, register::low::read(&storage));
+/// ```
+///
+/// ## Flattening nested layouts
+/// A `#[flatten(<<module>>::{<<field>>, ...})]` attribute on a field holding a nested layout generates a flattened alias for each listed field
+/// of the nested layout, named `<<outer field>>_<<inner field>>`. The aliases are [NestedField](crate::NestedField)s at the absolute offset
+/// of the inner field, so they can read and write it without constructing views for the nested layouts. `<<module>>` is the module of the
+/// nested layout and the inner fields are given with the names they're declared with. Flattened aliases of the nested layout can be listed
+/// as well, so leaf fields of layouts nested several levels deep can be flattened level by level. An alias fails to compile if the field isn't
+/// a field of the nested layout, and it also fails if its name collides with another field.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(address, BigEndian, {
+///   ip: [u8; 4],
+///   port: u16,
+/// });
+/// binary_layout!(route, BigEndian, {
+///   #[flatten(address::{port})]
+///   gateway: address::NestedView,
+/// });
+/// binary_layout!(config, LittleEndian, {
+///   version: u8,
+///   #[flatten(route::{gateway_port})]
+///   default_route: route::NestedView,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 7];
+/// config::default_route_gateway_port::write(&mut storage, 8080);
+/// assert_eq!(5, config::default_route_gateway_port::OFFSET);
+/// assert_eq!(8080, config::View::new(&storage).default_route().gateway().port().read());
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(address, BigEndian, {
+///   port: u16,
+/// });
+/// binary_layout!(other, BigEndian, {
+///   port: u16,
+/// });
+/// // fails because `other` isn't the layout nested in `gateway`
+/// binary_layout!(route, BigEndian, {
+///   #[flatten(other::{port})]
+///   gateway: address::NestedView,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ## Bit fields with RFC bit numbering
+/// Headers specified with ASCII diagrams, like in RFCs, number the bits of each row from the most significant bit of its
+/// first byte, i.e. bit 0 is the top bit of byte 0 and bit 8 the top bit of byte 1. A field declared as `bits START..END`
+/// holds the bits `START` to `END - 1` in this numbering, counted from the offset of the field, so the declaration can be
+/// copied from the diagram. These fields read and write the bits as a big endian [u64] like a [Bits](crate::Bits) field with the
+/// bit order [Msb0](crate::Msb0), independently of the endianness of the layout. A field `bits START..END` takes up the bytes
+/// up to and including the one holding bit `END - 1`, so fields sharing bytes are declared in a `union { ... }` block,
+/// typically one per row of the diagram. Fields can span at most 64 bits and 8 bytes.
+///
+/// This is the IPv4 header from RFC 791:
+/// ```text
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |Version|  IHL  |Type of Service|          Total Length         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |         Identification        |Flags|      Fragment Offset    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  Time to Live |    Protocol   |         Header Checksum       |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                       Source Address                          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                    Destination Address                        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// ```
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(ipv4_header, BigEndian, {
+///   union {
+///     version: bits 0..4,
+///     ihl: bits 4..8,
+///     type_of_service: bits 8..16,
+///     total_length: bits 16..32,
+///   },
+///   union {
+///     identification: bits 0..16,
+///     flags: bits 16..19,
+///     fragment_offset: bits 19..32,
+///   },
+///   union {
+///     time_to_live: bits 0..8,
+///     protocol: bits 8..16,
+///     header_checksum: bits 16..32,
+///   },
+///   source_address: [u8; 4],
+///   destination_address: [u8; 4],
+/// });
+///
+/// # fn main() {
+/// let storage = [
+///     0x45, 0x00, 0x00, 0x54, 0x1c, 0x46, 0x40, 0x00, 0x40, 0x01,
+///     0xb1, 0xe6, 192, 168, 0, 104, 192, 168, 0, 1,
+/// ];
+/// let header = ipv4_header::View::new(&storage);
+/// assert_eq!(4, header.version().read());
+/// assert_eq!(5, header.ihl().read());
+/// assert_eq!(84, header.total_length().read());
+/// assert_eq!(0b010, header.flags().read());
+/// assert_eq!(0, header.fragment_offset().read());
+/// assert_eq!(64, header.time_to_live().read());
+/// assert_eq!(0xb1e6, header.header_checksum().read());
+/// assert_eq!(&[192, 168, 0, 1], header.destination_address());
+///
+/// let mut storage = storage;
+/// ipv4_header::fragment_offset::try_write(&mut storage, 0x1234).unwrap();
+/// assert_eq!([0x52, 0x34], storage[6..8]);
+/// # }
+/// ```
+///
+/// ## Padding to a fixed record size
+/// Formats storing records in fixed size slots, e.g. 64 byte database pages, can put a `#[pad_to(N)]` attribute
+/// in front of the layout name. This rounds `SIZE` up to the next multiple of `N`, so views and [iter_records](#fn-iter_records)
+/// treat the padding as part of the record. The generated module also offers a `PADDING` constant with the range of the padding bytes
+/// and a `zero_padding(storage)` function that sets them to zero. Padding can only be added to layouts with a static size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[pad_to(64)] page_header, LittleEndian, {
+///   page_id: u32,
+///   num_entries: u16,
+/// });
+///
+/// assert_eq!(Some(64), page_header::SIZE);
+/// assert_eq!(6..64, page_header::PADDING);
+///
+/// let mut storage = [0xFF; 64];
+/// page_header::zero_padding(&mut storage);
+/// assert_eq!([0; 58], storage[6..]);
+/// ```
+///
+/// Records that tile into aligned buffers, e.g. the slots of a ring buffer, can additionally put an `#[assert_pow2_size]` attribute
+/// in front of the layout name. This fails to compile if `SIZE` isn't a power of two, and the compiler error shows the actual size
+/// as the parameter of `AssertPow2Size<SIZE>`. Like padding, this can only be used for layouts with a static size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[assert_pow2_size] #[pad_to(16)] slot, LittleEndian, {
+///   sequence: u64,
+///   value: u32,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because the size is 12
+/// binary_layout!(#[assert_pow2_size] slot, LittleEndian, {
+///   sequence: u64,
+///   value: u32,
+/// });
+/// # fn main() {}
+/// ```
+///
+/// Strict parsers that require reserved bytes to be zero can call `check_reserved_zero(storage)`. It checks that all bytes
+/// that don't belong to a field, i.e. the `GAPS` and the padding, are zero, and returns a [LayoutError::ReservedNotZero](crate::LayoutError::ReservedNotZero) with the offset of the first byte that isn't.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[pad_to(8)] record, LittleEndian, {
+///   kind: u8,
+///   value @4: u16,
+/// });
+///
+/// let mut storage = [1, 0, 0, 0, 2, 0, 0, 0];
+/// assert!(record::check_reserved_zero(&storage).is_ok());
+/// storage[7] = 0xFF;
+/// assert_eq!(Err(LayoutError::ReservedNotZero { offset: 7 }), record::check_reserved_zero(&storage));
+/// ```
+///
+/// ## Display format
+/// To get concise log lines for a message type, put a `#[display("...")]` attribute with a format string in front of the layout name.
+/// This implements [Display](core::fmt::Display) for the generated [View](#struct-view), and the format string can reference fields
+/// by the name they're declared with, like the inline arguments of [format!]. Only the referenced fields are read, and they support
+/// format specs like `{len:>4}` or, for integer fields, `{flags:#04x}`. Fields that fail to read are written as their error, e.g.
+/// `<NonZeroIsZeroError(())>`. Referencing a field that can't be read into a value implementing the format trait, e.g. a byte array,
+/// fails to compile.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[display("pkt type={kind} len={length}")] packet, BigEndian, {
+///   kind: u8,
+///   length: u16,
+///   payload: [u8],
+/// });
+///
+/// let storage = [2, 0, 3, 10, 11, 12];
+/// assert_eq!("pkt type=2 len=3", packet::View::new(&storage).to_string());
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because byte arrays don't implement Display
+/// binary_layout!(#[display("payload={payload}")] packet, BigEndian, {
+///   kind: u8,
+///   payload: [u8; 4],
+/// });
+/// # fn main() {}
+/// ```
+///
+/// ## Optional items
+/// Some of the [generated code](#generated-code) is only generated for layouts that ask for it with a `#[derive(...)]` attribute in front of
+/// the layout name, so that layouts that don't need it don't get items that could shadow types of your own in the surrounding module:
+/// - `Fields` generates the [Fields](#struct-fields) struct and the `read_fields(storage)` function. Nested layouts must derive `Fields` as well.
+/// - `ReadView` generates the [ReadView and WriteView](#trait-readview-and-trait-writeview) traits
+/// - `TrackedView` generates the [TrackedView](#struct-trackedview) struct
+/// - `VersionedView` generates the `VersionedView` struct and the `new_version` and `version_byte_len` functions for [versioned fields](#versioned-fields)
+/// - `DebugFields` generates the [debug_fields](#fn-debug_fields) function
+/// - `Diff` generates the [diff](#fn-diff) function
+/// - `FromStr` generates the [from_fields_str](#fn-from_fields_str) function and implements [FromStr](core::str::FromStr) for the `View`
+/// - `Translate` implements the traits [translate](crate::translate) needs for the `View`
+/// - `Arbitrary` implements [Arbitrary](#impl-arbitrary) for the `View`
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(ReadView, TrackedView)] settings, LittleEndian, {
+///   volume: u8,
+///   brightness: u8,
+/// });
+///
+/// let tracked = settings::TrackedView::new([0; 2]);
+/// assert!(!tracked.is_dirty());
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// // fails because `Debug` isn't an optional item of a layout
+/// binary_layout!(#[derive(Debug)] settings, LittleEndian, {
+///   volume: u8,
+/// });
+/// # fn main() {}
+/// ```
 ///
 /// ## Example
 /// ```
@@ -65,88 +639,1904 @@
 /// - Mutable borrowed storage: `&mut [u8]`
 /// - Owning storage: impl `AsRef<u8>` (for example: `Vec<u8>`)
 ///
+/// The storage has to be initialized. Views over uninitialized buffers like `[MaybeUninit<u8>; N]` aren't supported,
+/// because turning them into initialized bytes after writing all fields needs `unsafe` code and this crate doesn't use any.
+/// Initialize the buffer, e.g. with zeroes, before creating a view over it.
+///
 /// The generated `View` struct will offer
 /// - `View::new(storage)` to create a `View`
 /// - `View::into_storage(self)` to destroy a `View` and return the storage held
+/// - `TryFrom<&[u8]>` and `TryFrom<&mut [u8]>` implementations doing the same as `try_new`, for generic code working with [TryFrom](core::convert::TryFrom)
+/// - an [Arbitrary](#impl-arbitrary) implementation for `View<Vec<u8>>` creating random messages, e.g. for fuzzing (requires `#[derive(Arbitrary)]` and the `arbitrary` feature)
 ///
 /// and it will offer the following accessors for each field
 /// - `${field_name}()`: Read access. This returns a [FieldView](crate::FieldView) instance with read access.
 /// - `${field_name}_mut()`: Read access. This returns a [FieldView](crate::FieldView) instance with write access.
 /// - `into_${field_name}`: Extract access. This destroys the `View` and returns a [FieldView](crate::FieldView) instance owning the storage. Mostly useful for slice fields when you want to return an owning slice.
-#[macro_export]
-macro_rules! binary_layout {
-    ($name: ident, $endianness: ident, {$($field_name: ident : $field_type: ty $(as $underlying_type: ty)?),* $(,)?}) => {
-        $crate::internal::doc_comment!{
-            concat!{"
-            This module is autogenerated. It defines a layout using the [binary_layout] crate based on the following definition:
-            ```ignore
-            binary_layout!(", stringify!($name), ", ", stringify!($endianness), ", {", $("
-                ", stringify!($field_name), ": ", stringify!($field_type), $(" as ", stringify!($underlying_type), )? ",", )* "
-            });
-            ```
-            "},
-            #[allow(dead_code)]
-            pub mod $name {
-                #[allow(unused_imports)]
-                use super::*;
-
-                $crate::binary_layout!(@impl_fields $crate::$endianness, Some(0), {$($field_name : $field_type $(as $underlying_type)?),*});
-
-                $crate::internal::doc_comment!{
-                    concat!{"
-                    The [View] struct defines the [FieldView](crate::FieldView) API.
-                    An instance of [View] wraps a storage (either borrowed or owned)
-                    and allows accessors for the layout fields.
-
-                    This view is based on the following layout definition:
-                    ```ignore
-                    binary_layout!(", stringify!($name), ", ", stringify!($endianness), ", {", $("
-                        ", stringify!($field_name), ": ", stringify!($field_type), $(" as ", stringify!($underlying_type), )? ",",)* "
-                    });
-                    ```
-                    "},
-                    pub struct View<S: AsRef<[u8]>> {
-                        storage: S,
-                    }
-                }
-                impl <S: AsRef<[u8]>> View<S> {
-                    /// You can create views over a storage by calling [View::new].
-                    ///
-                    /// `S` is the type of underlying storage. It can be
-                    /// - Immutable borrowed storage: `&[u8]`
-                    /// - Mutable borrowed storage: `&mut [u8]`
-                    /// - Owning storage: impl `AsRef<u8>` (for example: `Vec<u8>`)
-                    #[inline]
-                    pub fn new(storage: S) -> Self {
-                        Self {storage}
-                    }
-
-                    /// This destroys the view and returns the underlying storage back to you.
-                    /// This is useful if you created an owning view (e.g. based on `Vec<u8>`)
-                    /// and now need the underlying `Vec<u8>` back.
-                    #[inline]
-                    pub fn into_storage(self) -> S {
-                        self.storage
-                    }
-
-                    $crate::binary_layout!(@impl_view_into {$($field_name),*});
-                }
-                impl <S: AsRef<[u8]>> View<S> {
-                    $crate::binary_layout!(@impl_view_asref {$($field_name),*});
-                }
-                impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
-                    $crate::binary_layout!(@impl_view_asmut {$($field_name),*});
-                }
-
-                /// Use this as a marker type for using this layout as a nested field within another layout.
-                ///
-                /// # Example
-                /// ```
-                /// use binary_layout::prelude::*;
-                ///
-                /// binary_layout!(icmp_header, BigEndian, {
-                ///   packet_type: u8,
-                ///   code: u8,
+///
+/// The other functions creating a `View` are generated as functions of the module, so they don't collide with the accessors of a field:
+/// - `try_new(storage)` to create a `View` after checking that the storage is long enough for the layout, returning a [LayoutError](crate::LayoutError) otherwise
+/// - `try_new_exact(storage)` to create a `View` like `try_new`, but additionally return a [LayoutError](crate::LayoutError) if the storage is longer than the layout
+/// - `at_offset(storage, base)` to create a `View` over a layout starting at offset `base` within the storage
+/// - `view_at(storage, offset, len)` to create a `View` over a layout stored in the region `offset..offset + len` of the storage, returning a [LayoutError](crate::LayoutError) if the region is out of bounds or too short
+/// - `new_version(storage, version)` to create a [VersionedView](#versioned-fields) over a message of an older version of the layout (requires `#[derive(VersionedView)]`)
+/// - `from_vec_truncating(vec)` to create an owning `View` like `try_new` and truncate the `Vec<u8>` to the bytes used by the layout, keeping its allocation (requires the `std` feature)
+/// - `read_framed(reader)` to read a complete message, including a tail with a computed length, from a [Read](std::io::Read) into an owning `View<Vec<u8>>` (requires the `std` feature)
+///
+/// The same goes for the functions working on the storage of a whole message:
+/// - `byte_len(storage)` to get the number of bytes used by the layout, including the current length of a dynamically sized last field
+/// - `check_magic(storage)` to check that all fields declared with a [`#[magic(...)]` attribute](#magic-numbers) hold their magic number
+/// - `matches(storage, template, mask)` to compare the bytes used by the layout against a template, only looking at the bits set in the mask
+/// - `reset_to_defaults(storage)` to reset all fields to their [default value](#default-values)
+/// - `push_tail(vec, bytes)` to append bytes to the dynamically sized last field of a message in a `Vec<u8>` and update a [`#[tail_len]` field](#growing-the-tail) (requires the `std` feature)
+/// - `read_fields(storage)` to decode all fields into a [Fields](#struct-fields) struct (requires `#[derive(Fields)]` and the `std` feature)
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, BigEndian, {
+///   len: u8,
+///   payload: [u8; expr(|v| v.len().read() as usize)],
+/// });
+///
+/// let storage = [2, 10, 11, 0xFF];
+/// let view = message::try_new(&storage[..]).unwrap();
+/// assert_eq!(&[10, 11], view.payload());
+/// assert_eq!(3, message::byte_len(&storage));
+/// assert!(message::try_new_exact(&storage[..]).is_err());
+/// ```
+///
+/// ## trait ReadView and trait WriteView
+/// Layouts with a `#[derive(ReadView)]` attribute also get a `ReadView` trait implemented by all views of the layout and a `WriteView` trait
+/// implemented by all views with write access. Generic code can take any view with `impl ReadView` or `impl WriteView` instead of needing
+/// one function per storage type, and access the fields through `as_view()` or `as_view_mut()`.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(ReadView)] message, BigEndian, {
+///   id: u16,
+///   counter: u8,
+/// });
+///
+/// fn inspect(view: &impl message::ReadView) -> u16 {
+///   view.as_view().id().read()
+/// }
+///
+/// fn count(view: &mut impl message::WriteView) {
+///   let mut view = view.as_view_mut();
+///   let counter = view.counter().read();
+///   view.counter_mut().write(counter + 1);
+/// }
+///
+/// let mut storage = [0, 5, 0];
+/// let mut view = message::View::new(&mut storage);
+/// count(&mut view);
+/// assert_eq!(5, inspect(&view));
+/// assert_eq!(5, inspect(&message::View::new(&storage)));
+/// assert_eq!(1, storage[2]);
+/// ```
+///
+/// ## struct TrackedView
+/// Layouts with a `#[derive(TrackedView)]` attribute also get a `TrackedView` struct that wraps a `View` and records which fields were
+/// written through it, e.g. to send only the fields that changed since the last sync. It offers the `${field_name}_mut()` accessors of
+/// the `View`, which mark the field as dirty, and `view()` for read access. `dirty_fields()` returns the names of the dirty fields
+/// and `clear_dirty()` marks all fields as clean again. If the layout also derives `ReadView`, the `TrackedView` implements `ReadView`.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(TrackedView)] settings, LittleEndian, {
+///   volume: u8,
+///   brightness: u8,
+///   timeout: u16,
+/// });
+///
+/// let mut tracked = settings::TrackedView::new([0; 4]);
+/// tracked.timeout_mut().write(300);
+/// tracked.volume_mut().write(10);
+/// assert_eq!(vec!["volume", "timeout"], tracked.dirty_fields().collect::<Vec<_>>());
+///
+/// tracked.clear_dirty();
+/// assert_eq!(None, tracked.dirty_fields().next());
+/// assert_eq!(300, tracked.view().timeout().read());
+/// ```
+///
+/// ## fn required_len
+/// The generated module also offers a `const fn required_len(tail_len)` returning the number of bytes a storage needs to hold
+/// the layout if its dynamically sized last field, e.g. an open ended byte array or a `[u8; expr(...)]` field, is `tail_len` bytes long.
+/// This is useful to allocate a buffer of the right size before writing a message. For layouts with a static size, it returns `SIZE`
+/// and ignores `tail_len`.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, BigEndian, {
+///   payload_len: u16,
+///   payload: [u8; expr(|v| v.payload_len().read() as usize)],
+/// });
+///
+/// let payload = b"hello";
+/// let mut storage = vec![0; message::required_len(payload.len())];
+/// let mut view = message::View::new(&mut storage);
+/// view.payload_len_mut().write(payload.len() as u16);
+/// view.payload_mut().copy_from_slice(payload);
+/// assert_eq!(7, storage.len());
+/// ```
+///
+/// ## fn iter_records
+/// The generated module also offers an `iter_records(storage)` function returning a [Records](crate::Records) iterator
+/// over records of the layout that are stored back to back in the storage, e.g. a sequence of type-length-value records.
+///
+/// ## fn offset_in_record
+/// For code that accesses records stored back to back by index instead of iterating over them, the generated module offers
+/// a `STRIDE` constant, which is the same as `SIZE`, and an `offset_in_record::<F>(index)` function returning the absolute offset
+/// of the field `F` in the `index`-th record. `offset_in_record` fails to compile for layouts with a dynamic size.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(sample, LittleEndian, {
+///   channel: u8,
+///   value: i16,
+/// });
+///
+/// let storage = [1, 0xFB, 0xFF, 2, 0x2C, 0x01];
+/// assert_eq!(Some(3), sample::STRIDE);
+/// let offset = sample::offset_in_record::<sample::value>(1);
+/// assert_eq!(4, offset);
+/// assert_eq!(300, i16::from_le_bytes([storage[offset], storage[offset + 1]]));
+/// ```
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, LittleEndian, {
+///   len: u8,
+///   payload: [u8],
+/// });
+///
+/// // fails because records of this layout don't have a fixed stride
+/// let offset = message::offset_in_record::<message::len>(1);
+/// ```
+///
+/// ## fn read_from
+/// The generated module also offers a `read_from(reader, storage)` function that reads a record byte by byte from a
+/// [ByteReader](crate::ByteReader), e.g. a serial port, into a caller provided buffer and returns a `View` over it.
+/// It doesn't need `std::io` or `alloc`, see [ByteReader](crate::ByteReader) for an example.
+///
+/// With the `std` feature enabled, the generated module also offers a `read_framed(reader)` function, which reads a complete
+/// message from a [Read](std::io::Read), e.g. a TCP stream, into an owning `View` over a `Vec<u8>` it allocates. It reads the statically sized fields first,
+/// takes the length of the message from the [computed length](crate#byte-arrays-with-computed-length-u8-expr) of a last `[u8; expr(...)]` field,
+/// and then reads the rest of the message.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(frame, BigEndian, {
+///   kind: u8,
+///   payload_len: u16,
+///   payload: [u8; expr(|v| v.payload_len().read() as usize)],
+/// });
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() -> Result<(), binary_layout::ReadFromError<std::io::Error>> {
+/// let mut stream: &[u8] = &[1, 0, 3, b'a', b'b', b'c', 2, 0, 0];
+/// let first = frame::read_framed(&mut stream)?;
+/// assert_eq!(b"abc", first.payload());
+/// let second = frame::read_framed(&mut stream)?;
+/// assert_eq!(2, second.kind().read());
+/// assert!(stream.is_empty());
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## fn push_record
+/// With the `std` feature enabled, the generated module also offers a `push_record(buf)` function that appends a zeroed record
+/// of the layout to a `Vec<u8>` and returns a `View` over the appended bytes. This is useful to write many records back to back,
+/// e.g. in capture tools, without tracking offsets by hand. For layouts with a dynamic size, the dynamically sized last field is empty.
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(sample, LittleEndian, {
+///   channel: u8,
+///   value: i16,
+/// });
+///
+/// let mut buf = Vec::new();
+/// for (channel, value) in [(1, -5), (2, 300)] {
+///     let mut record = sample::push_record(&mut buf);
+///     record.channel_mut().write(channel);
+///     record.value_mut().write(value);
+/// }
+/// assert_eq!(vec![1, 0xFB, 0xFF, 2, 0x2C, 0x01], buf);
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## Growing the tail
+/// With the `std` feature enabled, the generated module also offers a `push_tail(vec, bytes)` function, which appends bytes to the dynamically
+/// sized last field of a message stored in a `Vec<u8>`, e.g. to build a message with a list of options piece by piece. If a field holds the length of the last field, mark it with a
+/// `#[tail_len]` attribute and `push_tail` updates it, so a `[u8; expr(...)]` field reading it keeps covering all appended bytes.
+/// It returns a [LayoutError::TailLenOutOfRange](crate::LayoutError::TailLenOutOfRange) if the new length doesn't fit into the field.
+/// The statically sized fields don't move, so their offsets stay valid while the message grows.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(packet, BigEndian, {
+///   kind: u8,
+///   #[tail_len]
+///   options_len: u8,
+///   options: [u8; expr(|v| v.options_len().read() as usize)],
+/// });
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() -> Result<(), LayoutError> {
+/// let mut storage = vec![0; packet::required_len(0)];
+/// packet::kind::write(&mut storage, 7);
+/// packet::push_tail(&mut storage, &[1, 1, 0xAA])?;
+/// packet::push_tail(&mut storage, &[2, 2, 0xBB, 0xCC])?;
+/// let view = packet::View::new(&storage);
+/// assert_eq!(7, view.kind().read());
+/// assert_eq!(7, view.options_len().read());
+/// assert_eq!(vec![7, 7, 1, 1, 0xAA, 2, 2, 0xBB, 0xCC], storage);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// The new length is written to the `#[tail_len]` field as is, so the `expr(...)` of the last field must return the value of the field unchanged.
+/// Lengths counted in other units, e.g. in elements of several bytes, aren't supported. Calling `push_tail` for a layout with a static size fails to compile.
+///
+/// ```compile_fail
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(packet, BigEndian, {
+///   kind: u8,
+///   options: [u8; 4],
+/// });
+///
+/// # fn main() {
+/// // fails because `packet` doesn't have a dynamically sized last field
+/// packet::push_tail(&mut vec![0; 5], &[1]).unwrap();
+/// # }
+/// ```
+///
+/// ## fn debug_fields
+/// With the `std` feature enabled, layouts with a `#[derive(DebugFields)]` attribute also get a `debug_fields(storage)` function that reads
+/// all fields of the layout that can be read as a whole, e.g. integer fields, and returns them as `(name, Box<dyn Debug>)` pairs. This is useful for debugging tools.
+///
+/// ## fn field_layout
+/// With the `std` feature enabled, the generated module also offers a `field_layout(storage)` function
+/// returning the name and the byte range of each field as `(name, Range<usize>)` pairs, resolving the length of dynamically sized fields
+/// for the given storage. This is useful for tools like protocol dissectors.
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, BigEndian, {
+///   len: u8,
+///   flags: u8,
+///   payload: [u8; expr(|v| v.len().read() as usize)],
+/// });
+///
+/// let storage = [3, 0, 10, 11, 12, 0xFF];
+/// assert_eq!(
+///     vec![("len", 0..1), ("flags", 1..2), ("payload", 2..5)],
+///     message::field_layout(&storage),
+/// );
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## fn diff
+/// With the `std` feature enabled, layouts with a `#[derive(Diff)]` attribute also get a `diff(a, b)` function that compares two storages
+/// of the layout field by field and returns a [FieldDiff](crate::FieldDiff) for each field that is stored differently. Fields that can be read as a whole
+/// report both values formatted with [Debug](core::fmt::Debug), slice fields report the range of bytes that differ.
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::{prelude::*, FieldDiff};
+///
+/// binary_layout!(#[derive(Diff)] message, BigEndian, {
+///   id: u16,
+///   flags: u8,
+///   payload: [u8],
+/// });
+///
+/// let a = [0, 1, 0, 10, 11, 12];
+/// let b = [0, 2, 0, 10, 21, 22];
+/// assert_eq!(
+///     vec![
+///         FieldDiff::Value { name: "id", a: "1".to_string(), b: "2".to_string() },
+///         FieldDiff::Bytes { name: "payload", range: 1..3 },
+///     ],
+///     message::diff(&a, &b),
+/// );
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## fn to_hex
+/// With the `std` feature enabled, the generated module also offers `to_hex(storage)` and `to_hex_upper(storage)` functions
+/// that return the bytes used by the layout as a hex string without separators, e.g. for single line logs.
+/// For layouts with a dynamic size, only the bytes up to the current end of the layout are included, see `byte_len(storage)`.
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, BigEndian, {
+///   len: u8,
+///   payload: [u8; expr(|v| v.len().read() as usize)],
+/// });
+///
+/// let storage = [2, 0xAB, 0x0C, 0xFF, 0xFF];
+/// assert_eq!("02ab0c", message::to_hex(&storage));
+/// assert_eq!("02AB0C", message::to_hex_upper(&storage));
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## fn write_to_chunked
+/// With the `std` feature enabled, the generated module also offers a `write_to_chunked(storage, writer, chunk)` function
+/// that writes the bytes used by the layout to a [Write](std::io::Write) in pieces of at most `chunk` bytes and flushes the writer
+/// after each piece, e.g. for transports with a fixed segment size. The last piece can be shorter. It returns the number of bytes written.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(message, BigEndian, {
+///   id: u32,
+///   payload: [u8; 6],
+/// });
+///
+/// # #[cfg(feature = "std")]
+/// # fn main() -> std::io::Result<()> {
+/// let storage = [0, 0, 0, 7, 1, 2, 3, 4, 5, 6, 0xFF];
+/// let mut output = Vec::new();
+/// assert_eq!(10, message::write_to_chunked(&storage, &mut output, 4)?);
+/// assert_eq!(storage[..10], output);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## fn from_fields_str
+/// With the `std` feature enabled, layouts with a `#[derive(FromStr)]` attribute also get a `from_fields_str(text)` function that creates
+/// an owned `View` from a text with comma separated `name=value` pairs, e.g. for test fixtures or command line tools. Each value is parsed with
+/// [FromStr](core::str::FromStr) into the type of the field, and the bytes of fields that aren't given are zero. The `View<Vec<u8>>`
+/// also implements [FromStr](core::str::FromStr) this way. Fields whose type can't be parsed, e.g. byte arrays, and unknown names
+/// return a [ParseFieldsError](crate::ParseFieldsError).
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::{prelude::*, ParseFieldsError};
+///
+/// binary_layout!(#[derive(FromStr)] message, BigEndian, {
+///   kind: u8,
+///   flags: u8,
+///   temperature: i16,
+///   payload: [u8; 2],
+/// });
+///
+/// let view = message::from_fields_str("kind=3, temperature=-20").unwrap();
+/// assert_eq!(vec![3, 0, 0xFF, 0xEC, 0, 0], view.into_storage());
+///
+/// let view: message::View<Vec<u8>> = "flags=255".parse().unwrap();
+/// assert_eq!(255, view.flags().read());
+///
+/// assert_eq!(
+///     Err(ParseFieldsError::InvalidValue { name: "flags", value: "256".to_string() }),
+///     message::from_fields_str("flags=256").map(|view| view.into_storage()),
+/// );
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// ## impl Arbitrary
+/// With the `arbitrary` feature enabled, the owned `View<Vec<u8>>` of layouts with a `#[derive(Arbitrary)]` attribute implements [arbitrary::Arbitrary](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html),
+/// e.g. for fuzzing parsers or for property tests. The bytes of the message are arbitrary, but fields whose type implements
+/// `Arbitrary`, e.g. integers, `bool` or `NonZeroU32`, are overwritten with an arbitrary value of that type, so reading them
+/// succeeds. Layouts ending in an open ended byte slice get an arbitrary number of bytes for it.
+///
+/// ```
+/// # #[cfg(feature = "arbitrary")]
+/// # fn main() {
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(Arbitrary)] message, LittleEndian, {
+///   id: core::num::NonZeroU16,
+///   ready: bool as u8,
+///   payload: [u8],
+/// });
+///
+/// let mut u = Unstructured::new(&[0, 0, 2, 0, 1, 7, 3, 5, 5, 1]);
+/// let view = message::View::arbitrary(&mut u).unwrap();
+/// assert!(view.id().try_read().is_ok());
+/// assert!(view.ready().try_read().is_ok());
+/// # }
+/// # #[cfg(not(feature = "arbitrary"))]
+/// # fn main() {}
+/// ```
+///
+/// ## struct Fields
+/// With the `std` feature enabled, layouts with a `#[derive(Fields)]` attribute also get a `Fields` struct with one public member per field,
+/// and a `read_fields(storage)` function decoding all fields into it. This is useful for code that parses a message once
+/// and then works with plain values, e.g. by destructuring them with a pattern. Integer and float fields are decoded into their value,
+/// other fields that can be read as a whole into the result of reading them, byte arrays into a `Vec<u8>` copy of their bytes
+/// and nested layouts into the `Fields` struct of the nested layout.
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(#[derive(Fields)] message, BigEndian, {
+///   id: u16,
+///   flags: u8,
+///   payload: [u8],
+/// });
+///
+/// let storage = vec![0, 1, 2, 10, 11, 12];
+/// let message::Fields { id, flags, payload } = message::read_fields(&storage);
+/// assert_eq!(1, id);
+/// assert_eq!(2, flags);
+/// assert_eq!(vec![10, 11, 12], payload);
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! binary_layout {
+    (#[assert_pow2_size] $(#[$($attr: tt)*])* $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!($(#[$($attr)*])* $name, $endianness, {$($fields)*});
+        const _: () = $crate::internal::AssertPow2Size::<{$crate::internal::static_layout_size($name::SIZE)}>::CHECK;
+    };
+    (#[$($first_attr: tt)*] #[assert_pow2_size] $($tail: tt)*) => {
+        $crate::binary_layout!(#[assert_pow2_size] #[$($first_attr)*] $($tail)*);
+    };
+    (#[$($first_attr: tt)*] #[$($second_attr: tt)*] #[assert_pow2_size] $($tail: tt)*) => {
+        $crate::binary_layout!(#[$($first_attr)*] #[assert_pow2_size] #[$($second_attr)*] $($tail)*);
+    };
+    (#[deny_gaps] $(#[$($attr: tt)*])* $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!($(#[$($attr)*])* $name, $endianness, {$($fields)*});
+        const _: () = $crate::internal::DenyGaps::<{$crate::internal::first_gap($name::GAPS).0}, {$crate::internal::first_gap($name::GAPS).1}>::CHECK;
+    };
+    (#[$($first_attr: tt)*] #[deny_gaps] $($tail: tt)*) => {
+        $crate::binary_layout!(#[deny_gaps] #[$($first_attr)*] $($tail)*);
+    };
+    (#[$($first_attr: tt)*] #[$($second_attr: tt)*] #[deny_gaps] $($tail: tt)*) => {
+        $crate::binary_layout!(#[$($first_attr)*] #[deny_gaps] #[$($second_attr)*] $($tail)*);
+    };
+    (#[derive($($derive: ident),* $(,)?)] $(#[$($attr: tt)*])* $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@derive [$($derive)*] $(#[$($attr)*])* $name, $endianness, {$($fields)*});
+    };
+    (#[$($first_attr: tt)*] #[derive($($derive: ident),* $(,)?)] $($tail: tt)*) => {
+        $crate::binary_layout!(#[derive($($derive),*)] #[$($first_attr)*] $($tail)*);
+    };
+    (#[$($first_attr: tt)*] #[$($second_attr: tt)*] #[derive($($derive: ident),* $(,)?)] $($tail: tt)*) => {
+        $crate::binary_layout!(#[$($first_attr)*] #[derive($($derive),*)] #[$($second_attr)*] $($tail)*);
+    };
+    ($(#[$($attr: tt)*])* $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@derive [] $(#[$($attr)*])* $name, $endianness, {$($fields)*});
+    };
+
+    // The @derive rules take the optional items requested with `#[derive(...)]`, see @if_derive, and the other layout attributes
+    (@derive [$($derive: ident)*] $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness, derive = [$($derive)*]} [] [] [] (Some(0)), [pub], {$($fields)*});
+    };
+    (@derive [$($derive: ident)*] #[pad_to($pad_to: expr)] $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness, pad_to = $pad_to, derive = [$($derive)*]} [] [] [] (Some(0)), [pub], {$($fields)*});
+    };
+    (@derive [$($derive: ident)*] #[display($display: literal)] $(#[pad_to($pad_to: expr)])? $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)?, display = $display, derive = [$($derive)*]} [] [] [] (Some(0)), [pub], {$($fields)*});
+    };
+    (@derive [$($derive: ident)*] #[pad_to($pad_to: expr)] #[display($display: literal)] $name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!(@derive [$($derive)*] #[display($display)] #[pad_to($pad_to)] $name, $endianness, {$($fields)*});
+    };
+
+    // The @parse_fields rules walk through the field list one field at a time and accumulate
+    // - the definition of each field for the generated documentation,
+    // - the items (e.g. type aliases) generated for each field,
+    // - the names of the fields,
+    // - the offset of the next field
+    // and once all fields are processed, they generate the module for the layout.
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {}) => {
+        $crate::binary_layout!(@impl_module {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*]} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator);
+    };
+
+    // A `union {...}` block starts a union. Its members are parsed like other fields, but each of them starts at the offset
+    // of the union, see @add_field. A `@union_end` marker after the members ends the union, and the following fields continue
+    // after the end of its largest member.
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {union {$($members: tt)*} $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*], union = $offset_accumulator []} [
+            $($docs)* "\n  union {",
+        ] [$($items)*] [$($fields)*] $offset_accumulator, [pub], {$($members)*, @union_end $($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*], union = $union_start: tt [$($union_members: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {, @union_end $($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*], union = $union_start [$($union_members)*]} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$($field_vis)*], {@union_end $($tail)*});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*], union = $union_start: tt [$($union_members: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {@union_end $($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*]} [
+            $($docs)* "\n  },",
+        ] [$($items)*] [$($fields)*] ($crate::internal::union_end(&[$([<__ $union_members __end>]),*])), [pub], {$($tail)*});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* pub $(($($restriction: tt)*))? $field_name: ident $($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [pub $(($($restriction)*))?], {$(#[$($field_attr)*])* $field_name $($tail)*});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident @ $pinned_offset: literal $($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [
+            $($docs)* "\n  // ", stringify!($field_name), " is pinned to offset ", stringify!($pinned_offset),
+        ] [$($items)*] [$($fields)*] ($crate::internal::pin_offset($offset_accumulator, $pinned_offset)), [$($field_vis)*], {$(#[$($field_attr)*])* $field_name $($tail)*});
+    };
+    // `bits START..END` numbers bits like RFC diagrams, i.e. bit 0 is the most significant bit of the first byte.
+    // This is a big endian `Bits<Msb0, ...>` field spanning the bytes up to and including the one with bit `END - 1`.
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : bits $start: literal .. $end: literal $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": bits ", stringify!($start), "..", stringify!($end), ","
+        } [] [
+            $crate::PrimitiveField::<$crate::Bits<$crate::Msb0, {($end + 7) / 8}, $start, {$end - $start}>, $crate::BigEndian, {$crate::internal::unwrap_field_size($offset_accumulator)}>
+        ], {$($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : [u8; expr($length: expr)] $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": [u8; expr(", stringify!($length), ")],"
+        } [
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            pub struct [<__ $field_name __length>];
+            impl $crate::internal::ComputedLength for [<__ $field_name __length>] {
+                #[inline(always)]
+                fn len(storage: &[u8]) -> usize {
+                    let length: fn(&View<&[u8]>) -> usize = $length;
+                    length(&View::new(storage))
+                }
+            }
+        ] [
+            $crate::PrimitiveField::<$crate::internal::ComputedSlice<[<__ $field_name __length>]>, $crate::$endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>
+        ], {$($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : [$element_type: ty; $len: expr] as $underlying_type: ty $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": [", stringify!($element_type), "; ", stringify!($len), "] as ", stringify!($underlying_type), ","
+        } [
+            #[doc(hidden)]
+            #[allow(non_snake_case, dead_code)]
+            fn [<__ $field_name __layout_as_check>]() {
+                $crate::internal::field_type_must_implement_LayoutAs_for_the_type_after_as::<$element_type, $underlying_type>();
+            }
+        ] [
+            $crate::PrimitiveField::<$crate::LayoutAsArray<$underlying_type, $element_type, $len>, $crate::$endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>
+        ], {$($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : $field_type: ty as ($first_type: ty, $second_type: ty) $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": ", stringify!($field_type), " as (", stringify!($first_type), ", ", stringify!($second_type), "),"
+        } [] [
+            $crate::CompositeField::<
+                $first_type,
+                $second_type,
+                $field_type,
+                $crate::PrimitiveField::<$first_type, $crate::$endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>,
+                $crate::PrimitiveField::<$second_type, $crate::$endianness, {$crate::internal::unwrap_field_size($crate::internal::option_usize_add($crate::internal::unwrap_field_size($offset_accumulator), <$crate::PrimitiveField::<$first_type, $crate::$endianness, 0> as $crate::Field>::SIZE))}>
+            >
+        ], {$($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : $field_type: ty as $underlying_type: ty $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": ", stringify!($field_type), " as ", stringify!($underlying_type), ","
+        } [
+            // Surface a mismatch between the custom type and the declared underlying type as an error
+            // mentioning the field, before the errors from the generated code using the field
+            #[doc(hidden)]
+            #[allow(non_snake_case, dead_code)]
+            fn [<__ $field_name __layout_as_check>]() {
+                $crate::internal::field_type_must_implement_LayoutAs_for_the_type_after_as::<$field_type, $underlying_type>();
+            }
+        ] [
+            $crate::WrappedField::<$underlying_type, $field_type, $crate::PrimitiveField::<$underlying_type, $crate::$endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>>
+        ], {$($($tail)*)?});
+    };
+    (@parse_fields {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*] $(, union = $union_start: tt [$($union_members: ident)*])?} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_vis: tt)*], {$(#[$($field_attr: tt)*])* $field_name: ident : $field_type: ty $(, $($tail: tt)*)?}) => {
+        $crate::binary_layout!(@add_field {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*] $(, union = $union_start [$($union_members)*])?} [$($docs)*] [$($items)*] [$($fields)*] $offset_accumulator, [$(#[$($field_attr)*])*] [$($field_vis)*], $field_name, {
+            ": ", stringify!($field_type), ","
+        } [] [
+            $crate::PrimitiveField::<$field_type, $crate::$endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>
+        ], {$($($tail)*)?});
+    };
+
+    // The @add_field rule is shared by all field kinds. It adds the field to the documentation and lets the
+    // @field_items rules generate the items for the field. The offset of the next field is taken from a
+    // constant generated by @field_items.
+    (@add_field {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_attrs: tt)*] [$($field_vis: tt)*], $field_name: ident, {$($field_docs: tt)*} [$($field_items: tt)*] [$($field_type: tt)*], {$($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*]} [
+            $($docs)* "\n  ", stringify!($field_name), $($field_docs)*,
+        ] [
+            $($items)*
+            $crate::binary_layout!(@field_items [$($field_attrs)*] [] [] [] [[$($field_vis)*]] [] [] [] [] [] {$field_name [$($field_vis)*] [$($field_items)*] [$($field_type)*] $offset_accumulator [$($derive)*]});
+        ] [$($fields)* {$field_name [$($field_attrs)*]}] ([<__ $field_name __end>]), [pub], {$($tail)*});
+    };
+
+    // Fields in a union block all start at the offset of the union, and the union keeps track of its members
+    // so its end can be computed once all members are processed.
+    (@add_field {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*], union = $union_start: tt [$($union_members: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$($fields: tt)*] $offset_accumulator: tt, [$($field_attrs: tt)*] [$($field_vis: tt)*], $field_name: ident, {$($field_docs: tt)*} [$($field_items: tt)*] [$($field_type: tt)*], {$($tail: tt)*}) => {
+        $crate::binary_layout!(@parse_fields {$name, $endianness $(, pad_to = $pad_to)? $(, display = $display)?, derive = [$($derive)*], union = $union_start [$($union_members)* $field_name]} [
+            $($docs)* "\n    ", stringify!($field_name), $($field_docs)*,
+        ] [
+            $($items)*
+            $crate::binary_layout!(@field_items [$($field_attrs)*] [] [] [] [[$($field_vis)*]] [] [] [] [] [] {$field_name [$($field_vis)*] [$($field_items)*] [$($field_type)*] $offset_accumulator [$($derive)*]});
+        ] [$($fields)* {$field_name [$($field_attrs)*]}] $union_start, [pub], {$($tail)*});
+    };
+
+    // The @field_items rules walk through the attributes of a field and
+    // - collect its cfg predicates,
+    // - take the name for the generated items from a `#[name(...)]` attribute, if there is one,
+    // - collect the checksums described by `#[checksum(...)]` attributes,
+    // - take the default value of the field from a `#[default(...)]` attribute, if there is one,
+    // - take the version the field was added in from a `#[since(...)]` attribute, if there is one,
+    // - take the magic number of the field from a `#[magic(...)]` attribute, if there is one, which is also its default value,
+    // - remember whether the field holds the length of the dynamically sized last field from a `#[tail_len]` attribute,
+    // - collect the fields of the nested layout to generate flattened aliases for from `#[flatten(...)]` attributes,
+    // - keep all other attributes so they can be forwarded to the generated items.
+    // They then generate the type alias and View accessors for the field, a constant with the offset
+    // at which the next field starts, and a function returning the number of bytes of the storage used up
+    // to the end of the field. If the field is disabled by a cfg predicate, the next field starts where
+    // this field would have started and the field doesn't contribute to the byte length. Items only needed
+    // for an attribute or a crate feature, e.g. `__reset` for `#[default(...)]` or `__parse` for `std`,
+    // are only generated if the field has the attribute or the feature is enabled.
+    (@field_items [#[cfg($($cfg: tt)*)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)* #[cfg($($cfg)*)]] [$($cfgs)* ($($cfg)*)] [$($public_name)*] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[name($new_name: ident)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$new_name] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[checksum($algorithm: ty, from = $from: ident, to = $to: ident $(,)?)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)* ($algorithm, $from, $to)] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[default($value: expr)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$value] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[magic($value: expr)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$value] [$($since)*] [$value] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[tail_len] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [tail_len] [$($flatten)*] $context);
+    };
+    (@field_items [#[flatten($($module: ident)::+ :: {$($inner: ident),* $(,)?})] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)* ([$($module)::+] [$($inner)*])] $context);
+    };
+    (@field_items [#[since($version: expr)] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)*] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$($default)*] [$version] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [#[$($attr: tt)*] $($attrs: tt)*] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [$($public_name: tt)*] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] $context: tt) => {
+        $crate::binary_layout!(@field_items [$($attrs)*] [$($kept_attrs)* #[$($attr)*]] [$($cfgs)*] [$($public_name)*] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] $context);
+    };
+    (@field_items [] [$($kept_attrs: tt)*] [$($cfgs: tt)*] [] [$($checksums: tt)*] [$($default: tt)*] [$($since: tt)*] [$($magic: tt)*] [$($tail_len: tt)*] [$($flatten: tt)*] {$field_name: ident $($context: tt)*}) => {
+        $crate::binary_layout!(@field_items [] [$($kept_attrs)*] [$($cfgs)*] [$field_name] [$($checksums)*] [$($default)*] [$($since)*] [$($magic)*] [$($tail_len)*] [$($flatten)*] {$field_name $($context)*});
+    };
+    (@field_items [] [$($field_attrs: tt)*] [$(($($cfg: tt)*))*] [$public_name: ident] [$item_vis: tt $(($algorithm: ty, $from: ident, $to: ident))*] [$($default_value: expr)?] [$($since_version: expr)?] [$($magic_value: expr)?] [$($tail_len: ident)?] [$($flatten: tt)*] {$field_name: ident [$($field_vis: tt)*] [$($field_items: tt)*] [$($field_type: tt)*] $offset_accumulator: tt [$($derive: ident)*]}) => {
+        $crate::internal::paste!{
+            $crate::internal::doc_comment!{
+                concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!([<$field_name>]), "` field\n"),
+                $($field_attrs)*
+                #[allow(non_camel_case_types)]
+                $($field_vis)* type $public_name = $($field_type)*;
+            }
+            impl <S: AsRef<[u8]>> View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return a [FieldView](crate::FieldView) with read access to the `", stringify!([<$field_name>]), "` field\n"),
+                    $($field_attrs)*
+                    #[inline]
+                    $($field_vis)* fn $public_name(&self) -> <$public_name as $crate::internal::StorageToFieldView<&[u8]>>::View {
+                        <$public_name as $crate::internal::StorageToFieldView<&[u8]>>::view(self.storage.as_ref())
+                    }
+                }
+                $crate::internal::doc_comment!{
+                    concat!("Destroy the [View] and return a field accessor to the `", stringify!([<$field_name>]), "` field owning the storage. This is mostly useful for [FieldView::extract](crate::FieldView::extract)\n"),
+                    $($field_attrs)*
+                    #[inline]
+                    $($field_vis)* fn [<into_ $public_name>](self) -> <$public_name as $crate::internal::StorageIntoFieldView<S>>::View {
+                        <$public_name as $crate::internal::StorageIntoFieldView<S>>::into_view(self.storage)
+                    }
+                }
+            }
+            impl <S: AsRef<[u8]> + AsMut<[u8]>> View<S> {
+                $crate::internal::doc_comment!{
+                    concat!("Return a [FieldView](crate::FieldView) with write access to the `", stringify!([<$field_name>]), "` field\n"),
+                    $($field_attrs)*
+                    #[inline]
+                    $($field_vis)* fn [<$public_name _mut>](&mut self) -> <$public_name as $crate::internal::StorageToFieldView<&mut [u8]>>::View {
+                        <$public_name as $crate::internal::StorageToFieldView<&mut [u8]>>::view(self.storage.as_mut())
+                    }
+                }
+            }
+            $crate::binary_layout!(@if_derive TrackedView [$($derive)*] {
+                impl <S: AsRef<[u8]> + AsMut<[u8]>> TrackedView<S> {
+                    $crate::internal::doc_comment!{
+                        concat!("Return a [FieldView](crate::FieldView) with write access to the `", stringify!([<$field_name>]), "` field and mark the field as dirty\n"),
+                        $($field_attrs)*
+                        #[inline]
+                        $($field_vis)* fn [<$public_name _mut>](&mut self) -> <$public_name as $crate::internal::StorageToFieldView<&mut [u8]>>::View {
+                            const INDEX: usize = $crate::internal::field_index(FIELD_NAMES, [<__ $field_name __name>]);
+                            self.dirty[INDEX] = true;
+                            self.view.[<$public_name _mut>]()
+                        }
+                    }
+                }
+            });
+            $crate::binary_layout!(@if_derive VersionedView [$($derive)*] {
+                impl <S: AsRef<[u8]>> VersionedView<S> {
+                    $crate::internal::doc_comment!{
+                        concat!("Return a [FieldView](crate::FieldView) with read access to the `", stringify!([<$field_name>]), "` field, or `None` if the field doesn't exist in the version of this view\n"),
+                        $($field_attrs)*
+                        #[inline]
+                        $($field_vis)* fn $public_name(&self) -> Option<<$public_name as $crate::internal::StorageToFieldView<&[u8]>>::View> {
+                            if [<__ $field_name __since>] <= self.version {
+                                Some(self.view.$public_name())
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                }
+                impl <S: AsRef<[u8]> + AsMut<[u8]>> VersionedView<S> {
+                    $crate::internal::doc_comment!{
+                        concat!("Return a [FieldView](crate::FieldView) with write access to the `", stringify!([<$field_name>]), "` field, or `None` if the field doesn't exist in the version of this view\n"),
+                        $($field_attrs)*
+                        #[inline]
+                        $($field_vis)* fn [<$public_name _mut>](&mut self) -> Option<<$public_name as $crate::internal::StorageToFieldView<&mut [u8]>>::View> {
+                            if [<__ $field_name __since>] <= self.version {
+                                Some(self.view.[<$public_name _mut>]())
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                }
+            });
+            // The hidden items of a field are named `__<field>__<item>`. Item names don't contain a double underscore, so the items of
+            // different fields can't collide, e.g. the `min_end` of `temp` and the `end` of `temp_min`.
+            #[doc(hidden)]
+            #[allow(non_upper_case_globals)]
+            const [<__ $field_name __name>]: &str = stringify!([<$public_name>]);
+            $crate::binary_layout!(@if_derive VersionedView [$($derive)*] {
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __since>]: u32 = {
+                    #[allow(unused_mut, unused_assignments)]
+                    let mut since = 0;
+                    $(since = $since_version;)?
+                    since
+                };
+            } {
+                $crate::binary_layout!(@if_some [$($since_version)?] {
+                    compile_error!("Error: Fields with a #[since(...)] attribute need a #[derive(VersionedView)] attribute on the layout");
+                });
+            });
+            $crate::binary_layout!(@cfg_items (all($($($cfg)*),*)) {
+                $($field_items)*
+                $(
+                    $crate::binary_layout!(@checksum_items $item_vis $public_name, $algorithm, $from, $to);
+                )*
+                $(
+                    $crate::binary_layout!(@flatten_items $item_vis $field_name $public_name, $flatten);
+                )*
+                // The marker is generic over the field type so that its FieldPath impl is only as public as the field type
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                pub struct [<__ $field_name __path>]<F = $public_name>(::core::marker::PhantomData<F>);
+                impl $crate::internal::FieldPath for [<__ $field_name __path>]<$public_name> {
+                    type Layout = NestedView;
+                    type Field = $public_name;
+                }
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __offset>]: Option<usize> = Some(<$public_name as $crate::Field>::OFFSET);
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __end>]: Option<usize> = $crate::internal::option_usize_add(<$public_name as $crate::Field>::OFFSET, <$public_name as $crate::Field>::SIZE);
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __min_end>]: usize = <$public_name as $crate::Field>::OFFSET + <$public_name as $crate::internal::FieldByteLen>::MIN_LEN;
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __dynamic_offset>]: usize = match <$public_name as $crate::Field>::SIZE {
+                    Some(_) => 0,
+                    None => <$public_name as $crate::Field>::OFFSET,
+                };
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                #[inline(always)]
+                fn [<__ $field_name __byte_len>](storage: &[u8]) -> usize {
+                    <$public_name as $crate::Field>::OFFSET + <$public_name as $crate::internal::FieldByteLen>::byte_len(storage)
+                }
+                // The items for the `#[default(...)]`, `#[magic(...)]` and `#[tail_len]` attributes only exist if the field has
+                // the attribute, and the functions of the layout only call them for those fields, see @call_fields.
+                $(
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    fn [<__ $field_name __reset>](storage: &mut [u8]) {
+                        <$public_name as $crate::FieldWriteExt>::write(storage, $default_value);
+                    }
+                )?
+                $(
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    fn [<__ $field_name __set_tail_len>](storage: &mut [u8], len: usize) -> Result<(), $crate::LayoutError> {
+                        let $tail_len = ::core::convert::TryFrom::try_from(len)
+                            .map_err(|_| $crate::LayoutError::TailLenOutOfRange { offset: <$public_name as $crate::Field>::OFFSET, len })?;
+                        <$public_name as $crate::FieldWriteExt>::write(storage, $tail_len);
+                        Ok(())
+                    }
+                )?
+                $(
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    fn [<__ $field_name __check_magic>](storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                        <$public_name as $crate::FieldMagic>::check(storage, $magic_value)
+                            .map_err(|_| $crate::LayoutError::BadMagic { offset: <$public_name as $crate::Field>::OFFSET })
+                    }
+                )?
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                #[inline(always)]
+                fn [<__ $field_name __validate>](storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                    $crate::binary_layout!(@probe [&&] $public_name, [ValidateCheckedField, ValidateOtherField], validate(storage))?;
+                    $crate::binary_layout!(@if_some [$($magic_value)?] {
+                        [<__ $field_name __check_magic>](storage)?;
+                    });
+                    Ok(())
+                }
+                $crate::internal::if_std!{
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline]
+                    fn [<__ $field_name __range>](storage: &[u8], ranges: &mut $crate::internal::Vec<(&'static str, ::core::ops::Range<usize>)>) {
+                        ranges.push((stringify!([<$public_name>]), <$public_name as $crate::Field>::OFFSET..[<__ $field_name __byte_len>](storage)));
+                    }
+                }
+                $crate::binary_layout!(@if_derive Fields [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_camel_case_types)]
+                        type [<__ $field_name __value>] = <$public_name as $crate::internal::FieldValue>::Value;
+                    }
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline]
+                        fn [<__ $field_name __into_value>](storage: &[u8]) -> [<__ $field_name __value>] {
+                            <$public_name as $crate::internal::FieldValue>::value(storage)
+                        }
+                    }
+                });
+                $crate::binary_layout!(@if_derive FromStr [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline]
+                        fn [<__ $field_name __parse>](storage: &mut [u8], name: &str, value: &str) -> Option<Result<(), $crate::ParseFieldsError>> {
+                            if name != [<__ $field_name __name>] {
+                                return None;
+                            }
+                            Some($crate::binary_layout!(@probe [&&] $public_name, [ParseCopyField, ParseOtherField], parse_into(storage, [<__ $field_name __name>], value)))
+                        }
+                    }
+                });
+                $crate::binary_layout!(@if_derive Translate [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline]
+                        fn [<__ $field_name __translate_value>](storage: &[u8], values: &mut $crate::internal::Vec<(&'static str, $crate::internal::Box<dyn ::core::any::Any>)>) {
+                            if let Some(value) = $crate::binary_layout!(@probe [&&&] $public_name, [TranslateCopyField, TranslateSliceField, TranslateOtherField], read_value(storage)) {
+                                values.push(([<__ $field_name __name>], value));
+                            }
+                        }
+                    }
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline]
+                        fn [<__ $field_name __translate_write>](storage: &mut [u8], name: &str, value: &dyn ::core::any::Any) -> Option<bool> {
+                            if name != [<__ $field_name __name>] {
+                                return None;
+                            }
+                            Some($crate::binary_layout!(@probe [&&&] $public_name, [TranslateCopyField, TranslateSliceField, TranslateOtherField], write_value(storage, value)))
+                        }
+                    }
+                });
+            } {
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                pub struct [<__ $field_name __path>]<F = ()>(::core::marker::PhantomData<F>);
+                impl $crate::internal::FieldPath for [<__ $field_name __path>] {
+                    type Layout = NestedView;
+                    type Field = ();
+                }
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __offset>]: Option<usize> = None;
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __end>]: Option<usize> = $offset_accumulator;
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __min_end>]: usize = 0;
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                const [<__ $field_name __dynamic_offset>]: usize = 0;
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                #[inline(always)]
+                fn [<__ $field_name __byte_len>](_storage: &[u8]) -> usize {
+                    0
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                #[inline(always)]
+                fn [<__ $field_name __validate>](_storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                    Ok(())
+                }
+                $crate::internal::if_std!{
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    #[inline(always)]
+                    fn [<__ $field_name __range>](_storage: &[u8], _ranges: &mut $crate::internal::Vec<(&'static str, ::core::ops::Range<usize>)>) {
+                    }
+                }
+                $crate::binary_layout!(@if_derive Fields [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_camel_case_types)]
+                        type [<__ $field_name __value>] = ();
+                    }
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        fn [<__ $field_name __into_value>](_storage: &[u8]) {
+                        }
+                    }
+                });
+                $crate::binary_layout!(@if_derive FromStr [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        fn [<__ $field_name __parse>](_storage: &mut [u8], _name: &str, _value: &str) -> Option<Result<(), $crate::ParseFieldsError>> {
+                            None
+                        }
+                    }
+                });
+                $crate::binary_layout!(@if_derive Translate [$($derive)*] {
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        fn [<__ $field_name __translate_value>](_storage: &[u8], _values: &mut $crate::internal::Vec<(&'static str, $crate::internal::Box<dyn ::core::any::Any>)>) {
+                        }
+                    }
+                    $crate::internal::if_std!{
+                        #[doc(hidden)]
+                        #[allow(non_snake_case)]
+                        #[inline(always)]
+                        fn [<__ $field_name __translate_write>](_storage: &mut [u8], _name: &str, _value: &dyn ::core::any::Any) -> Option<bool> {
+                            None
+                        }
+                    }
+                });
+            });
+        }
+    };
+    // Generate the flattened aliases given in a `#[flatten(...)]` attribute, one for each field of the nested layout.
+    // Each alias also gets a path marker, so the alias can be flattened again by layouts nesting this one.
+    (@flatten_items [$($field_vis: tt)*] $field_name: ident $public_name: ident, ([$($module: ident)::+] [])) => {};
+    (@flatten_items [$($field_vis: tt)*] $field_name: ident $public_name: ident, ([$($module: ident)::+] [$inner: ident $($rest: ident)*])) => {
+        $crate::internal::paste!{
+            $crate::internal::doc_comment!{
+                concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($inner), "` field of the layout nested in the `", stringify!($field_name), "` field, at its absolute offset\n"),
+                #[allow(non_camel_case_types)]
+                $($field_vis)* type [<$public_name _ $inner>] = $crate::NestedField<[<__ $field_name __path>], $($module)::+ :: [<__ $inner __path>]>;
+            }
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            pub type [<__ $public_name _ $inner __path>] = [<$public_name _ $inner>];
+            // Check that the field holds the nested layout and that `$inner` is one of its fields
+            const _: usize = <[<$public_name _ $inner>] as $crate::Field>::OFFSET;
+        }
+        $crate::binary_layout!(@flatten_items [$($field_vis)*] $field_name $public_name, ([$($module)::+] [$($rest)*]));
+    };
+    // Generate the type describing a checksum given in a `#[checksum(...)]` attribute
+    (@checksum_items [$($field_vis: tt)*] $public_name: ident, $algorithm: ty, $from: ident, $to: ident) => {
+        $crate::internal::paste!{
+            $crate::internal::doc_comment!{
+                concat!("Describes the checksum stored in the `", stringify!($public_name), "` field, covering the fields from `", stringify!($from), "` to `", stringify!($to), "`.\n",
+                        "See [LayoutChecksum](crate::LayoutChecksum) for the functions to compute, verify and seal the checksum."),
+                #[allow(non_camel_case_types)]
+                $($field_vis)* struct [<$public_name _checksum>];
+            }
+            impl $crate::LayoutChecksum for [<$public_name _checksum>] {
+                type Algorithm = $algorithm;
+                type Field = $public_name;
+
+                #[inline]
+                fn covered_range(storage: &[u8]) -> ::core::ops::Range<usize> {
+                    <$from as $crate::Field>::OFFSET..(<$to as $crate::Field>::OFFSET + <$to as $crate::internal::FieldByteLen>::byte_len(storage))
+                }
+            }
+            const _: () = assert!(
+                <$from as $crate::Field>::OFFSET <= <$to as $crate::Field>::OFFSET,
+                concat!("Error: The checksum range of `", stringify!($public_name), "` must start at a field before or at the field it ends at")
+            );
+        }
+    };
+    // Emit the first list of items if the cfg predicate is satisfied and the second list otherwise
+    (@cfg_items $predicate: tt {$($enabled: item)*} {$($disabled: item)*}) => {
+        $(
+            #[cfg $predicate]
+            $enabled
+        )*
+        $(
+            #[cfg(not $predicate)]
+            $disabled
+        )*
+    };
+
+    (@impl_module {$name: ident, $endianness: ident $(, pad_to = $pad_to: expr)? $(, display = $display: literal)?, derive = [$($derive: ident)*]} [$($docs: tt)*] [$($items: tt)*] [$({$field_name: ident [$($field_attrs: tt)*]})*] $offset_accumulator: tt) => {
+        $crate::internal::doc_comment!{
+            concat!{
+                "This module is autogenerated. It defines a layout using the [binary_layout] crate based on the following definition:\n",
+                "```ignore\n",
+                "binary_layout!(", $("#[display(", stringify!($display), ")] ",)? $("#[pad_to(", stringify!($pad_to), ")] ",)? stringify!($name), ", ", stringify!($endianness), ", {", $($docs)* "\n",
+                "});\n",
+                "```\n",
+            },
+            #[allow(dead_code)]
+            pub mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                $($crate::binary_layout!(@check_derive $derive);)*
+
+                $($items)*
+
+                /// Total size of the layout in number of bytes, including padding added by `#[pad_to(...)]`.
+                /// This can be None if the layout ends with an open ended field like a byte slice.
+                pub const SIZE: Option<usize> = $crate::internal::pad_size($crate::internal::paste!($offset_accumulator), {
+                    #[allow(unused_variables)]
+                    let pad_to: Option<usize> = None;
+                    $(let pad_to = Some($pad_to);)?
+                    pad_to
+                });
+
+                $(
+                    /// The range of padding bytes at the end of the layout, added by `#[pad_to(
+                    #[doc = stringify!($pad_to)]
+                    /// )]`. These bytes don't belong to any field.
+                    pub const PADDING: ::core::ops::Range<usize> = {
+                        let _ = $pad_to;
+                        $crate::internal::unwrap_field_size($crate::internal::paste!($offset_accumulator))..$crate::internal::unwrap_field_size(SIZE)
+                    };
+
+                    /// Set the padding bytes at the end of the layout to zero, see [PADDING].
+                    #[inline]
+                    pub fn zero_padding(storage: &mut [u8]) {
+                        storage[PADDING].fill(0);
+                    }
+                )?
+
+                // The number of gaps between fields, e.g. before fields pinned to an offset
+                #[allow(unused_mut, unused_assignments)]
+                const GAP_COUNT: usize = $crate::internal::paste!{{
+                    let mut count = 0;
+                    let mut end = Some(0);
+                    $(
+                        // Fields disabled by a cfg predicate don't have an offset and are skipped
+                        if let Some(offset) = [<__ $field_name __offset>] {
+                            if let Some(start) = end {
+                                if offset > start {
+                                    count += 1;
+                                }
+                            }
+                            end = match (end, [<__ $field_name __end>]) {
+                                // Fields in a union block can end before the previous field
+                                (Some(end), Some(field_end)) if end > field_end => Some(end),
+                                (_, field_end) => field_end,
+                            };
+                        }
+                    )*
+                    count
+                }};
+
+                /// The byte ranges between the fields of the layout that don't belong to any field, as `(start, end)` pairs
+                /// with an exclusive end. Gaps come from fields pinned to an offset with `field @offset: T`.
+                /// The padding added by `#[pad_to(...)]` isn't a gap. Layouts with a `#[deny_gaps]` attribute fail to compile if there are gaps.
+                #[allow(unused_mut, unused_variables, unused_assignments)]
+                pub const GAPS: &[(usize, usize)] = &$crate::internal::paste!{{
+                    let mut gaps = [(0, 0); GAP_COUNT];
+                    let mut count = 0;
+                    let mut end = Some(0);
+                    $(
+                        // Fields disabled by a cfg predicate don't have an offset and are skipped
+                        if let Some(offset) = [<__ $field_name __offset>] {
+                            if let Some(start) = end {
+                                if offset > start {
+                                    gaps[count] = (start, offset);
+                                    count += 1;
+                                }
+                            }
+                            end = match (end, [<__ $field_name __end>]) {
+                                // Fields in a union block can end before the previous field
+                                (Some(end), Some(field_end)) if end > field_end => Some(end),
+                                (_, field_end) => field_end,
+                            };
+                        }
+                    )*
+                    gaps
+                }};
+
+                /// Check that all bytes of the layout that don't belong to a field are zero, i.e. the [GAPS] between fields and the padding
+                /// added by `#[pad_to(...)]`. Strict parsers can use this to reject messages that store data in reserved regions.
+                /// Returns a [LayoutError::ReservedNotZero](crate::LayoutError::ReservedNotZero) with the offset of the first byte that isn't zero otherwise.
+                ///
+                /// # Panics
+                /// This panics if the storage is too short for the layout.
+                #[inline]
+                pub fn check_reserved_zero(storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                    let padding: &[::core::ops::Range<usize>] = &[$({
+                        let _ = $pad_to;
+                        PADDING
+                    })?];
+                    let gaps = GAPS.iter().map(|&(start, end)| start..end);
+                    for range in gaps.chain(padding.iter().cloned()) {
+                        if let Some(index) = storage[range.clone()].iter().position(|&byte| byte != 0) {
+                            return Err($crate::LayoutError::ReservedNotZero { offset: range.start + index });
+                        }
+                    }
+                    Ok(())
+                }
+
+                $crate::binary_layout!(@if_derive TrackedView [$($derive)*] {
+                    // The names of all fields in the order they're declared in, including fields disabled with `#[cfg(...)]`
+                    const FIELD_NAMES: &[&str] = $crate::internal::paste!(&[$([<__ $field_name __name>]),*]);
+                });
+
+                // The number of bytes taken up by all statically sized fields
+                const MIN_SIZE: usize = $crate::internal::paste!{{
+                    #[allow(unused_mut)]
+                    let mut size = 0;
+                    $(
+                        if [<__ $field_name __min_end>] > size {
+                            size = [<__ $field_name __min_end>];
+                        }
+                    )*
+                    match SIZE {
+                        // Includes the padding added by #[pad_to(...)]
+                        Some(padded) if padded > size => padded,
+                        _ => size,
+                    }
+                }};
+
+                // The offset of the dynamically sized last field, if there is one
+                const DYNAMIC_OFFSET: usize = $crate::internal::paste!{{
+                    #[allow(unused_mut)]
+                    let mut offset = 0;
+                    $(
+                        if [<__ $field_name __dynamic_offset>] > offset {
+                            offset = [<__ $field_name __dynamic_offset>];
+                        }
+                    )*
+                    offset
+                }};
+
+                $crate::binary_layout!(@if_derive VersionedView [$($derive)*] {
+                    // The latest version of a field declared with `#[since(...)]`, or 0 if there are none
+                    const LATEST_VERSION: u32 = $crate::internal::paste!{{
+                        #[allow(unused_mut)]
+                        let mut latest = 0;
+                        $(
+                            assert!(
+                                [<__ $field_name __since>] >= latest,
+                                "Error: Fields with a #[since(...)] attribute must be declared in the order of their versions"
+                            );
+                            latest = [<__ $field_name __since>];
+                        )*
+                        latest
+                    }};
+                    const _: u32 = LATEST_VERSION;
+
+                    // The number of bytes taken up by the statically sized fields that exist in the given version
+                    #[inline]
+                    fn version_min_size(version: u32) -> usize {
+                        if version >= LATEST_VERSION {
+                            return MIN_SIZE;
+                        }
+                        #[allow(unused_mut)]
+                        let mut size = 0;
+                        $crate::internal::paste!{
+                            $(
+                                if [<__ $field_name __since>] <= version {
+                                    size = usize::max(size, [<__ $field_name __min_end>]);
+                                }
+                            )*
+                        }
+                        size
+                    }
+
+                    /// Return the number of bytes of the storage that are used by the fields that exist in the given `version` of the layout,
+                    /// see [new_version]. For the latest version, this is the same as [byte_len].
+                    #[inline]
+                    pub fn version_byte_len(storage: &[u8], version: u32) -> usize {
+                        if version >= LATEST_VERSION {
+                            return byte_len(storage);
+                        }
+                        #[allow(unused_mut)]
+                        let mut len = 0;
+                        $crate::internal::paste!{
+                            $(
+                                if [<__ $field_name __since>] <= version {
+                                    len = usize::max(len, [<__ $field_name __byte_len>](storage));
+                                }
+                            )*
+                        }
+                        len
+                    }
+
+                    // Check the storage length and the contents of the fields that exist in the given version
+                    #[inline]
+                    fn version_validate(storage: &[u8], version: u32) -> Result<(), $crate::LayoutError> {
+                        let actual = storage.len();
+                        let min_size = version_min_size(version);
+                        if actual < min_size {
+                            return Err($crate::LayoutError::StorageTooShort { expected: min_size, actual });
+                        }
+                        let expected = version_byte_len(storage, version);
+                        if actual < expected {
+                            return Err($crate::LayoutError::StorageTooShort { expected, actual });
+                        }
+                        $crate::internal::paste!{
+                            $(
+                                if [<__ $field_name __since>] <= version {
+                                    [<__ $field_name __validate>](storage)?;
+                                }
+                            )*
+                        }
+                        Ok(())
+                    }
+                });
+
+                /// Return the number of bytes a storage needs to hold this layout if its dynamically sized last field,
+                /// e.g. an open ended byte array, is `tail_len` bytes long. For layouts with a static size, this returns
+                /// [SIZE] and ignores `tail_len`.
+                #[inline]
+                pub const fn required_len(tail_len: usize) -> usize {
+                    match SIZE {
+                        Some(size) => size,
+                        None => {
+                            let len = DYNAMIC_OFFSET + tail_len;
+                            if len > MIN_SIZE {
+                                len
+                            } else {
+                                MIN_SIZE
+                            }
+                        }
+                    }
+                }
+
+                /// Return the number of bytes of the storage that are used by the layout.
+                /// For layouts with a static size, this is always the same as [SIZE].
+                /// For layouts ending in a field with a dynamic size, this is the offset of that
+                /// field plus the length it currently has in the storage, e.g. the remaining length of the
+                /// storage for an open ended byte array or the computed length for a `[u8; expr(...)]` field.
+                #[inline]
+                pub fn byte_len(storage: &[u8]) -> usize {
+                    if let Some(size) = SIZE {
+                        return size;
+                    }
+                    #[allow(unused_mut)]
+                    let mut len = 0;
+                    $crate::internal::paste!{
+                        $(
+                            len = usize::max(len, [<__ $field_name __byte_len>](storage));
+                        )*
+                    }
+                    len
+                }
+
+                /// Set all fields with a `#[default(...)]` attribute to their default value and all other bytes used by the layout to zero.
+                /// For layouts with a dynamic size, the number of bytes used is taken from the storage before resetting it, see [byte_len].
+                /// Bytes after the end of the layout are left unchanged.
+                #[inline]
+                pub fn reset_to_defaults(storage: &mut [u8]) {
+                    let len = byte_len(storage);
+                    storage[..len].fill(0);
+                    $crate::binary_layout!(@call_fields reset(storage) [] [$({$field_name [$($field_attrs)*]})*]);
+                }
+
+                /// Compare the bytes used by the layout in `storage` against `template`, only looking at the bits that are set in `mask`,
+                /// i.e. return whether `storage[i] & mask[i] == template[i] & mask[i]` for each byte `i` up to [byte_len].
+                /// This is useful to classify messages by the values of some of their fields while ignoring the others.
+                /// Bytes after the end of `template` or `mask` aren't compared, so a template can cover only the beginning of the layout.
+                #[inline]
+                pub fn matches(storage: &[u8], template: &[u8], mask: &[u8]) -> bool {
+                    storage[..byte_len(storage)]
+                        .iter()
+                        .zip(template)
+                        .zip(mask)
+                        .all(|((byte, template), mask)| byte & mask == template & mask)
+                }
+
+                /// Check that all fields with a `#[magic(...)]` attribute hold their magic number, e.g. to recognize a file format before parsing it.
+                /// Returns a [LayoutError::BadMagic](crate::LayoutError::BadMagic) with the offset of the first field that doesn't otherwise.
+                /// [try_new] and the other functions creating views that validate the storage run this check as well.
+                ///
+                /// # Panics
+                /// This panics if the storage is too short for the layout.
+                #[inline]
+                #[allow(unused_variables)]
+                pub fn check_magic(storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                    $crate::binary_layout!(@call_fields check_magic(storage) [?] [$({$field_name [$($field_attrs)*]})*]);
+                    Ok(())
+                }
+
+                // Check the contents of fields that can be invalid, e.g. `Utf8<N>` fields, and the magic numbers of fields declared with `#[magic(...)]`
+                #[inline]
+                fn validate(storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                    $crate::internal::paste!{
+                        $(
+                            [<__ $field_name __validate>](storage)?;
+                        )*
+                    }
+                    Ok(())
+                }
+
+                $crate::binary_layout!(@if_derive DebugFields [$($derive)*] {
+                    $crate::internal::if_std!{
+                        /// Read all fields of this layout that can be read as a whole, e.g. integer fields, and return
+                        /// their names and values in the order they're declared in. Fields that fail to read are returned
+                        /// with their error. Other fields, e.g. slices and nested layouts, are skipped.
+                        /// This is mostly useful for printing or comparing whole messages in debugging tools.
+                        pub fn debug_fields(storage: &[u8]) -> $crate::internal::Vec<(&'static str, $crate::internal::Box<dyn ::core::fmt::Debug>)> {
+                            #[allow(unused_mut)]
+                            let mut fields = $crate::internal::Vec::new();
+                            $crate::internal::paste!{
+                                $(
+                                    if let Some(value) = $crate::binary_layout!(@probe [&&] <[<__ $field_name __path>] as $crate::internal::FieldPath>::Field, [DebugCopyField, DebugOtherField], debug_value(storage)) {
+                                        fields.push(([<__ $field_name __name>], value));
+                                    }
+                                )*
+                            }
+                            fields
+                        }
+                    }
+                });
+
+                $crate::internal::if_std!{
+                    /// Return the name and the byte range of each field of this layout in the given storage, in the order they're declared in.
+                    /// Unlike [Field::OFFSET](crate::Field::OFFSET) and [Field::SIZE](crate::Field::SIZE), this resolves the length of
+                    /// fields with a dynamic size, e.g. open ended byte arrays or `[u8; expr(...)]` fields, for this storage.
+                    /// This is mostly useful for tools that display or dissect messages.
+                    pub fn field_layout(storage: &[u8]) -> $crate::internal::Vec<(&'static str, ::core::ops::Range<usize>)> {
+                        #[allow(unused_mut)]
+                        let mut ranges = $crate::internal::Vec::new();
+                        $crate::internal::paste!{
+                            $(
+                                [<__ $field_name __range>](storage, &mut ranges);
+                            )*
+                        }
+                        ranges
+                    }
+                }
+
+                $crate::binary_layout!(@if_derive Diff [$($derive)*] {
+                    $crate::internal::if_std!{
+                        /// Compare two storages of this layout field by field and return the fields that are stored differently,
+                        /// in the order they're declared in. Fields that can be read as a whole, e.g. integer fields, are returned
+                        /// with both values, slice fields with the range of bytes that differ. Nested layouts are skipped.
+                        /// This is mostly useful for comparing captured messages in tests.
+                        pub fn diff(a: &[u8], b: &[u8]) -> $crate::internal::Vec<$crate::FieldDiff> {
+                            #[allow(unused_mut)]
+                            let mut diffs = $crate::internal::Vec::new();
+                            $crate::internal::paste!{
+                                $(
+                                    if let Some(diff) = $crate::binary_layout!(@probe [&&&] <[<__ $field_name __path>] as $crate::internal::FieldPath>::Field, [DiffCopyField, DiffSliceField, DiffOtherField], diff([<__ $field_name __name>], a, b)) {
+                                        diffs.push(diff);
+                                    }
+                                )*
+                            }
+                            diffs
+                        }
+                    }
+                });
+
+                $crate::internal::if_std!{
+                    /// Return the bytes used by the layout in `storage` as a lowercase hex string without separators, e.g. `"0a01ff"`,
+                    /// for single line logs. Only the first [byte_len] bytes are included, not any trailing bytes of the storage.
+                    #[inline]
+                    pub fn to_hex(storage: &[u8]) -> $crate::internal::String {
+                        $crate::internal::hex_string(&storage[..byte_len(storage)], false)
+                    }
+
+                    /// Return the bytes used by the layout in `storage` as an uppercase hex string without separators, e.g. `"0A01FF"`, see [to_hex].
+                    #[inline]
+                    pub fn to_hex_upper(storage: &[u8]) -> $crate::internal::String {
+                        $crate::internal::hex_string(&storage[..byte_len(storage)], true)
+                    }
+
+                    /// Write the bytes used by the layout in `storage` to `writer` in pieces of at most `chunk` bytes, flushing the writer
+                    /// after each piece, e.g. for a transport with a maximum segment size. The last piece holds the remaining bytes and can be
+                    /// shorter than `chunk`. Only the first [byte_len] bytes are written. Returns the number of bytes written.
+                    ///
+                    /// # Panics
+                    /// This panics if `chunk` is zero.
+                    #[inline]
+                    pub fn write_to_chunked<W: $crate::internal::io::Write>(storage: &[u8], writer: W, chunk: usize) -> $crate::internal::io::Result<usize> {
+                        $crate::internal::write_chunked(&storage[..byte_len(storage)], writer, chunk)
+                    }
+                }
+
+                $crate::binary_layout!(@if_derive FromStr [$($derive)*] {
+                    $crate::internal::if_std!{
+                        /// Create an owned [View] from a text like `"kind=3, length=12"` with comma separated `name=value` pairs, e.g. for test fixtures
+                        /// or command line tools. The named fields are parsed with [FromStr](core::str::FromStr) into their type and all other bytes are zero.
+                        /// The storage has the minimum size of the layout, so open ended byte arrays are empty.
+                        /// Fields whose type doesn't implement [FromStr](core::str::FromStr), e.g. byte arrays and nested layouts, can't be given in the text.
+                        /// The [View] also implements [FromStr](core::str::FromStr) with this function.
+                        pub fn from_fields_str(fields: &str) -> Result<View<$crate::internal::Vec<u8>>, $crate::ParseFieldsError> {
+                            let mut storage = $crate::internal::Vec::new();
+                            storage.resize(MIN_SIZE, 0);
+                            $crate::internal::parse_fields(fields, |name, value| {
+                                #[allow(unused_mut)]
+                                let mut result = None;
+                                $crate::internal::paste!{
+                                    $(
+                                        if result.is_none() {
+                                            result = [<__ $field_name __parse>](&mut storage, name, value);
+                                        }
+                                    )*
+                                }
+                                result
+                            })?;
+                            Ok(View::new(storage))
+                        }
+
+                        impl ::core::str::FromStr for View<$crate::internal::Vec<u8>> {
+                            type Err = $crate::ParseFieldsError;
+
+                            /// Parse the fields of a message from a text with comma separated `name=value` pairs, see [from_fields_str].
+                            #[inline]
+                            fn from_str(fields: &str) -> Result<Self, $crate::ParseFieldsError> {
+                                from_fields_str(fields)
+                            }
+                        }
+                    }
+                });
+
+                $crate::binary_layout!(@if_derive Translate [$($derive)*] {
+                    $crate::internal::if_std!{
+                        impl <S: AsRef<[u8]>> $crate::TranslateSource for View<S> {
+                            fn field_values(&self) -> $crate::internal::Vec<(&'static str, $crate::internal::Box<dyn ::core::any::Any>)> {
+                                let storage = self.storage.as_ref();
+                                #[allow(unused_mut)]
+                                let mut values = $crate::internal::Vec::new();
+                                $crate::internal::paste!{
+                                    $(
+                                        [<__ $field_name __translate_value>](storage, &mut values);
+                                    )*
+                                }
+                                values
+                            }
+                        }
+
+                        impl <S: AsRef<[u8]> + AsMut<[u8]>> $crate::TranslateTarget for View<S> {
+                            fn write_field_value(&mut self, name: &str, value: &dyn ::core::any::Any) -> bool {
+                                #[allow(unused_variables)]
+                                let storage = self.storage.as_mut();
+                                $crate::internal::paste!{
+                                    $(
+                                        if let Some(written) = [<__ $field_name __translate_write>](storage, name, value) {
+                                            return written;
+                                        }
+                                    )*
+                                }
+                                false
+                            }
+                        }
+                    }
+                });
+
+                $crate::binary_layout!(@if_derive Arbitrary [$($derive)*] {
+                    $crate::internal::if_arbitrary!{
+                        impl <'a> $crate::internal::arbitrary::Arbitrary<'a> for View<$crate::internal::Vec<u8>> {
+                            /// Create an owned view over an arbitrary message of this layout, e.g. for fuzzing or property tests.
+                            /// Fields whose type implements `Arbitrary` hold an arbitrary value of that type,
+                            /// all other bytes, e.g. byte arrays, are arbitrary.
+                            fn arbitrary(u: &mut $crate::internal::arbitrary::Unstructured<'a>) -> $crate::internal::arbitrary::Result<Self> {
+                                let storage = $crate::internal::arbitrary_storage(u, MIN_SIZE, SIZE.is_none(), byte_len, |storage, u| {
+                                    $crate::internal::paste!{
+                                        $(
+                                            $crate::binary_layout!(@probe [&&] <[<__ $field_name __path>] as $crate::internal::FieldPath>::Field, [ArbitraryCopyField, ArbitraryOtherField], fill_arbitrary(storage, u))?;
+                                        )*
+                                    }
+                                    Ok(())
+                                })?;
+                                Ok(View::new(storage))
+                            }
+                        }
+                    }
+                });
+
+                $crate::binary_layout!(@if_derive Fields [$($derive)*] {
+                    $crate::internal::if_std!{
+                        $crate::internal::paste!{
+                            /// The values of all fields of this layout, returned by [read_fields]. Each field is decoded into a value
+                            /// that doesn't borrow the storage, so you can destructure it and keep working with plain values:
+                            /// - Integer and float fields hold their value.
+                            /// - Other fields that are read by copying, e.g. `NonZeroU32` fields or custom types declared with `as`, hold the result of reading them.
+                            /// - Byte arrays hold a copy of their bytes in a `Vec<u8>`, `Utf8<N>` fields hold the result of converting them into a `String`,
+                            ///   packed arrays and arrays of custom types hold a `Vec` of their elements.
+                            /// - Nested layouts hold the `Fields` struct of the nested layout.
+                            ///
+                            /// Fields that are disabled with `#[cfg(...)]` hold `()`.
+                            pub struct Fields {
+                                $(
+                                    #[doc = concat!("The value of the `", stringify!($field_name), "` field")]
+                                    pub $field_name: [<__ $field_name __value>],
+                                )*
+                            }
+
+                            /// Decode all fields of the message in `storage` into a [Fields] struct holding plain values, e.g. to parse a message once
+                            /// and then work with the values or destructure them with a pattern. See [Fields] for the types the fields are decoded into.
+                            /// Byte arrays are copied out of the storage. To take a single byte array out of an owned storage without copying it,
+                            /// use `View::into_${field_name}` and [FieldView::extract](crate::FieldView::extract) instead.
+                            #[inline]
+                            pub fn read_fields(storage: &[u8]) -> Fields {
+                                Fields {
+                                    $(
+                                        $field_name: [<__ $field_name __into_value>](storage),
+                                    )*
+                                }
+                            }
+                        }
+                    }
+                });
+
+                $crate::internal::doc_comment!{
+                    concat!{
+                        "The [View] struct defines the [FieldView](crate::FieldView) API.\n",
+                        "An instance of [View] wraps a storage (either borrowed or owned)\n",
+                        "and allows accessors for the layout fields.\n",
+                        "\n",
+                        "This view is based on the following layout definition:\n",
+                        "```ignore\n",
+                        "binary_layout!(", $("#[display(", stringify!($display), ")] ",)? $("#[pad_to(", stringify!($pad_to), ")] ",)? stringify!($name), ", ", stringify!($endianness), ", {", $($docs)* "\n",
+                        "});\n",
+                        "```\n",
+                    },
+                    pub struct View<S: AsRef<[u8]>> {
+                        storage: S,
+                    }
+                }
+                impl <S: AsRef<[u8]>> View<S> {
+                    /// You can create views over a storage by calling [View::new].
+                    ///
+                    /// `S` is the type of underlying storage. It can be
+                    /// - Immutable borrowed storage: `&[u8]`
+                    /// - Mutable borrowed storage: `&mut [u8]`
+                    /// - Owning storage: impl `AsRef<u8>` (for example: `Vec<u8>`)
+                    #[inline]
+                    pub fn new(storage: S) -> Self {
+                        Self {storage}
+                    }
+
+                    /// This destroys the view and returns the underlying storage back to you.
+                    /// This is useful if you created an owning view (e.g. based on `Vec<u8>`)
+                    /// and now need the underlying `Vec<u8>` back.
+                    #[inline]
+                    pub fn into_storage(self) -> S {
+                        self.storage
+                    }
+                }
+
+                /// Create a view over a storage like [View::new], but check that the storage is long enough
+                /// for the layout. For layouts with a dynamic size, this means it is long enough for the current
+                /// length of the dynamically sized field, see [byte_len].
+                /// It also checks that `Utf8<N>` fields, including those of nested layouts, contain valid UTF-8.
+                /// If the storage is too short or a field is invalid, this returns a [LayoutError](crate::LayoutError).
+                #[inline]
+                pub fn try_new<S: AsRef<[u8]>>(storage: S) -> Result<View<S>, $crate::LayoutError> {
+                    $crate::internal::check_storage_len(storage.as_ref(), MIN_SIZE, byte_len)?;
+                    validate(storage.as_ref())?;
+                    Ok(View {storage})
+                }
+
+                $crate::binary_layout!(@if_derive VersionedView [$($derive)*] {
+                    /// Create a view over a message of the given `version`, e.g. a message written by an older version of a protocol.
+                    /// Fields declared with a `#[since(...)]` attribute for a newer version are treated as absent: the returned
+                    /// [VersionedView] returns `None` for them, and the storage only needs to be long enough for the fields that exist
+                    /// in `version`. Otherwise, this checks the storage like [try_new] and returns a [LayoutError](crate::LayoutError)
+                    /// if it is too short or a field is invalid.
+                    #[inline]
+                    pub fn new_version<S: AsRef<[u8]>>(storage: S, version: u32) -> Result<VersionedView<S>, $crate::LayoutError> {
+                        version_validate(storage.as_ref(), version)?;
+                        Ok(VersionedView {view: View {storage}, version})
+                    }
+                });
+
+                /// Create a view over a storage like [try_new], but additionally check that the storage isn't longer
+                /// than the layout, i.e. that its length is exactly [byte_len]. This catches framing errors where a
+                /// storage with extra bytes at the end was passed in. For layouts ending with an open ended byte array,
+                /// the byte array takes up the rest of the storage, so this behaves like [try_new].
+                /// If the storage is too long, this returns [LayoutError::StorageTooLong](crate::LayoutError::StorageTooLong).
+                #[inline]
+                pub fn try_new_exact<S: AsRef<[u8]>>(storage: S) -> Result<View<S>, $crate::LayoutError> {
+                    let view = try_new(storage)?;
+                    let expected = byte_len(view.storage.as_ref());
+                    let actual = view.storage.as_ref().len();
+                    if actual > expected {
+                        return Err($crate::LayoutError::StorageTooLong { expected, actual });
+                    }
+                    Ok(view)
+                }
+
+                /// Create a view over a layout that starts at offset `base` within a larger storage, e.g. a record
+                /// at a position read from a table of offsets. All fields are accessed relative to `base`.
+                /// The view holds the whole storage, and `view.into_storage().offset()` returns `base` again,
+                /// see [Data::offset](crate::Data::offset).
+                ///
+                /// Panics if `base` is beyond the end of the storage.
+                #[inline]
+                pub fn at_offset<S: AsRef<[u8]>>(storage: S, base: usize) -> View<$crate::Data<S>> {
+                    let len = storage.as_ref().len();
+                    assert!(base <= len, "Base offset {} is out of bounds for a storage of length {}", base, len);
+                    View {storage: $crate::Data::from(storage).into_subregion(base..)}
+                }
+
+                /// Create a view over a layout stored in the region of `len` bytes starting at `offset` within a larger storage,
+                /// e.g. a sub-structure whose offset and length are read from a directory at the start of a container file.
+                /// All fields are accessed relative to `offset` and the view can't access bytes after the end of the region.
+                ///
+                /// Returns a [LayoutError::RegionOutOfBounds](crate::LayoutError::RegionOutOfBounds) if the region reaches beyond
+                /// the end of the storage. Otherwise, this checks the region like [try_new] and returns a
+                /// [LayoutError](crate::LayoutError) if it is too short for the layout or a field is invalid.
+                #[inline]
+                pub fn view_at<S: AsRef<[u8]>>(storage: S, offset: usize, len: usize) -> Result<View<$crate::Data<S>>, $crate::LayoutError> {
+                    let actual = storage.as_ref().len();
+                    match offset.checked_add(len) {
+                        Some(end) if end <= actual => try_new($crate::Data::from(storage).into_subregion(offset..end)),
+                        _ => Err($crate::LayoutError::RegionOutOfBounds { offset, len, actual }),
+                    }
+                }
+
+                $crate::binary_layout!(@if_derive ReadView [$($derive)*] {
+                    /// Implemented by all [View]s of this layout, so that generic code can take any view with read access,
+                    /// e.g. `fn inspect(view: &impl ReadView)` accepts both a `View<&[u8]>` and a `View<&mut [u8]>`.
+                    /// The field accessors are available through [ReadView::as_view].
+                    pub trait ReadView {
+                        /// Return a [View] with read access to the storage of this view.
+                        fn as_view(&self) -> View<&[u8]>;
+                    }
+
+                    /// Implemented by all [View]s of this layout that have write access to their storage, e.g. a `View<&mut [u8]>`
+                    /// or a `View<Vec<u8>>`, so that generic code can take any view it can write to.
+                    /// The field accessors are available through [WriteView::as_view_mut].
+                    pub trait WriteView: ReadView {
+                        /// Return a [View] with write access to the storage of this view.
+                        fn as_view_mut(&mut self) -> View<&mut [u8]>;
+                    }
+
+                    impl <S: AsRef<[u8]>> ReadView for View<S> {
+                        #[inline]
+                        fn as_view(&self) -> View<&[u8]> {
+                            View {storage: self.storage.as_ref()}
+                        }
+                    }
+
+                    impl <S: AsRef<[u8]> + AsMut<[u8]>> WriteView for View<S> {
+                        #[inline]
+                        fn as_view_mut(&mut self) -> View<&mut [u8]> {
+                            View {storage: self.storage.as_mut()}
+                        }
+                    }
+
+                    $crate::binary_layout!(@if_derive TrackedView [$($derive)*] {
+                        impl <S: AsRef<[u8]>> ReadView for TrackedView<S> {
+                            #[inline]
+                            fn as_view(&self) -> View<&[u8]> {
+                                self.view.as_view()
+                            }
+                        }
+                    });
+                });
+
+                $crate::binary_layout!(@if_derive TrackedView [$($derive)*] {
+                    /// A [TrackedView] wraps a [View] and records which fields were written through it, e.g. to send only the
+                    /// fields that changed since the last sync. Fields are marked as dirty when their `${field_name}_mut()` accessor
+                    /// is called, regardless of whether the returned [FieldView](crate::FieldView) is written to.
+                    /// Read access is available through [TrackedView::view] and [ReadView::as_view]. Writes that don't go through
+                    /// the [TrackedView], e.g. through the [Field](crate::Field) API on the storage, aren't tracked.
+                    pub struct TrackedView<S: AsRef<[u8]>> {
+                        view: View<S>,
+                        dirty: [bool; FIELD_NAMES.len()],
+                    }
+
+                    impl <S: AsRef<[u8]>> TrackedView<S> {
+                        /// Create a [TrackedView] over a storage, with no dirty fields.
+                        #[inline]
+                        pub fn new(storage: S) -> Self {
+                            Self {view: View::new(storage), dirty: [false; FIELD_NAMES.len()]}
+                        }
+
+                        /// Return the wrapped [View] with read access to the fields.
+                        #[inline]
+                        pub fn view(&self) -> &View<S> {
+                            &self.view
+                        }
+
+                        /// This destroys the [TrackedView] and returns the wrapped [View].
+                        #[inline]
+                        pub fn into_view(self) -> View<S> {
+                            self.view
+                        }
+
+                        /// Return the names of the fields that were written since the [TrackedView] was created or [TrackedView::clear_dirty]
+                        /// was called, in the order they're declared in.
+                        #[inline]
+                        pub fn dirty_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+                            FIELD_NAMES
+                                .iter()
+                                .zip(self.dirty.iter())
+                                .filter(|(_, dirty)| **dirty)
+                                .map(|(name, _)| *name)
+                        }
+
+                        /// Return whether any field was written since the [TrackedView] was created or [TrackedView::clear_dirty] was called.
+                        #[inline]
+                        pub fn is_dirty(&self) -> bool {
+                            self.dirty.contains(&true)
+                        }
+
+                        /// Mark all fields as clean, e.g. after the dirty fields were synced.
+                        #[inline]
+                        pub fn clear_dirty(&mut self) {
+                            self.dirty = [false; FIELD_NAMES.len()];
+                        }
+                    }
+                });
+
+                $crate::binary_layout!(@if_derive VersionedView [$($derive)*] {
+                    /// A [VersionedView] is a view over a message of an older version of this layout, created by [new_version].
+                    /// Fields declared with a `#[since(...)]` attribute for a newer version don't exist in the message, so their
+                    /// accessors return `None`. All other fields are accessed like on a [View].
+                    pub struct VersionedView<S: AsRef<[u8]>> {
+                        view: View<S>,
+                        version: u32,
+                    }
+
+                    impl <S: AsRef<[u8]>> From<VersionedView<S>> for View<S> {
+                        /// This destroys the [VersionedView] and returns a [View] with access to all fields, regardless of the version.
+                        #[inline]
+                        fn from(versioned: VersionedView<S>) -> Self {
+                            versioned.view
+                        }
+                    }
+                });
+
+                $crate::internal::if_std!{
+                    /// Create an owning view over a `Vec<u8>` that holds a message followed by trailing bytes, e.g. because
+                    /// it was read in larger chunks. This checks the vec like [try_new] and then truncates it to
+                    /// [byte_len], so [View::into_storage] returns only the bytes used by the layout.
+                    /// Truncating doesn't reallocate, the vec keeps its capacity. To keep the trailing bytes instead,
+                    /// use [try_new] and get the number of used bytes from [byte_len].
+                    /// For layouts ending with an open ended byte array, the byte array takes up the rest of the vec and nothing is truncated.
+                    #[inline]
+                    pub fn from_vec_truncating(mut storage: $crate::internal::Vec<u8>) -> Result<View<$crate::internal::Vec<u8>>, $crate::LayoutError> {
+                        $crate::internal::check_storage_len(&storage, MIN_SIZE, byte_len)?;
+                        validate(&storage)?;
+                        storage.truncate(byte_len(&storage));
+                        Ok(View {storage})
+                    }
+
+                    /// Read a complete message from a [Read](std::io::Read), e.g. a TCP stream, into a new owning view. This first reads
+                    /// the `MIN_SIZE` bytes of the statically sized fields, then computes the length of the message from them like
+                    /// [byte_len] and reads the rest. The field holding the length is the one the `expr(...)` of a last
+                    /// `[u8; expr(...)]` field reads, see [byte arrays with computed length](crate#byte-arrays-with-computed-length-u8-expr).
+                    /// For statically sized layouts and layouts ending in an open ended byte array, only the statically sized fields are read.
+                    /// Bytes after the message aren't read. The tail is read in pieces, so a corrupt length field doesn't allocate more
+                    /// memory than the reader actually delivers.
+                    /// Returns an error if reading fails, the reader ends before the message is complete, or a field is invalid.
+                    #[inline]
+                    pub fn read_framed<R: $crate::internal::io::Read>(reader: &mut R) -> Result<View<$crate::internal::Vec<u8>>, $crate::ReadFromError<$crate::internal::io::Error>> {
+                        let storage = $crate::internal::read_framed_record(reader, MIN_SIZE, byte_len)?;
+                        validate(&storage)?;
+                        Ok(View {storage})
+                    }
+
+                    /// Append `bytes` to the dynamically sized last field of the message in `storage`, e.g. an open ended byte array
+                    /// or a `[u8; expr(...)]` field, growing the vec. Bytes after the end of the message, i.e. after [byte_len], are
+                    /// dropped first. Fields declared with a `#[tail_len]` attribute are set to the new length of the last field in bytes,
+                    /// so the `expr(...)` of the last field has to return the value of the `#[tail_len]` field unchanged, e.g.
+                    /// `expr(|v| v.len().read() as usize)`. Lengths counted in other units, e.g. in elements of several bytes, aren't supported.
+                    /// The statically sized fields keep their offsets and values.
+                    ///
+                    /// Returns a [LayoutError::StorageTooShort](crate::LayoutError::StorageTooShort) if the vec doesn't hold a complete
+                    /// message, and a [LayoutError::TailLenOutOfRange](crate::LayoutError::TailLenOutOfRange) if the new length doesn't fit
+                    /// into a `#[tail_len]` field. In both cases, the message is left unchanged.
+                    /// Calling this for a layout without a dynamically sized last field fails to compile.
+                    #[inline]
+                    pub fn push_tail<B: AsRef<[u8]> + ?Sized>(storage: &mut $crate::internal::Vec<u8>, bytes: &B) -> Result<(), $crate::LayoutError> {
+                        #[allow(clippy::let_unit_value)]
+                        let () = $crate::internal::AssertDynamicTail::<NestedView, B>::CHECK;
+                        let bytes = bytes.as_ref();
+                        $crate::internal::check_storage_len(storage, MIN_SIZE, byte_len)?;
+                        let len = byte_len(storage);
+                        #[allow(unused_variables)]
+                        let tail_len = len - DYNAMIC_OFFSET + bytes.len();
+                        $crate::binary_layout!(@call_fields set_tail_len(storage, tail_len) [?] [$({$field_name [$($field_attrs)*]})*]);
+                        storage.truncate(len);
+                        storage.extend_from_slice(bytes);
+                        debug_assert_eq!(
+                            storage.len(),
+                            byte_len(storage),
+                            "Error: The expr(...) of the last field must return the value of the #[tail_len] field as the length in bytes"
+                        );
+                        Ok(())
+                    }
+                }
+
+                impl <'a> ::core::convert::TryFrom<&'a [u8]> for View<&'a [u8]> {
+                    type Error = $crate::LayoutError;
+
+                    /// Create a view with read access to the storage, see [try_new].
+                    #[inline]
+                    fn try_from(storage: &'a [u8]) -> Result<Self, $crate::LayoutError> {
+                        try_new(storage)
+                    }
+                }
+
+                impl <'a> ::core::convert::TryFrom<&'a mut [u8]> for View<&'a mut [u8]> {
+                    type Error = $crate::LayoutError;
+
+                    /// Create a view with write access to the storage, see [try_new].
+                    #[inline]
+                    fn try_from(storage: &'a mut [u8]) -> Result<Self, $crate::LayoutError> {
+                        try_new(storage)
+                    }
+                }
+
+                /// Use this as a marker type for using this layout as a nested field within another layout.
+                ///
+                /// # Example
+                /// ```
+                /// use binary_layout::prelude::*;
+                ///
+                /// binary_layout!(icmp_header, BigEndian, {
+                ///   packet_type: u8,
+                ///   code: u8,
                 ///   checksum: u16,
                 ///   rest_of_header: [u8; 4],
                 /// });
@@ -157,214 +2547,1746 @@ macro_rules! binary_layout {
                 /// # fn main() {}
                 /// ```
                 pub struct NestedView;
+
+                /// Iterate over records of this layout that are stored back to back in the given storage,
+                /// see `binary_layout::Records`.
+                #[inline]
+                pub fn iter_records(storage: &[u8]) -> $crate::Records<'_, NestedView> {
+                    $crate::Records::new(storage)
+                }
+
+                /// The distance in bytes between the starts of two consecutive records of this layout stored back to back,
+                /// i.e. the same as [SIZE]. This is `None` for layouts with a dynamic size since their records don't have
+                /// a fixed stride, use [iter_records] to iterate over those.
+                pub const STRIDE: Option<usize> = SIZE;
+
+                /// Return the absolute offset of the field `F` in the `index`-th record of this layout, for records that are stored
+                /// back to back starting at offset zero, i.e. `index * STRIDE + F::OFFSET`. This is useful to access records by index
+                /// without computing offsets by hand, e.g. `offset_in_record::<my_layout::value>(3)`.
+                /// `F` should be a field of this layout. This fails to compile for layouts with a dynamic size.
+                #[inline]
+                pub fn offset_in_record<F: $crate::Field>(index: usize) -> usize {
+                    index * $crate::internal::RecordStride::<NestedView, F>::STRIDE + F::OFFSET
+                }
+
+                /// Read a record of this layout byte by byte from `reader` into the beginning of `storage`, e.g. a stack buffer,
+                /// and return a [View] over it. For layouts with a dynamic size, the statically sized fields are read first
+                /// to find out how many more bytes belong to the record. Bytes after the record aren't read.
+                /// Like [try_new], this returns an error if `storage` is too short or a field is invalid, see `binary_layout::ByteReader`.
+                #[inline]
+                pub fn read_from<S: AsRef<[u8]> + AsMut<[u8]>, R: $crate::ByteReader>(reader: &mut R, mut storage: S) -> Result<View<S>, $crate::ReadFromError<R::Error>> {
+                    $crate::internal::read_record(reader, storage.as_mut(), MIN_SIZE, byte_len)?;
+                    validate(storage.as_ref())?;
+                    Ok(View::new(storage))
+                }
+
+                $crate::internal::if_std!{
+                    /// Append a record of this layout to the end of `buf`, filled with zeroes, and return a [View] over the appended bytes.
+                    /// The record takes up [required_len]`(0)` bytes, i.e. for layouts with a dynamic size, the dynamically sized last field is empty.
+                    #[inline]
+                    pub fn push_record(buf: &mut $crate::internal::Vec<u8>) -> View<&mut [u8]> {
+                        let start = buf.len();
+                        buf.resize(start + required_len(0), 0);
+                        View::new(&mut buf[start..])
+                    }
+                }
                 impl <S: AsRef<[u8]>> $crate::internal::OwningNestedView<$crate::Data<S>> for NestedView where S: AsRef<[u8]> {
                     type View = View<$crate::Data<S>>;
 
-                    #[inline(always)]
-                    fn into_view(storage: $crate::Data<S>) -> Self::View {
-                        Self::View {storage}
-                    }
-                }
-                impl <S: AsRef<[u8]>> $crate::internal::BorrowingNestedView<S> for NestedView {
-                    type View = View<S>;
+                    #[inline(always)]
+                    fn into_view(storage: $crate::Data<S>) -> Self::View {
+                        Self::View {storage}
+                    }
+                }
+                impl <S: AsRef<[u8]>> $crate::internal::BorrowingNestedView<S> for NestedView {
+                    type View = View<S>;
+
+                    #[inline(always)]
+                    fn view(storage: S) -> Self::View {
+                        Self::View {storage: storage.into()}
+                    }
+                }
+
+                $crate::binary_layout!(@if_derive Fields [$($derive)*] {
+                    $crate::internal::if_std!{
+                        impl $crate::internal::NestedFields for NestedView {
+                            type Fields = Fields;
+
+                            #[inline(always)]
+                            fn fields(storage: &[u8]) -> Fields {
+                                read_fields(storage)
+                            }
+                        }
+                    }
+                });
+
+                impl $crate::internal::NestedViewInfo for NestedView {
+                    const SIZE: Option<usize> = SIZE;
+                    const MIN_SIZE: usize = MIN_SIZE;
+
+                    #[inline(always)]
+                    fn byte_len(storage: &[u8]) -> usize {
+                        byte_len(storage)
+                    }
+
+                    #[inline(always)]
+                    fn validate(storage: &[u8]) -> Result<(), $crate::LayoutError> {
+                        validate(storage)
+                    }
+                }
+
+                $crate::binary_layout!(@impl_display [$($display)?] [$($field_name)*]);
+            }
+        }
+    };
+
+    // The @impl_display rules implement Display for the View of layouts with a `#[display(...)]` attribute.
+    // Each field is bound to a local variable with the name it is declared with, so the format string can
+    // capture it by name.
+    (@impl_display [] [$($field_name: ident)*]) => {};
+    (@impl_display [$display: literal] [$($field_name: ident)*]) => {
+        impl<S: AsRef<[u8]>> ::core::fmt::Display for View<S> {
+            #[allow(non_snake_case, unused_variables)]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let storage = self.storage.as_ref();
+                $crate::internal::paste!{
+                    $(
+                        let $field_name = $crate::internal::DisplayField::<<[<__ $field_name __path>] as $crate::internal::FieldPath>::Field>::new(storage);
+                    )*
+                }
+                ::core::write!(f, $display)
+            }
+        }
+    };
+
+    // The @call_fields rules call the `__<field>__<item>` function of each field with the attribute the function is generated for,
+    // see @field_items, i.e. `reset` for fields with a `#[default(...)]` or `#[magic(...)]` attribute, `check_magic` for fields
+    // with a `#[magic(...)]` attribute and `set_tail_len` for fields with a `#[tail_len]` attribute. Each call is guarded by the
+    // cfg predicates of its field. `[?]` propagates errors returned by the calls.
+    (@call_fields $item: ident $args: tt $try: tt [$($field: tt)*]) => {
+        $(
+            $crate::binary_layout!(@call_field $item $args $try $field [] []);
+        )*
+    };
+    (@call_field $item: ident $args: tt $try: tt {$field_name: ident [#[cfg($($cfg: tt)*)] $($attrs: tt)*]} [$($cfgs: tt)*] $called: tt) => {
+        $crate::binary_layout!(@call_field $item $args $try {$field_name [$($attrs)*]} [$($cfgs)* ($($cfg)*)] $called);
+    };
+    (@call_field reset $args: tt $try: tt {$field_name: ident [#[default($($value: tt)*)] $($attrs: tt)*]} $cfgs: tt $called: tt) => {
+        $crate::binary_layout!(@call_field reset $args $try {$field_name [$($attrs)*]} $cfgs [called]);
+    };
+    (@call_field reset $args: tt $try: tt {$field_name: ident [#[magic($($value: tt)*)] $($attrs: tt)*]} $cfgs: tt $called: tt) => {
+        $crate::binary_layout!(@call_field reset $args $try {$field_name [$($attrs)*]} $cfgs [called]);
+    };
+    (@call_field check_magic $args: tt $try: tt {$field_name: ident [#[magic($($value: tt)*)] $($attrs: tt)*]} $cfgs: tt $called: tt) => {
+        $crate::binary_layout!(@call_field check_magic $args $try {$field_name [$($attrs)*]} $cfgs [called]);
+    };
+    (@call_field set_tail_len $args: tt $try: tt {$field_name: ident [#[tail_len] $($attrs: tt)*]} $cfgs: tt $called: tt) => {
+        $crate::binary_layout!(@call_field set_tail_len $args $try {$field_name [$($attrs)*]} $cfgs [called]);
+    };
+    (@call_field $item: ident $args: tt $try: tt {$field_name: ident [#[$($attr: tt)*] $($attrs: tt)*]} $cfgs: tt $called: tt) => {
+        $crate::binary_layout!(@call_field $item $args $try {$field_name [$($attrs)*]} $cfgs $called);
+    };
+    (@call_field $item: ident $args: tt $try: tt {$field_name: ident []} $cfgs: tt []) => {};
+    (@call_field $item: ident ($($arg: expr),*) [$($try: tt)?] {$field_name: ident []} [$(($($cfg: tt)*))*] [called]) => {
+        #[cfg(all($($($cfg)*),*))]
+        $crate::internal::paste!{
+            [<__ $field_name __ $item>]($($arg),*)$($try)?;
+        }
+    };
+
+    // The @if_derive rules expand to the first group of tokens if the given name is in the `#[derive(...)]` attribute of the layout,
+    // and to the second group otherwise. The optional items of a layout, e.g. the `TrackedView`, are only generated if they're derived.
+    (@if_derive $wanted: ident $derive: tt {$($yes: tt)*}) => {
+        $crate::binary_layout!(@if_derive $wanted $derive {$($yes)*} {});
+    };
+    (@if_derive $wanted: ident [] $yes: tt {$($no: tt)*}) => {
+        $($no)*
+    };
+    (@if_derive Fields [Fields $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive ReadView [ReadView $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive TrackedView [TrackedView $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive VersionedView [VersionedView $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive DebugFields [DebugFields $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive Diff [Diff $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive FromStr [FromStr $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive Translate [Translate $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive Arbitrary [Arbitrary $($derive: ident)*] {$($yes: tt)*} $no: tt) => {
+        $($yes)*
+    };
+    (@if_derive $wanted: ident [$other: ident $($derive: ident)*] $yes: tt $no: tt) => {
+        $crate::binary_layout!(@if_derive $wanted [$($derive)*] $yes $no);
+    };
+    // The @check_derive rules fail to compile for names in `#[derive(...)]` that aren't optional items of a layout
+    (@check_derive Fields) => {};
+    (@check_derive ReadView) => {};
+    (@check_derive TrackedView) => {};
+    (@check_derive VersionedView) => {};
+    (@check_derive DebugFields) => {};
+    (@check_derive Diff) => {};
+    (@check_derive FromStr) => {};
+    (@check_derive Translate) => {};
+    (@check_derive Arbitrary) => {};
+    (@check_derive $other: ident) => {
+        compile_error!(concat!("Error: `", stringify!($other), "` can't be derived for a layout, expected one of `Fields`, `ReadView`, `TrackedView`, `VersionedView`, `DebugFields`, `Diff`, `FromStr`, `Translate` or `Arbitrary`"));
+    };
+
+    // The @if_some rules expand to the given tokens if the optional value is present.
+    (@if_some [] {$($tokens: tt)*}) => {};
+    (@if_some [$value: tt] {$($tokens: tt)*}) => {
+        $($tokens)*
+    };
+
+    // The @probe rule calls a method on a [FieldProbe](crate::internal::FieldProbe) for the given field type. The method is looked up
+    // through autoref from the given references, so the trait implemented for the most references that applies to the field is used.
+    (@probe [$($refs: tt)*] $field_type: ty, [$($trait: ident),*], $method: ident($($arg: expr),*)) => {{
+        #[allow(unused_imports)]
+        use $crate::internal::{$($trait as _),*};
+        ($($refs)* $crate::internal::FieldProbe::<$field_type>::default()).$method($($arg),*)
+    }};
+}
+
+/// Deprecated name for [crate::binary_layout!]. Please switch to [crate::binary_layout!].
+#[deprecated = "The `define_layout!` macro was renamed to `binary_layout!` and the old name will be removed in future versions."]
+#[macro_export]
+macro_rules! define_layout {
+    ($name: ident, $endianness: ident, {$($fields: tt)*}) => {
+        $crate::binary_layout!($name, $endianness, {$($fields)*});
+    }
+}
+
+// TODO This only exists because Option<usize>::unwrap() isn't const. Remove this once it is.
+/// Internal function, don't use!
+/// Unwraps an `Option<usize>`
+#[inline(always)]
+pub const fn unwrap_field_size(opt: Option<usize>) -> usize {
+    match opt {
+        Some(x) => x,
+        None => {
+            panic!("Error: Fields without a static size (e.g. open-ended byte arrays) can only be used at the end of a layout");
+        }
+    }
+}
+
+/// Internal macro, don't use!
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __binary_layout_if_std {
+    ($($items: item)*) => {
+        $($items)*
+    };
+}
+
+/// Internal macro, don't use!
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __binary_layout_if_std {
+    ($($items: item)*) => {};
+}
+
+/// Internal macro, don't use!
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __binary_layout_if_arbitrary {
+    ($($items: item)*) => {
+        $($items)*
+    };
+}
+
+/// Internal macro, don't use!
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __binary_layout_if_arbitrary {
+    ($($items: item)*) => {};
+}
+
+/// Internal function, don't use!
+#[inline(always)]
+pub const fn option_usize_add(lhs: usize, rhs: Option<usize>) -> Option<usize> {
+    match (lhs, rhs) {
+        (lhs, Some(rhs)) => Some(lhs + rhs),
+        (_, None) => None,
+    }
+}
+
+/// Internal function, don't use!
+/// Returns the index of a field name in the names of all fields of a layout
+#[inline(always)]
+pub const fn field_index(names: &[&str], name: &str) -> usize {
+    let mut index = 0;
+    while index < names.len() {
+        if str_eq(names[index], name) {
+            return index;
+        }
+        index += 1;
+    }
+    panic!("Error: Field not found in the layout");
+}
+
+#[inline(always)]
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < lhs.len() {
+        if lhs[index] != rhs[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Internal function, don't use!
+/// Called by [binary_layout!](crate::binary_layout!) for each `field: T as U` so that a missing `LayoutAs<U>` implementation
+/// for `T` is reported as a failed bound of this function, whose name explains the problem.
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn field_type_must_implement_LayoutAs_for_the_type_after_as<T: crate::LayoutAs<U>, U>() {}
+
+/// Internal type, don't use!
+/// Checks the size of a layout with an `#[assert_pow2_size]` attribute. The size is a const parameter
+/// so that the compiler error for a failed check shows it.
+pub struct AssertPow2Size<const SIZE: usize>;
+
+impl<const SIZE: usize> AssertPow2Size<SIZE> {
+    /// Internal constant, don't use!
+    /// Fails to evaluate if `SIZE` isn't a power of two
+    pub const CHECK: () = assert!(
+        SIZE.is_power_of_two(),
+        "Error: The size of a layout with #[assert_pow2_size] must be a power of two. The actual size is the SIZE parameter of AssertPow2Size<SIZE> in this error."
+    );
+}
+
+/// Internal function, don't use!
+/// Unwraps the size of a layout with an `#[assert_pow2_size]` attribute
+#[inline(always)]
+pub const fn static_layout_size(size: Option<usize>) -> usize {
+    match size {
+        Some(size) => size,
+        None => {
+            panic!("Error: #[assert_pow2_size] can only be used for layouts with a static size");
+        }
+    }
+}
+
+/// Internal type, don't use!
+/// Holds the stride of a layout for `offset_in_record`. Being generic over the field `F`, it is only evaluated when
+/// `offset_in_record` is used, so layouts with a dynamic size only fail to compile if they use it.
+pub struct RecordStride<N, F>(core::marker::PhantomData<(N, F)>);
+
+impl<N: crate::internal::NestedViewInfo, F> RecordStride<N, F> {
+    /// Internal constant, don't use!
+    /// Fails to evaluate if the layout doesn't have a static size
+    pub const STRIDE: usize = match N::SIZE {
+        Some(size) => size,
+        None => {
+            panic!("Error: offset_in_record can only be used for layouts with a static size");
+        }
+    };
+}
+
+/// Internal type, don't use!
+/// Checks that a layout has a dynamically sized last field for `push_tail`. Being generic over the type of the appended
+/// bytes `B`, it is only evaluated when `push_tail` is used, so layouts with a static size only fail to compile if they use it.
+pub struct AssertDynamicTail<N, B: ?Sized>(
+    core::marker::PhantomData<N>,
+    core::marker::PhantomData<B>,
+);
+
+impl<N: crate::internal::NestedViewInfo, B: ?Sized> AssertDynamicTail<N, B> {
+    /// Internal constant, don't use!
+    /// Fails to evaluate if the layout has a static size
+    pub const CHECK: () = assert!(
+        N::SIZE.is_none(),
+        "Error: push_tail can only be used for layouts with a dynamically sized last field"
+    );
+}
+
+/// Internal type, don't use!
+/// Checks that a layout with a `#[deny_gaps]` attribute doesn't have gaps. The first gap is passed as const parameters
+/// so that the compiler error for a failed check shows it.
+pub struct DenyGaps<const START: usize, const END: usize>;
+
+impl<const START: usize, const END: usize> DenyGaps<START, END> {
+    /// Internal constant, don't use!
+    /// Fails to evaluate if `START..END` isn't empty
+    pub const CHECK: () = assert!(
+        START == END,
+        "Error: A layout with #[deny_gaps] has bytes that don't belong to any field. The first gap is START..END from the parameters of DenyGaps<START, END> in this error."
+    );
+}
+
+/// Internal function, don't use!
+/// Returns the first gap of a layout, or an empty gap if there is none
+#[inline(always)]
+pub const fn first_gap(gaps: &[(usize, usize)]) -> (usize, usize) {
+    if gaps.is_empty() {
+        (0, 0)
+    } else {
+        gaps[0]
+    }
+}
+
+/// Internal function, don't use!
+/// Computes the end of a `union {...}` block from the ends of its members
+#[inline(always)]
+pub const fn union_end(member_ends: &[Option<usize>]) -> Option<usize> {
+    assert!(
+        !member_ends.is_empty(),
+        "Error: A union block must have at least one field"
+    );
+    let mut end = 0;
+    let mut i = 0;
+    while i < member_ends.len() {
+        match member_ends[i] {
+            Some(member_end) => {
+                if member_end > end {
+                    end = member_end;
+                }
+            }
+            None => {
+                panic!("Error: Fields in a union block must have a static size");
+            }
+        }
+        i += 1;
+    }
+    Some(end)
+}
+
+/// Internal function, don't use!
+/// Formats bytes as a hex string without separators
+#[cfg(feature = "std")]
+pub fn hex_string(bytes: &[u8], upper: bool) -> String {
+    use core::fmt::Write;
+    let mut hex = String::with_capacity(2 * bytes.len());
+    for byte in bytes {
+        if upper {
+            write!(hex, "{:02X}", byte).unwrap();
+        } else {
+            write!(hex, "{:02x}", byte).unwrap();
+        }
+    }
+    hex
+}
+
+/// Internal function, don't use!
+/// Writes bytes in pieces of at most `chunk` bytes and flushes after each piece
+#[cfg(feature = "std")]
+pub fn write_chunked<W: std::io::Write>(
+    bytes: &[u8],
+    mut writer: W,
+    chunk: usize,
+) -> std::io::Result<usize> {
+    assert!(chunk > 0, "Error: The chunk size must not be zero");
+    for piece in bytes.chunks(chunk) {
+        writer.write_all(piece)?;
+        writer.flush()?;
+    }
+    Ok(bytes.len())
+}
+
+/// Internal function, don't use!
+/// Moves the offset of a field declared as `field @offset: T` to the offset it is pinned to
+#[inline(always)]
+pub const fn pin_offset(offset: Option<usize>, pinned_offset: usize) -> Option<usize> {
+    match offset {
+        Some(offset) => {
+            assert!(
+                pinned_offset >= offset,
+                "Error: A field can't be pinned to an offset before the end of the previous field"
+            );
+            Some(pinned_offset)
+        }
+        None => {
+            panic!(
+                "Error: Fields can't be pinned to an offset after a field without a static size"
+            );
+        }
+    }
+}
+
+/// Internal function, don't use!
+/// Rounds the size of a layout up to a multiple of `pad_to`, if given
+#[inline(always)]
+pub const fn pad_size(size: Option<usize>, pad_to: Option<usize>) -> Option<usize> {
+    match (size, pad_to) {
+        (size, None) => size,
+        (Some(size), Some(pad_to)) => {
+            assert!(
+                pad_to > 0,
+                "Error: #[pad_to(...)] must be greater than zero"
+            );
+            Some((size + pad_to - 1) / pad_to * pad_to)
+        }
+        (None, Some(_)) => {
+            panic!("Error: #[pad_to(...)] can only be used for layouts with a static size");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Unused since `binary_layout!` is in textual scope here, but kept so the tests read like user code
+    #[allow(unused_imports)]
+    use crate::prelude::*;
+
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    #[cfg(feature = "std")]
+    fn data_region_vec(size: usize, seed: u64) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut res = vec![0; size];
+        rng.fill_bytes(&mut res);
+        res
+    }
+
+    fn data_region(seed: u64) -> [u8; 1024] {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut res = [0; 1024];
+        rng.fill_bytes(&mut res);
+        res
+    }
+
+    binary_layout!(module_level_layout, LittleEndian, {
+        first: i8,
+        second: i64,
+        third: u16,
+    });
+
+    #[test]
+    fn layouts_can_be_defined_at_module_level() {
+        let storage: [u8; 1024] = [0; 1024];
+        let view = module_level_layout::View::new(storage);
+        assert_eq!(0, view.third().read());
+    }
+
+    #[test]
+    fn field_names_that_extend_other_field_names() {
+        use crate::Field;
+
+        binary_layout!(my_layout, LittleEndian, {
+            temp: u16,
+            temp_min: u16,
+        });
+
+        assert_eq!(2, my_layout::temp_min::OFFSET);
+        assert_eq!(Some(4), my_layout::SIZE);
+
+        binary_layout!(dynamic_layout, LittleEndian, {
+            x: u8,
+            x_dynamic: u8,
+            tail: [u8],
+        });
+
+        assert_eq!(1, dynamic_layout::x_dynamic::OFFSET);
+        assert_eq!(2, dynamic_layout::required_len(0));
+    }
+
+    #[test]
+    fn layouts_can_be_defined_at_function_level() {
+        binary_layout!(function_level_layout, LittleEndian, {
+            first: i8,
+            second: i64,
+            third: u16,
+        });
+
+        let storage: [u8; 1024] = [0; 1024];
+        let view = function_level_layout::View::new(storage);
+        assert_eq!(0, view.third().read());
+    }
+
+    #[test]
+    fn can_be_created_with_and_without_trailing_comma() {
+        binary_layout!(first, LittleEndian, { field: u8 });
+        binary_layout!(second, LittleEndian, {
+            field: u8,
+            second: u16
+        });
+        binary_layout!(third, LittleEndian, {
+            field: u8,
+        });
+        binary_layout!(fourth, LittleEndian, {
+            field: u8,
+            second: u16,
+        });
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn there_can_be_multiple_views_if_readonly_vec() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: i64,
+        });
+
+        let storage = data_region_vec(1024, 0);
+        let view1 = my_layout::View::new(&storage);
+        let view2 = my_layout::View::new(&storage);
+        view1.field1().read();
+        view2.field1().read();
+    }
+
+    #[test]
+    fn there_can_be_multiple_views_if_readonly_array() {
+        binary_layout!(my_layout, BigEndian, {
+            field1: u16,
+            field2: i64,
+        });
+
+        let storage = data_region(0);
+        let view1 = my_layout::View::new(&storage);
+        let view2 = my_layout::View::new(&storage);
+        view1.field1().read();
+        view2.field1().read();
+    }
+
+    #[test]
+    fn fields_can_have_attributes() {
+        binary_layout!(my_layout, LittleEndian, {
+            /// Some documentation
+            field1: u16,
+            #[doc = "More documentation"]
+            #[allow(clippy::all)]
+            field2: i64,
+            /// Documentation
+            /// over multiple lines
+            #[doc(alias = "tail")]
+            field3: [u8; expr(|_| 4)],
+        });
+
+        let storage = data_region(0);
+        let view = my_layout::View::new(&storage);
+        assert_eq!(
+            i64::from_le_bytes(storage[2..10].try_into().unwrap()),
+            view.field2().read()
+        );
+        assert_eq!(4, view.field3().len());
+    }
+
+    #[test]
+    fn fields_can_be_disabled_with_cfg() {
+        use crate::Field;
+
+        binary_layout!(my_layout, LittleEndian, {
+            field1: u8,
+            #[cfg(any())]
+            disabled: u32,
+            #[cfg(all())]
+            enabled: u16,
+            field2: u16,
+            /// Disabled by the second cfg predicate
+            #[cfg(all())]
+            #[cfg(any())]
+            tail: [u8],
+        });
+
+        assert_eq!(1, my_layout::enabled::OFFSET);
+        assert_eq!(3, my_layout::field2::OFFSET);
+        assert_eq!(Some(5), my_layout::SIZE);
+
+        let storage = data_region(0);
+        let view = my_layout::View::new(&storage);
+        assert_eq!(
+            u16::from_le_bytes([storage[3], storage[4]]),
+            view.field2().read()
+        );
+        assert_eq!(5, my_layout::byte_len(&storage));
+    }
+
+    #[test]
+    fn fields_after_disabled_dynamically_sized_field() {
+        use crate::Field;
+
+        binary_layout!(my_layout, LittleEndian, {
+            field1: u8,
+            #[cfg(any())]
+            disabled: [u8],
+            field2: u16,
+            #[cfg(any())]
+            disabled_len: u8,
+            #[cfg(any())]
+            disabled_computed: [u8; expr(|v| v.disabled_len().read() as usize)],
+        });
+
+        assert_eq!(1, my_layout::field2::OFFSET);
+        assert_eq!(Some(3), my_layout::SIZE);
+    }
+
+    mod visibility {
+        binary_layout!(my_layout, LittleEndian, {
+            pub(super) internal: u16,
+            pub(crate) crate_visible: u8,
+            pub public: u8,
+            default: u8,
+        });
+
+        pub fn read_internal(storage: &[u8]) -> u16 {
+            my_layout::View::new(storage).internal().read()
+        }
+    }
+
+    #[test]
+    fn fields_can_have_visibility() {
+        use crate::Field;
+
+        let storage = data_region(0);
+        assert_eq!(
+            u16::from_le_bytes([storage[0], storage[1]]),
+            visibility::read_internal(&storage)
+        );
+        let view = visibility::my_layout::View::new(&storage);
+        assert_eq!(storage[2], view.crate_visible().read());
+        assert_eq!(storage[3], view.public().read());
+        assert_eq!(storage[4], view.default().read());
+        assert_eq!(4, visibility::my_layout::default::OFFSET);
+    }
+
+    #[test]
+    fn fields_can_be_renamed() {
+        use crate::Field;
+
+        binary_layout!(my_layout, LittleEndian, {
+            #[name(message_type)]
+            msg_type: u8,
+            /// The length of the message
+            #[name(length)]
+            pub(crate) len: u16,
+            #[name(payload)]
+            data: [u8],
+        });
+
+        assert_eq!(0, my_layout::message_type::OFFSET);
+        assert_eq!(1, my_layout::length::OFFSET);
+        assert_eq!(3, my_layout::payload::OFFSET);
+
+        let mut storage = data_region(0);
+        let mut view = my_layout::View::new(&mut storage);
+        view.message_type_mut().write(5);
+        view.length_mut().write(1000);
+        assert_eq!(5, view.message_type().read());
+        assert_eq!(1000, view.length().read());
+        assert_eq!(&data_region(0)[3..], view.payload());
+        let payload = view.into_payload();
+        assert_eq!(&data_region(0)[3..], &*payload);
+    }
+
+    #[test]
+    fn fields_can_have_reserved_names() {
+        use crate::Field;
+
+        binary_layout!(my_layout, BigEndian, {
+            r#type: u8,
+            r#match: u16,
+            #[name(movement)]
+            r#move: u8,
+            r#struct: [u8],
+        });
+
+        assert_eq!(0, my_layout::r#type::OFFSET);
+        assert_eq!(1, my_layout::r#match::OFFSET);
+        assert_eq!(3, my_layout::movement::OFFSET);
+        assert_eq!(4, my_layout::r#struct::OFFSET);
+
+        let mut storage = data_region(0);
+        let mut view = my_layout::View::new(&mut storage);
+        view.type_mut().write(1);
+        view.match_mut().write(1000);
+        view.movement_mut().write(3);
+        assert_eq!(1, view.r#type().read());
+        assert_eq!(1000, view.r#match().read());
+        assert_eq!(3, view.movement().read());
+        assert_eq!(&data_region(0)[4..], view.r#struct());
+        let tail = view.into_struct();
+        assert_eq!(&data_region(0)[4..], &*tail);
+    }
+
+    #[test]
+    fn fields_can_have_names_of_layout_functions() {
+        binary_layout!(my_layout, BigEndian, {
+            byte_len: u8,
+            try_new: u8,
+            matches: u8,
+            to_hex: u8,
+            field_layout: u8,
+            reset_to_defaults: u8,
+            push_tail: u8,
+            read_framed: [u8],
+        });
+
+        let mut storage = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut view = my_layout::try_new(&mut storage[..]).unwrap();
+        view.byte_len_mut().write(10);
+        assert_eq!(10, view.byte_len().read());
+        assert_eq!(2, view.try_new().read());
+        assert_eq!(3, view.matches().read());
+        assert_eq!(4, view.to_hex().read());
+        assert_eq!(5, view.field_layout().read());
+        assert_eq!(6, view.reset_to_defaults().read());
+        assert_eq!(7, view.push_tail().read());
+        assert_eq!(&[8, 9], view.read_framed());
+        assert_eq!(9, my_layout::byte_len(&storage));
+    }
+
+    #[cfg(feature = "std")]
+    binary_layout!(debug_nested_layout, LittleEndian, {
+        inner: u8,
+    });
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_fields() {
+        binary_layout!(#[derive(DebugFields)] my_layout, LittleEndian, {
+            first: u16,
+            #[cfg(any())]
+            disabled: u32,
+            #[name(renamed)]
+            second: i8,
+            r#type: u8,
+            flag: bool as u8,
+            id: core::num::NonZeroU8,
+            array: [u8; 2],
+            nested: debug_nested_layout::NestedView,
+            tail: [u8],
+        });
+
+        let storage = [0x01, 0x02, 0xFF, 3, 1, 0, 5, 6, 7, 8];
+        let fields: Vec<(&str, String)> = my_layout::debug_fields(&storage)
+            .into_iter()
+            .map(|(name, value)| (name, format!("{:?}", value)))
+            .collect();
+        assert_eq!(
+            vec![
+                ("first", "513".to_string()),
+                ("renamed", "-1".to_string()),
+                ("type", "3".to_string()),
+                ("flag", "true".to_string()),
+                ("id", "NonZeroIsZeroError(())".to_string()),
+            ],
+            fields
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn diff() {
+        use crate::FieldDiff;
+
+        binary_layout!(#[derive(Diff)] my_layout, LittleEndian, {
+            first: u16,
+            #[name(renamed)]
+            second: i8,
+            id: core::num::NonZeroU8,
+            array: [u8; 4],
+            nested: debug_nested_layout::NestedView,
+            tail: [u8],
+        });
+
+        let a = [0x01, 0x02, 0xFF, 1, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(Vec::<FieldDiff>::new(), my_layout::diff(&a, &a));
+
+        let b = [0x01, 0x03, 0xFF, 0, 1, 9, 9, 4, 6, 6];
+        assert_eq!(
+            vec![
+                FieldDiff::Value {
+                    name: "first",
+                    a: "513".to_string(),
+                    b: "769".to_string(),
+                },
+                FieldDiff::Value {
+                    name: "id",
+                    a: "1".to_string(),
+                    b: "NonZeroIsZeroError(())".to_string(),
+                },
+                FieldDiff::Bytes {
+                    name: "array",
+                    range: 1..3,
+                },
+                FieldDiff::Bytes {
+                    name: "tail",
+                    range: 1..2,
+                },
+            ],
+            my_layout::diff(&a, &b)
+        );
+    }
+
+    #[test]
+    fn reset_to_defaults() {
+        binary_layout!(my_layout, LittleEndian, {
+            #[default(0x0102)]
+            first: u16,
+            #[cfg(any())]
+            #[default(5)]
+            disabled: u32,
+            /// A renamed field with a default
+            #[default(-1)]
+            #[name(renamed)]
+            second: i8,
+            flag: bool as u8,
+            len: u8,
+            payload: [u8; expr(|v| v.len().read() as usize)],
+        });
+
+        let mut storage = [0xFF, 0xFF, 0x00, 0x01, 2, 0xAA, 0xBB, 0xCC];
+        my_layout::reset_to_defaults(&mut storage);
+        assert_eq!([0x02, 0x01, 0xFF, 0x00, 0, 0, 0, 0xCC], storage);
+
+        let mut storage = [0xEE, 0xFF, 0xFF, 0x00, 0x01, 0, 0xCC];
+        let mut view = my_layout::at_offset(&mut storage[..], 1);
+        view.renamed_mut().write(3);
+        my_layout::reset_to_defaults(&mut view.into_storage());
+        assert_eq!([0xEE, 0x02, 0x01, 0xFF, 0x00, 0, 0xCC], storage);
+    }
+
+    #[test]
+    fn magic() {
+        use crate::LayoutError;
+
+        binary_layout!(my_layout, BigEndian, {
+            #[magic(0xCAFE)]
+            first: u16,
+            #[cfg(any())]
+            #[magic(5)]
+            disabled: u8,
+            version: u8,
+            #[name(renamed)]
+            #[magic(-2)]
+            second: i8,
+        });
+
+        let mut storage = [0xCA, 0xFE, 1, 0xFE];
+        assert_eq!(Ok(()), my_layout::check_magic(&storage));
+        assert!(my_layout::try_new(&storage[..]).is_ok());
+
+        storage[3] = 0;
+        assert_eq!(
+            Err(LayoutError::BadMagic { offset: 3 }),
+            my_layout::check_magic(&storage)
+        );
+        assert_eq!(
+            Err(LayoutError::BadMagic { offset: 3 }),
+            my_layout::try_new(&storage[..]).map(|_| ())
+        );
+
+        storage[0] = 0;
+        assert_eq!(
+            Err(LayoutError::BadMagic { offset: 0 }),
+            my_layout::check_magic(&storage)
+        );
+
+        my_layout::reset_to_defaults(&mut storage);
+        assert_eq!([0xCA, 0xFE, 0, 0xFE], storage);
+        assert_eq!(Ok(()), my_layout::check_magic(&storage));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn push_tail() {
+        use crate::LayoutError;
+
+        binary_layout!(computed, LittleEndian, {
+            #[tail_len]
+            #[name(renamed_len)]
+            len: u8,
+            id: u16,
+            data: [u8; expr(|v| v.renamed_len().read() as usize)],
+        });
+
+        binary_layout!(open_ended, LittleEndian, {
+            id: u16,
+            #[cfg(any())]
+            #[tail_len]
+            disabled: u8,
+            tail: [u8],
+        });
+
+        // Bytes after the end of the message are dropped
+        let mut storage = vec![1, 0x34, 0x12, 0xAA, 0xFF, 0xFF];
+        computed::push_tail(&mut storage, &[0xBB, 0xCC]).unwrap();
+        let view = computed::View::new(&storage);
+        assert_eq!(0x1234, view.id().read());
+        assert_eq!(&[0xAA, 0xBB, 0xCC], view.data());
+        computed::push_tail(&mut storage, &[]).unwrap();
+        assert_eq!(vec![3, 0x34, 0x12, 0xAA, 0xBB, 0xCC], storage);
+
+        let mut storage = vec![0; computed::required_len(250)];
+        computed::renamed_len::write(&mut storage, 250);
+        assert_eq!(
+            Err(LayoutError::TailLenOutOfRange {
+                offset: 0,
+                len: 256
+            }),
+            computed::push_tail(&mut storage, &[0; 6])
+        );
+        assert_eq!(250, computed::renamed_len::read(&storage));
+        assert_eq!(253, storage.len());
+        computed::push_tail(&mut storage, &[0; 5]).unwrap();
+        assert_eq!(255, computed::renamed_len::read(&storage));
+
+        let mut storage = vec![1, 0];
+        open_ended::push_tail(&mut storage, &[2]).unwrap();
+        open_ended::push_tail(&mut storage, &[3, 4]).unwrap();
+        assert_eq!(1, open_ended::id::read(&storage));
+        assert_eq!(vec![1, 0, 2, 3, 4], storage);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn push_tail_storage_too_short() {
+        use crate::LayoutError;
+
+        binary_layout!(my_layout, LittleEndian, {
+            id: u16,
+            len: u8,
+            data: [u8; expr(|v| v.len().read() as usize)],
+        });
+
+        let mut storage = vec![1];
+        assert_eq!(
+            Err(LayoutError::StorageTooShort {
+                expected: 3,
+                actual: 1
+            }),
+            my_layout::push_tail(&mut storage, &[2])
+        );
+        let mut storage = vec![1, 0, 2, 0xAA];
+        assert_eq!(
+            Err(LayoutError::StorageTooShort {
+                expected: 5,
+                actual: 4
+            }),
+            my_layout::push_tail(&mut storage, &[2])
+        );
+        assert_eq!(vec![1, 0, 2, 0xAA], storage);
+    }
+
+    #[test]
+    fn view_at() {
+        use crate::LayoutError;
 
-                    #[inline(always)]
-                    fn view(storage: S) -> Self::View {
-                        Self::View {storage: storage.into()}
-                    }
-                }
+        binary_layout!(directory_entry, LittleEndian, {
+            offset: u16,
+            len: u16,
+        });
+        binary_layout!(chunk, LittleEndian, {
+            kind: u8,
+            payload: [u8],
+        });
 
-                impl $crate::internal::NestedViewInfo for NestedView {
-                    const SIZE: Option<usize> = SIZE;
-                }
-            }
-        }
-    };
+        // A directory with two entries, followed by the chunks they point to
+        let mut storage = [8, 0, 3, 0, 11, 0, 2, 0, 1, 0xA1, 0xA2, 2, 0xB1, 0xFF];
+        let entries = [
+            directory_entry::View::new(&storage[0..4]),
+            directory_entry::View::new(&storage[4..8]),
+        ];
+        let regions: [(usize, usize); 2] = [
+            (
+                entries[0].offset().read().into(),
+                entries[0].len().read().into(),
+            ),
+            (
+                entries[1].offset().read().into(),
+                entries[1].len().read().into(),
+            ),
+        ];
 
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {}) => {
-        /// Total size of the layout in number of bytes.
-        /// This can be None if the layout ends with an open ended field like a byte slice.
-        pub const SIZE: Option<usize> = $offset_accumulator;
-    };
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {$name: ident : $type: ty as $underlying_type: ty $(, $($tail:tt)*)?}) => {
-        $crate::internal::doc_comment!{
-            concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
-            #[allow(non_camel_case_types)]
-            pub type $name = $crate::WrappedField::<$underlying_type, $type, $crate::PrimitiveField::<$underlying_type, $endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>>;
-        }
-        $crate::binary_layout!(@impl_fields $endianness, ($crate::internal::option_usize_add(<$name as $crate::Field>::OFFSET, <$name as $crate::Field>::SIZE)), {$($($tail)*)?});
-    };
-    (@impl_fields $endianness: ty, $offset_accumulator: expr, {$name: ident : $type: ty $(, $($tail:tt)*)?}) => {
-        $crate::internal::doc_comment!{
-            concat!("Metadata and [Field](crate::Field) API accessors for the `", stringify!($name), "` field"),
-            #[allow(non_camel_case_types)]
-            pub type $name = $crate::PrimitiveField::<$type, $endianness, {$crate::internal::unwrap_field_size($offset_accumulator)}>;
-        }
-        $crate::binary_layout!(@impl_fields $endianness, ($crate::internal::option_usize_add(<$name as $crate::Field>::OFFSET, <$name as $crate::Field>::SIZE)), {$($($tail)*)?});
-    };
+        let first = chunk::view_at(&storage[..], regions[0].0, regions[0].1).unwrap();
+        assert_eq!(1, first.kind().read());
+        assert_eq!(&[0xA1, 0xA2], first.payload());
+        let second = chunk::view_at(&storage[..], regions[1].0, regions[1].1).unwrap();
+        assert_eq!(2, second.kind().read());
+        assert_eq!(&[0xB1], second.payload());
 
-    (@impl_view_asref {}) => {};
-    (@impl_view_asref {$name: ident $(, $name_tail: ident)*}) => {
-        $crate::internal::doc_comment!{
-            concat!("Return a [FieldView](crate::FieldView) with read access to the `", stringify!($name), "` field"),
-            #[inline]
-            pub fn $name(&self) -> <$name as $crate::internal::StorageToFieldView<&[u8]>>::View {
-                <$name as $crate::internal::StorageToFieldView<&[u8]>>::view(self.storage.as_ref())
-            }
-        }
-        $crate::binary_layout!(@impl_view_asref {$($name_tail),*});
-    };
+        let mut view = chunk::view_at(&mut storage[..], 11, 3).unwrap();
+        view.kind_mut().write(3);
+        assert_eq!(3, storage[11]);
 
-    (@impl_view_asmut {}) => {};
-    (@impl_view_asmut {$name: ident $(, $name_tail: ident)*}) => {
-        $crate::internal::paste!{
-            $crate::internal::doc_comment!{
-                concat!("Return a [FieldView](crate::FieldView) with write access to the `", stringify!($name), "` field"),
-                #[inline]
-                pub fn [<$name _mut>](&mut self) -> <$name as $crate::internal::StorageToFieldView<&mut [u8]>>::View {
-                    <$name as $crate::internal::StorageToFieldView<&mut [u8]>>::view(self.storage.as_mut())
-                }
-            }
-        }
-        $crate::binary_layout!(@impl_view_asmut {$($name_tail),*});
-    };
+        assert_eq!(
+            Err(LayoutError::RegionOutOfBounds {
+                offset: 12,
+                len: 3,
+                actual: 14
+            }),
+            chunk::view_at(&storage[..], 12, 3).map(|_| ())
+        );
+        assert_eq!(
+            Err(LayoutError::RegionOutOfBounds {
+                offset: usize::MAX,
+                len: 1,
+                actual: 14
+            }),
+            chunk::view_at(&storage[..], usize::MAX, 1).map(|_| ())
+        );
+        assert_eq!(
+            Err(LayoutError::StorageTooShort {
+                expected: 1,
+                actual: 0
+            }),
+            chunk::view_at(&storage[..], 14, 0).map(|_| ())
+        );
+    }
 
-    (@impl_view_into {}) => {};
-    (@impl_view_into {$name: ident $(, $name_tail: ident)*}) => {
-        $crate::internal::paste!{
-            $crate::internal::doc_comment!{
-                concat!("Destroy the [View] and return a field accessor to the `", stringify!($name), "` field owning the storage. This is mostly useful for [FieldView::extract](crate::FieldView::extract)"),
-                #[inline]
-                pub fn [<into_ $name>](self) -> <$name as $crate::internal::StorageIntoFieldView<S>>::View {
-                    <$name as $crate::internal::StorageIntoFieldView<S>>::into_view(self.storage)
-                }
+    #[test]
+    fn matches() {
+        binary_layout!(my_layout, BigEndian, {
+            kind: u8,
+            flags: u8,
+            payload: [u8],
+        });
+
+        let template = [0x05, 0x80, 0xAA];
+        let mask = [0xFF, 0x80];
+        assert!(my_layout::matches(&[0x05, 0x81, 1, 2], &template, &mask));
+        assert!(my_layout::matches(&[0x05, 0xFF], &template, &mask));
+        assert!(!my_layout::matches(&[0x05, 0x01, 1, 2], &template, &mask));
+        assert!(!my_layout::matches(&[0x06, 0x80], &template, &mask));
+        assert!(my_layout::matches(&[0x06, 0x80], &template, &[0x00, 0x80]));
+
+        let storage = [0x05, 0x80, 0xAA, 0xBB];
+        assert!(my_layout::matches(&storage, &storage, &[0xFF; 8]));
+        assert!(!my_layout::matches(
+            &storage,
+            &[0x05, 0x80, 0xAA, 0xBC],
+            &[0xFF; 4]
+        ));
+
+        binary_layout!(sized, BigEndian, {
+            kind: u8,
+            flags: u8,
+        });
+
+        // Bytes after the layout aren't compared
+        assert!(sized::matches(&storage, &[0x05, 0x80, 0, 0], &[0xFF; 4]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_hex() {
+        binary_layout!(sized, BigEndian, {
+            kind: u8,
+            value: u16,
+        });
+        binary_layout!(open_ended, BigEndian, {
+            kind: u8,
+            payload: [u8],
+        });
+
+        let storage = [0x0A, 0xBC, 0xDE, 0xFF];
+        assert_eq!("0abcde", sized::to_hex(&storage));
+        assert_eq!("0ABCDE", sized::to_hex_upper(&storage));
+        assert_eq!("0abcdeff", open_ended::to_hex(&storage));
+        assert_eq!("0a", open_ended::to_hex(&storage[..1]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_to_chunked() {
+        // Records each flushed piece
+        #[derive(Default)]
+        struct Segments {
+            pending: Vec<u8>,
+            flushed: Vec<Vec<u8>>,
+        }
+        impl std::io::Write for Segments {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.pending.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.flushed.push(std::mem::take(&mut self.pending));
+                Ok(())
             }
         }
-        $crate::binary_layout!(@impl_view_into {$($name_tail),*});
-    };
-}
 
-/// Deprecated name for [crate::binary_layout!]. Please switch to [crate::binary_layout!].
-#[deprecated = "The `define_layout!` macro was renamed to `binary_layout!` and the old name will be removed in future versions."]
-#[macro_export]
-macro_rules! define_layout {
-    ($name: ident, $endianness: ident, {$($field_name: ident : $field_type: ty $(as $underlying_type: ty)?),* $(,)?}) => {
-        $crate::binary_layout!($name, $endianness, {$($field_name : $field_type $(as $underlying_type)?),*});
+        binary_layout!(sized, BigEndian, {
+            kind: u8,
+            value: u32,
+        });
+        binary_layout!(open_ended, BigEndian, {
+            kind: u8,
+            payload: [u8],
+        });
+
+        let storage = [1, 2, 3, 4, 5, 6];
+        let mut segments = Segments::default();
+        assert_eq!(
+            5,
+            sized::write_to_chunked(&storage, &mut segments, 2).unwrap()
+        );
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], segments.flushed);
+
+        let mut segments = Segments::default();
+        assert_eq!(
+            6,
+            open_ended::write_to_chunked(&storage, &mut segments, 3).unwrap()
+        );
+        assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], segments.flushed);
+
+        let mut segments = Segments::default();
+        assert_eq!(
+            5,
+            sized::write_to_chunked(&storage, &mut segments, 100).unwrap()
+        );
+        assert_eq!(vec![vec![1, 2, 3, 4, 5]], segments.flushed);
     }
-}
 
-// TODO This only exists because Option<usize>::unwrap() isn't const. Remove this once it is.
-/// Internal function, don't use!
-/// Unwraps an `Option<usize>`
-#[inline(always)]
-pub const fn unwrap_field_size(opt: Option<usize>) -> usize {
-    match opt {
-        Some(x) => x,
-        None => {
-            panic!("Error: Fields without a static size (e.g. open-ended byte arrays) can only be used at the end of a layout");
-        }
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic(expected = "Error: The chunk size must not be zero")]
+    fn write_to_chunked_zero_chunk() {
+        binary_layout!(sized, BigEndian, {
+            value: u32,
+        });
+        sized::write_to_chunked(&[0; 4], Vec::new(), 0).unwrap();
     }
-}
 
-/// Internal function, don't use!
-#[inline(always)]
-pub const fn option_usize_add(lhs: usize, rhs: Option<usize>) -> Option<usize> {
-    match (lhs, rhs) {
-        (lhs, Some(rhs)) => Some(lhs + rhs),
-        (_, None) => None,
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        binary_layout!(#[derive(Arbitrary)] sized, BigEndian, {
+            id: core::num::NonZeroU32,
+            flag: bool as u8,
+            #[cfg(any())]
+            disabled: u32,
+            raw: [u8; 2],
+        });
+        binary_layout!(#[derive(Arbitrary)] dynamic, LittleEndian, {
+            len: u8,
+            data: [u8; expr(|v| v.len().read() as usize)],
+        });
+        binary_layout!(#[derive(Arbitrary)] open, LittleEndian, {
+            kind: u8,
+            tail: [u8],
+        });
+
+        let data = [0u8; 64];
+        let view = sized::View::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(view.id().try_read().is_ok());
+        assert!(!view.flag().try_read().unwrap());
+        assert_eq!(7, view.into_storage().len());
+
+        let data: Vec<u8> = (0..=255).collect();
+        let view = sized::View::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(view.id().try_read().is_ok());
+        assert!(view.flag().try_read().is_ok());
+        assert_eq!(7, view.into_storage().len());
+
+        let storage = dynamic::View::arbitrary(&mut Unstructured::new(&data))
+            .unwrap()
+            .into_storage();
+        assert_eq!(dynamic::byte_len(&storage), storage.len());
+
+        let storage = open::View::arbitrary(&mut Unstructured::new(&data))
+            .unwrap()
+            .into_storage();
+        assert!(open::byte_len(&storage) > 1);
+        assert_eq!(open::byte_len(&storage), storage.len());
+
+        assert!(sized::View::arbitrary(&mut Unstructured::new(&[])).is_ok());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::prelude::*;
+    binary_layout!(translate_nested, LittleEndian, {
+        value: u8,
+    });
 
-    use rand::{rngs::StdRng, RngCore, SeedableRng};
+    #[cfg(feature = "std")]
+    #[test]
+    fn translate() {
+        use crate::translate;
+        use core::num::NonZeroU16;
+
+        binary_layout!(#[derive(Translate)] v1, BigEndian, {
+            id: core::num::NonZeroU16,
+            count: u16,
+            #[name(label)]
+            name: [u8; 3],
+            inner: super::translate_nested::NestedView,
+            removed: u8,
+            tail: [u8],
+        });
+        binary_layout!(#[derive(Translate)] v2, LittleEndian, {
+            label: [u8; 3],
+            count: u32,
+            id: core::num::NonZeroU16,
+            #[cfg(any())]
+            removed: u8,
+            inner: super::translate_nested::NestedView,
+            added: u8,
+            tail: [u8],
+        });
+
+        let mut src = v1::View::new(vec![0; 11]);
+        src.id_mut().write(NonZeroU16::new(0x0102).unwrap());
+        src.count_mut().write(7);
+        src.label_mut().copy_from_slice(b"abc");
+        src.inner_mut().value_mut().write(9);
+        src.tail_mut().copy_from_slice(&[1, 2]);
+
+        let mut dst = v2::View::new(vec![0xFF; 12]);
+        // label and id match; count has a different type, inner is nested and tail has a different length
+        assert_eq!(2, translate(&src, &mut dst));
+        assert_eq!(b"abc", dst.label());
+        assert_eq!(0x0102, dst.id().try_read().unwrap().get());
+        assert_eq!(u32::MAX, dst.count().read());
+        assert_eq!(0xFF, dst.inner().value().read());
+        assert_eq!(0xFF, dst.added().read());
+        assert_eq!(&[0xFF], dst.tail());
+
+        let mut dst = v2::View::new(vec![0xFF; 13]);
+        assert_eq!(3, translate(&src, &mut dst));
+        assert_eq!(&[1, 2], dst.tail());
+
+        // Fields that fail to read aren't copied
+        let src = v1::View::new([0; 9]);
+        let mut dst = v2::View::new([0xFF; 11]);
+        assert_eq!(2, translate(&src, &mut dst));
+        assert_eq!([0; 3], *dst.label());
+        assert!(dst.id().try_read().is_ok());
+    }
 
     #[cfg(feature = "std")]
-    fn data_region_vec(size: usize, seed: u64) -> Vec<u8> {
-        let mut rng = StdRng::seed_from_u64(seed);
-        let mut res = vec![0; size];
-        rng.fill_bytes(&mut res);
-        res
+    #[test]
+    fn from_fields_str() {
+        use crate::ParseFieldsError;
+
+        binary_layout!(#[derive(FromStr)] my_layout, LittleEndian, {
+            kind: u8,
+            #[name(temp)]
+            temperature: f32,
+            id: core::num::NonZeroU16,
+            #[cfg(any())]
+            disabled: u32,
+            ready: bool as u8,
+            payload: [u8],
+        });
+
+        let view = my_layout::from_fields_str(" kind = 7,temp=1.5,, ready=true ").unwrap();
+        assert_eq!(vec![7, 0, 0, 0xC0, 0x3F, 0, 0, 1], view.into_storage());
+
+        let view: my_layout::View<Vec<u8>> = "id=513".parse().unwrap();
+        assert_eq!(vec![0, 0, 0, 0, 0, 1, 2, 0], view.into_storage());
+        assert_eq!(
+            vec![0; 8],
+            my_layout::from_fields_str("").unwrap().into_storage()
+        );
+
+        let error = |text| my_layout::from_fields_str(text).map(|view| view.into_storage());
+        assert_eq!(
+            Err(ParseFieldsError::MissingEquals {
+                pair: "kind".to_string()
+            }),
+            error("kind=1, kind")
+        );
+        assert_eq!(
+            Err(ParseFieldsError::UnknownField {
+                name: "temperature".to_string()
+            }),
+            error("temperature=1")
+        );
+        assert_eq!(
+            Err(ParseFieldsError::UnknownField {
+                name: "disabled".to_string()
+            }),
+            error("disabled=1")
+        );
+        assert_eq!(
+            Err(ParseFieldsError::UnsupportedField { name: "payload" }),
+            error("payload=1")
+        );
+        assert_eq!(
+            Err(ParseFieldsError::InvalidValue {
+                name: "id",
+                value: "0".to_string()
+            }),
+            error("id=0")
+        );
+        assert_eq!(
+            Err(ParseFieldsError::InvalidValue {
+                name: "kind",
+                value: "-1".to_string()
+            }),
+            error("kind=-1")
+        );
+        assert_eq!(
+            "Invalid value '-1' for the field 'kind'",
+            error("kind=-1").unwrap_err().to_string()
+        );
     }
 
-    fn data_region(seed: u64) -> [u8; 1024] {
-        let mut rng = StdRng::seed_from_u64(seed);
-        let mut res = [0; 1024];
-        rng.fill_bytes(&mut res);
-        res
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_and_write_views() {
+        binary_layout!(#[derive(ReadView)] my_layout, LittleEndian, {
+            first: u16,
+            second: u8,
+        });
+
+        fn sum(view: &impl my_layout::ReadView) -> u16 {
+            let view = view.as_view();
+            view.first().read() + u16::from(view.second().read())
+        }
+
+        fn increment(view: &mut impl my_layout::WriteView) {
+            let mut view = view.as_view_mut();
+            let second = view.second().read();
+            view.second_mut().write(second + 1);
+        }
+
+        let mut storage = vec![1, 0, 2];
+        assert_eq!(3, sum(&my_layout::View::new(&storage[..])));
+
+        let mut view = my_layout::View::new(&mut storage[..]);
+        increment(&mut view);
+        assert_eq!(4, sum(&view));
+
+        let mut view = my_layout::View::new(storage);
+        increment(&mut view);
+        assert_eq!(vec![1, 0, 4], view.into_storage());
     }
 
-    binary_layout!(module_level_layout, LittleEndian, {
-        first: i8,
-        second: i64,
-        third: u16,
-    });
+    #[cfg(feature = "std")]
+    #[test]
+    fn tracked_view() {
+        binary_layout!(#[derive(ReadView, TrackedView)] my_layout, BigEndian, {
+            first: u16,
+            #[name(renamed)]
+            second: u8,
+            #[cfg(any())]
+            disabled: u32,
+            third: [u8; 2],
+        });
+
+        let mut view = my_layout::TrackedView::new([0; 5]);
+        assert!(!view.is_dirty());
+        assert_eq!(0, view.dirty_fields().count());
+
+        view.third_mut().copy_from_slice(&[1, 2]);
+        view.first_mut().write(0x0304);
+        assert!(view.is_dirty());
+        assert_eq!(
+            vec!["first", "third"],
+            view.dirty_fields().collect::<Vec<_>>()
+        );
+        assert_eq!(0x0304, view.view().first().read());
+
+        view.clear_dirty();
+        assert!(!view.is_dirty());
+        view.renamed_mut().write(5);
+        assert_eq!(vec!["renamed"], view.dirty_fields().collect::<Vec<_>>());
+        assert_eq!(5, my_layout::ReadView::as_view(&view).renamed().read());
+
+        assert_eq!([3, 4, 5, 1, 2], view.into_view().into_storage());
+    }
 
     #[test]
-    fn layouts_can_be_defined_at_module_level() {
-        let storage: [u8; 1024] = [0; 1024];
-        let view = module_level_layout::View::new(storage);
-        assert_eq!(0, view.third().read());
+    fn derive_with_other_attributes() {
+        binary_layout!(#[pad_to(4)] #[derive(TrackedView, VersionedView)] #[display("{first}")] my_layout, BigEndian, {
+            first: u8,
+            #[since(2)]
+            second: u8,
+        });
+
+        let mut view = my_layout::TrackedView::new([1, 2, 0, 0]);
+        view.second_mut().write(3);
+        assert_eq!(vec!["second"], view.dirty_fields().collect::<Vec<_>>());
+        assert_eq!("1", view.into_view().to_string());
+        assert!(my_layout::new_version(&[1][..], 1).is_ok());
+        assert_eq!(Some(4), my_layout::SIZE);
     }
 
     #[test]
-    fn layouts_can_be_defined_at_function_level() {
-        binary_layout!(function_level_layout, LittleEndian, {
-            first: i8,
-            second: i64,
-            third: u16,
+    fn offset_in_record() {
+        binary_layout!(my_layout, BigEndian, {
+            first: u16,
+            second: u32,
+        });
+        binary_layout!(#[pad_to(8)] padded, BigEndian, {
+            first: u16,
+            second: u32,
+        });
+        binary_layout!(dynamic, BigEndian, {
+            first: u16,
+            tail: [u8],
         });
 
-        let storage: [u8; 1024] = [0; 1024];
-        let view = function_level_layout::View::new(storage);
-        assert_eq!(0, view.third().read());
+        assert_eq!(Some(6), my_layout::STRIDE);
+        assert_eq!(0, my_layout::offset_in_record::<my_layout::first>(0));
+        assert_eq!(2, my_layout::offset_in_record::<my_layout::second>(0));
+        assert_eq!(14, my_layout::offset_in_record::<my_layout::second>(2));
+
+        assert_eq!(Some(8), padded::STRIDE);
+        assert_eq!(26, padded::offset_in_record::<padded::second>(3));
+
+        assert_eq!(None, dynamic::STRIDE);
     }
 
     #[test]
-    fn can_be_created_with_and_without_trailing_comma() {
-        binary_layout!(first, LittleEndian, { field: u8 });
-        binary_layout!(second, LittleEndian, {
-            field: u8,
-            second: u16
+    fn versioned_fields() {
+        binary_layout!(#[derive(VersionedView)] my_layout, BigEndian, {
+            first: u16,
+            #[since(2)]
+            #[name(renamed)]
+            second: u8,
+            #[since(2)]
+            third: u8,
+            #[since(4)]
+            tail: [u8],
         });
-        binary_layout!(third, LittleEndian, {
-            field: u8,
+
+        let mut storage = [0, 1, 2, 3, 4, 5];
+        let view = my_layout::new_version(&storage[..2], 1).unwrap();
+        assert_eq!(1, view.first().unwrap().read());
+        assert!(view.renamed().is_none());
+        assert!(view.third().is_none());
+        assert!(view.tail().is_none());
+        assert_eq!(2, my_layout::version_byte_len(&storage, 1));
+
+        let mut view = my_layout::new_version(&mut storage[..], 3).unwrap();
+        assert_eq!(2, view.renamed().unwrap().read());
+        view.third_mut().unwrap().write(10);
+        assert!(view.tail_mut().is_none());
+        assert_eq!(4, my_layout::version_byte_len(&storage, 3));
+
+        let view = my_layout::new_version(&storage[..], 4).unwrap();
+        assert_eq!(&[4, 5], view.tail().unwrap());
+        assert_eq!(6, my_layout::version_byte_len(&storage, 4));
+        assert_eq!(10, my_layout::View::from(view).third().read());
+    }
+
+    #[test]
+    fn versioned_fields_storage_too_short() {
+        binary_layout!(#[derive(VersionedView)] my_layout, BigEndian, {
+            first: u16,
+            #[since(2)]
+            second: u32,
         });
-        binary_layout!(fourth, LittleEndian, {
-            field: u8,
-            second: u16,
+
+        assert_eq!(
+            Err(crate::LayoutError::StorageTooShort {
+                expected: 2,
+                actual: 1
+            }),
+            my_layout::new_version(&[0][..], 1).map(|_| ())
+        );
+        assert!(my_layout::new_version(&[0, 0][..], 1).is_ok());
+        assert_eq!(
+            Err(crate::LayoutError::StorageTooShort {
+                expected: 6,
+                actual: 2
+            }),
+            my_layout::new_version(&[0, 0][..], 2).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn rfc_bits() {
+        use crate::Field;
+
+        binary_layout!(rfc_layout, LittleEndian, {
+            tag: u8,
+            union {
+                high: bits 0..3,
+                spanning: bits 3..13,
+                single: bits 13..14,
+                low: bits 14..16,
+            },
+            wide: bits 4..60,
+            trailer: u8,
         });
+
+        assert_eq!(1, rfc_layout::high::OFFSET);
+        assert_eq!(Some(1), rfc_layout::high::SIZE);
+        assert_eq!(Some(2), rfc_layout::spanning::SIZE);
+        assert_eq!(3, rfc_layout::wide::OFFSET);
+        assert_eq!(Some(8), rfc_layout::wide::SIZE);
+        assert_eq!(11, rfc_layout::trailer::OFFSET);
+
+        let mut storage = [0; 12];
+        let mut view = rfc_layout::View::new(&mut storage[..]);
+        view.high_mut().try_write(0b101).unwrap();
+        view.spanning_mut().try_write(0b11_0000_0001).unwrap();
+        view.single_mut().try_write(1).unwrap();
+        view.low_mut().try_write(0b10).unwrap();
+        view.wide_mut().try_write(0xFF_FFFF_FFFF_FFFF).unwrap();
+        assert!(view.single_mut().try_write(2).is_err());
+        assert_eq!(0b101, view.high().read());
+        assert_eq!(0b11_0000_0001, view.spanning().read());
+        assert_eq!(1, view.single().read());
+        assert_eq!(0b10, view.low().read());
+        // The bits are big endian even though the layout is little endian
+        assert_eq!(
+            [
+                0,
+                0b1011_1000,
+                0b0000_1110,
+                0x0F,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xF0,
+                0
+            ],
+            storage
+        );
     }
 
-    #[cfg(feature = "std")]
     #[test]
-    fn there_can_be_multiple_views_if_readonly_vec() {
-        binary_layout!(my_layout, BigEndian, {
-            field1: u16,
-            field2: i64,
+    fn union_fields() {
+        use crate::Field;
+
+        binary_layout!(my_layout, LittleEndian, {
+            kind: u8,
+            union {
+                as_int: u32,
+                pub as_bytes: [u8; 6],
+                #[name(short)]
+                as_short: u16,
+            },
+            trailer: u16,
+            union {
+                value: i8,
+                flag: bool as u8
+            }
         });
 
-        let storage = data_region_vec(1024, 0);
-        let view1 = my_layout::View::new(&storage);
-        let view2 = my_layout::View::new(&storage);
-        view1.field1().read();
-        view2.field1().read();
+        assert_eq!(1, my_layout::as_int::OFFSET);
+        assert_eq!(1, my_layout::as_bytes::OFFSET);
+        assert_eq!(1, my_layout::short::OFFSET);
+        assert_eq!(7, my_layout::trailer::OFFSET);
+        assert_eq!(9, my_layout::value::OFFSET);
+        assert_eq!(9, my_layout::flag::OFFSET);
+        assert_eq!(Some(10), my_layout::SIZE);
+        assert!(my_layout::GAPS.is_empty());
+
+        let mut storage = [0; 10];
+        let mut view = my_layout::View::new(&mut storage);
+        view.as_bytes_mut()
+            .copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        view.trailer_mut().write(0x0807);
+        view.value_mut().write(1);
+        assert_eq!(0x04030201, view.as_int().read());
+        assert_eq!(0x0201, view.short().read());
+        assert!(view.flag().try_read().unwrap());
+        assert_eq!([0, 1, 2, 3, 4, 5, 6, 7, 8, 1], storage);
     }
 
     #[test]
-    fn there_can_be_multiple_views_if_readonly_array() {
+    fn reserved_zero() {
+        use crate::LayoutError;
+
+        binary_layout!(#[pad_to(12)] my_layout, LittleEndian, {
+            first: u16,
+            second @4: u8,
+            third @8: u8,
+        });
+
+        let mut storage = [0xFF; 12];
+        storage[2..4].fill(0);
+        storage[5..8].fill(0);
+        storage[9..12].fill(0);
+        assert_eq!(Ok(()), my_layout::check_reserved_zero(&storage));
+
+        for offset in [2, 3, 5, 7, 9, 11] {
+            let mut storage = storage;
+            storage[offset] = 1;
+            assert_eq!(
+                Err(LayoutError::ReservedNotZero { offset }),
+                my_layout::check_reserved_zero(&storage)
+            );
+        }
+
+        binary_layout!(no_reserved, LittleEndian, {
+            first: u16,
+            tail: [u8],
+        });
+        assert_eq!(Ok(()), no_reserved::check_reserved_zero(&[0xFF; 5]));
+    }
+
+    #[test]
+    fn pinned_offsets() {
+        use crate::Field;
+
         binary_layout!(my_layout, BigEndian, {
-            field1: u16,
-            field2: i64,
+            first: u16,
+            pub second @0x08: u32,
+            #[name(renamed)]
+            third @12: u8,
+            flag: bool as u8,
+            tail @0x10: [u8],
         });
 
-        let storage = data_region(0);
-        let view1 = my_layout::View::new(&storage);
-        let view2 = my_layout::View::new(&storage);
-        view1.field1().read();
-        view2.field1().read();
+        assert_eq!(0, my_layout::first::OFFSET);
+        assert_eq!(8, my_layout::second::OFFSET);
+        assert_eq!(12, my_layout::renamed::OFFSET);
+        assert_eq!(13, my_layout::flag::OFFSET);
+        assert_eq!(16, my_layout::tail::OFFSET);
+        assert_eq!(16, my_layout::required_len(0));
+        assert_eq!(&[(2, 8), (14, 16)], my_layout::GAPS);
+
+        let mut storage = [0; 18];
+        let mut view = my_layout::View::new(&mut storage);
+        view.second_mut().write(0x01020304);
+        view.renamed_mut().write(5);
+        view.tail_mut().copy_from_slice(&[6, 7]);
+        assert_eq!(
+            [0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0, 6, 7],
+            storage
+        );
+    }
+
+    #[test]
+    fn gaps() {
+        binary_layout!(no_pins, LittleEndian, {
+            first: u16,
+            tail: [u8],
+        });
+        binary_layout!(pinned_to_end, LittleEndian, {
+            first @0: u16,
+            #[cfg(any())]
+            disabled @4: u16,
+            second @6: u8,
+            third @8: u8,
+        });
+        binary_layout!(#[deny_gaps] #[pad_to(8)] #[assert_pow2_size] no_gaps, LittleEndian, {
+            first: u16,
+            second @2: u8,
+        });
+        binary_layout!(#[pad_to(4)] #[assert_pow2_size] #[deny_gaps] padded, LittleEndian, {
+            first: u8,
+        });
+
+        assert_eq!(&[] as &[(usize, usize)], no_pins::GAPS);
+        assert_eq!(&[(2, 6), (7, 8)], pinned_to_end::GAPS);
+        assert_eq!(&[] as &[(usize, usize)], no_gaps::GAPS);
+        assert_eq!(Some(8), no_gaps::SIZE);
+        assert_eq!(&[] as &[(usize, usize)], padded::GAPS);
     }
 
     #[test]
@@ -0,0 +1,66 @@
+/// This macro defines a family of [binary_layout!](crate::binary_layout!) layouts that all start with the same header fields.
+///
+/// The header fields are written once and spliced in front of the fields of each message layout, so each message layout
+/// is a flat layout with the header fields as its first fields and its own fields placed after them.
+/// The macro also defines a layout for the header on its own. Because the header is a prefix of each message,
+/// its `View` can read the header of a storage before you know which message it holds.
+///
+/// The header needs to have at least one field and all its fields need to have a static size.
+/// Field attributes like `#[name(...)]` or `#[default(...)]` work for header fields like for any other field.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, binary_layout_family};
+///
+/// binary_layout_family!(header, BigEndian, {
+///   message_type: u16,
+///   sequence: u16,
+///   len: u32,
+/// }, {
+///   ping: {
+///     token: u64,
+///   },
+///   data: {
+///     channel: u8,
+///     payload: [u8],
+///   },
+/// });
+///
+/// # fn main() {
+/// assert_eq!(Some(8), header::SIZE);
+/// assert_eq!(Some(16), ping::SIZE);
+/// assert_eq!(8, data::channel::OFFSET);
+///
+/// let mut storage = [0; 12];
+/// let mut view = data::View::new(&mut storage);
+/// view.message_type_mut().write(2);
+/// view.sequence_mut().write(7);
+/// view.payload_mut().copy_from_slice(&[1, 2, 3]);
+///
+/// let header = header::View::new(&storage);
+/// assert_eq!(2, header.message_type().read());
+/// assert_eq!(7, header.sequence().read());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! binary_layout_family {
+    ($header_name: ident, $endianness: ident, $header: tt, {$($name: ident : {$($fields: tt)*}),* $(,)?}) => {
+        $crate::binary_layout_family!(@header $header_name, $endianness, $header);
+        $(
+            $crate::binary_layout_family!(@splice $name, $endianness, [] $header {$($fields)*});
+        )*
+    };
+    (@header $header_name: ident, $endianness: ident, {$($header: tt)*}) => {
+        $crate::binary_layout!($header_name, $endianness, {$($header)*});
+    };
+    // Moves the header fields over one token at a time so that a trailing comma after the last header field can be dropped
+    (@splice $name: ident, $endianness: ident, [$($header: tt)*] {} {$($fields: tt)*}) => {
+        $crate::binary_layout!($name, $endianness, {$($header)*, $($fields)*});
+    };
+    (@splice $name: ident, $endianness: ident, [$($header: tt)*] {,} {$($fields: tt)*}) => {
+        $crate::binary_layout!($name, $endianness, {$($header)*, $($fields)*});
+    };
+    (@splice $name: ident, $endianness: ident, [$($header: tt)*] {$next: tt $($rest: tt)*} $fields: tt) => {
+        $crate::binary_layout_family!(@splice $name, $endianness, [$($header)* $next] {$($rest)*} $fields);
+    };
+}
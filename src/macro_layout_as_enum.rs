@@ -0,0 +1,330 @@
+/// This error is thrown when reading an enum defined with [layout_as_enum!](crate::layout_as_enum!)
+/// from a value that doesn't match the discriminant of any of its variants.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidDiscriminantError<U> {
+    /// The value that was read from the storage
+    pub value: U,
+}
+
+impl<U: core::fmt::Debug> core::fmt::Display for InvalidDiscriminantError<U> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "InvalidDiscriminantError: {:?}", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<U: core::fmt::Debug> std::error::Error for InvalidDiscriminantError<U> {}
+
+/// This macro defines a fieldless enum and implements [LayoutAs](crate::LayoutAs) for it, so that it can be used
+/// as a field type in a [binary_layout!](crate::binary_layout!) layout.
+///
+/// The `#[layout_as(...)]` attribute defines the integer type the discriminant is stored as in the layout.
+/// It is independent of the `#[repr(...)]` of the enum, so, for example, an enum with `#[repr(u8)]` can be stored as a [u16] to match
+/// a specification that reserves space for future variants. The `#[layout_as(...)]` attribute must be the first attribute of the enum.
+///
+/// Reading a value that doesn't match any variant throws an [InvalidDiscriminantError]. Because of this, you need to use
+/// [FieldCopyAccess::try_read](crate::FieldCopyAccess::try_read) to read these fields. Writing can't fail.
+/// Defining an enum with a discriminant that doesn't fit into the `#[layout_as(...)]` type is a compile time error.
+///
+/// # Example
+/// ```
+/// use binary_layout::{prelude::*, layout_as_enum};
+///
+/// layout_as_enum! {
+///   #[layout_as(u16)]
+///   #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///   #[repr(u8)]
+///   pub enum MessageKind {
+///     Request = 1,
+///     Response = 2,
+///     Error = 0xFF,
+///   }
+/// }
+///
+/// binary_layout!(message, BigEndian, {
+///   kind: MessageKind as u16,
+///   payload: [u8],
+/// });
+///
+/// # fn main() {
+/// let mut storage = [0; 16];
+/// message::kind::write(&mut storage, MessageKind::Error);
+/// assert_eq!([0x00, 0xFF], storage[..2]);
+/// assert_eq!(MessageKind::Error, message::kind::try_read(&storage).unwrap());
+///
+/// storage[..2].copy_from_slice(&[0x01, 0x00]);
+/// assert!(message::kind::try_read(&storage).is_err());
+/// # }
+/// ```
+///
+/// # Catch-all variant
+/// For protocols that add new values over time, the last variant can be marked with `#[layout_unknown]` and hold the raw value
+/// of the `#[layout_as(...)]` type, e.g. `#[layout_unknown] Unknown(u8)`. Values that don't match the discriminant of any other variant
+/// are then read into this variant instead of throwing an error, so reading can't fail and you can use [read](crate::FieldReadExt::read).
+/// Writing the catch-all variant writes the raw value it holds, so unknown values round-trip exactly.
+///
+/// Since the enum then has a variant with a field, its other variants can't be cast into an integer and each of them needs an explicit
+/// discriminant, which only determines the value they're stored as. The `#[layout_unknown]` attribute must be the first attribute of its variant.
+///
+/// ```
+/// use binary_layout::{prelude::*, layout_as_enum};
+///
+/// layout_as_enum! {
+///   #[layout_as(u8)]
+///   #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///   pub enum Command {
+///     Start = 1,
+///     Stop = 2,
+///     #[layout_unknown]
+///     Unknown(u8),
+///   }
+/// }
+///
+/// binary_layout!(message, BigEndian, {
+///   command: Command as u8,
+/// });
+///
+/// # fn main() {
+/// let mut storage = [2];
+/// assert_eq!(Command::Stop, message::command::read(&storage));
+///
+/// storage[0] = 0x42;
+/// assert_eq!(Command::Unknown(0x42), message::command::read(&storage));
+///
+/// message::command::write(&mut storage, Command::Unknown(0x43));
+/// assert_eq!([0x43], storage);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! layout_as_enum {
+    (
+        #[layout_as($underlying_type: ty)]
+        $(#[$attr: meta])*
+        $vis: vis enum $name: ident {
+            $($(#[$variant_attr: meta])* $variant: ident $(= $discriminant: expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $($(#[$variant_attr])* $variant $(= $discriminant)?,)*
+        }
+
+        const _: () = {
+            $(
+                assert!(
+                    $name::$variant as $underlying_type as i128 == $name::$variant as i128,
+                    concat!("The discriminant of `", stringify!($name), "::", stringify!($variant), "` doesn't fit into `", stringify!($underlying_type), "`"),
+                );
+            )*
+        };
+
+        impl $crate::LayoutAs<$underlying_type> for $name {
+            type ReadError = $crate::InvalidDiscriminantError<$underlying_type>;
+            type WriteError = ::core::convert::Infallible;
+
+            #[allow(clippy::unnecessary_cast)]
+            fn try_read(v: $underlying_type) -> ::core::result::Result<Self, Self::ReadError> {
+                $(
+                    if v == $name::$variant as $underlying_type {
+                        return ::core::result::Result::Ok($name::$variant);
+                    }
+                )*
+                ::core::result::Result::Err($crate::InvalidDiscriminantError { value: v })
+            }
+
+            #[allow(clippy::unnecessary_cast)]
+            fn try_write(v: Self) -> ::core::result::Result<$underlying_type, Self::WriteError> {
+                ::core::result::Result::Ok(v as $underlying_type)
+            }
+        }
+    };
+    // Enums with a `#[layout_unknown]` catch-all variant. The variants are moved over one at a time
+    // until only the catch-all variant is left, since it has to be the last one.
+    (
+        #[layout_as($underlying_type: ty)]
+        $(#[$attr: meta])*
+        $vis: vis enum $name: ident {
+            $($variants: tt)*
+        }
+    ) => {
+        $crate::layout_as_enum!(@with_unknown [$underlying_type] [$(#[$attr])*] [$vis] $name [] $($variants)*);
+    };
+    (@with_unknown [$underlying_type: ty] [$(#[$attr: meta])*] [$vis: vis] $name: ident [$([$(#[$variant_attr: meta])*] $variant: ident = $discriminant: expr;)*] #[layout_unknown] $(#[$unknown_attr: meta])* $unknown: ident($unknown_type: ty) $(,)?) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $($(#[$variant_attr])* $variant,)*
+            $(#[$unknown_attr])* $unknown($unknown_type),
+        }
+
+        const _: () = {
+            $(
+                assert!(
+                    ($discriminant) as $underlying_type as i128 == ($discriminant) as i128,
+                    concat!("The discriminant of `", stringify!($name), "::", stringify!($variant), "` doesn't fit into `", stringify!($underlying_type), "`"),
+                );
+            )*
+        };
+
+        impl $crate::LayoutAs<$underlying_type> for $name {
+            type ReadError = ::core::convert::Infallible;
+            type WriteError = ::core::convert::Infallible;
+
+            #[allow(clippy::unnecessary_cast)]
+            fn try_read(v: $underlying_type) -> ::core::result::Result<Self, Self::ReadError> {
+                $(
+                    if v == ($discriminant) as $underlying_type {
+                        return ::core::result::Result::Ok($name::$variant);
+                    }
+                )*
+                ::core::result::Result::Ok($name::$unknown(v))
+            }
+
+            #[allow(clippy::unnecessary_cast)]
+            fn try_write(v: Self) -> ::core::result::Result<$underlying_type, Self::WriteError> {
+                ::core::result::Result::Ok(match v {
+                    $($name::$variant => ($discriminant) as $underlying_type,)*
+                    $name::$unknown(value) => value,
+                })
+            }
+        }
+    };
+    (@with_unknown $underlying_type: tt $attrs: tt $vis: tt $name: ident [$($known: tt)*] $(#[$variant_attr: meta])* $variant: ident = $discriminant: expr, $($rest: tt)*) => {
+        $crate::layout_as_enum!(@with_unknown $underlying_type $attrs $vis $name [$($known)* [$(#[$variant_attr])*] $variant = $discriminant;] $($rest)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{InvalidDiscriminantError, LayoutAs, WrappedFieldError};
+
+    layout_as_enum! {
+        #[layout_as(u16)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(u8)]
+        pub enum Wide {
+            First = 1,
+            Second,
+            /// Variants can have attributes
+            Last = 0xFF,
+        }
+    }
+
+    layout_as_enum! {
+        #[layout_as(i32)]
+        #[derive(Debug, PartialEq, Eq)]
+        #[repr(i8)]
+        pub enum Signed {
+            Negative = -1,
+            Zero = 0,
+            Positive = 1
+        }
+    }
+
+    layout_as_enum! {
+        #[layout_as(u8)]
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum Implicit {
+            A,
+            B,
+        }
+    }
+
+    layout_as_enum! {
+        #[layout_as(u16)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum WithUnknown {
+            First = 1,
+            /// Variants can have attributes
+            Second = 0x0200,
+            #[layout_unknown]
+            /// The catch-all variant can have attributes after `#[layout_unknown]`
+            Unknown(u16),
+        }
+    }
+
+    binary_layout!(layout, LittleEndian, {
+        wide: Wide as u16,
+        signed: Signed as i32,
+        implicit: Implicit as u8,
+    });
+
+    binary_layout!(unknown_layout, BigEndian, {
+        value: WithUnknown as u16,
+    });
+
+    #[test]
+    fn metadata() {
+        assert_eq!(Some(7), layout::SIZE);
+        assert_eq!(Some(2), layout::wide::SIZE);
+        assert_eq!(Some(4), layout::signed::SIZE);
+        assert_eq!(Some(1), layout::implicit::SIZE);
+    }
+
+    #[test]
+    fn read_write() {
+        let mut storage = [0; 7];
+        layout::wide::write(&mut storage, Wide::Second);
+        layout::signed::write(&mut storage, Signed::Negative);
+        layout::implicit::write(&mut storage, Implicit::B);
+        assert_eq!([2, 0, 0xFF, 0xFF, 0xFF, 0xFF, 1], storage);
+        assert_eq!(Wide::Second, layout::wide::try_read(&storage).unwrap());
+        assert_eq!(
+            Signed::Negative,
+            layout::signed::try_read(&storage).unwrap()
+        );
+        assert_eq!(Implicit::B, layout::implicit::try_read(&storage).unwrap());
+
+        layout::wide::write(&mut storage, Wide::Last);
+        assert_eq!([0xFF, 0], storage[..2]);
+        assert_eq!(Wide::Last, layout::wide::try_read(&storage).unwrap());
+    }
+
+    #[test]
+    fn given_invalid_discriminant_then_read_fails() {
+        assert_eq!(
+            Err(InvalidDiscriminantError { value: 0x0101 }),
+            <Wide as LayoutAs<u16>>::try_read(0x0101)
+        );
+        assert_eq!(
+            Err(InvalidDiscriminantError { value: 2 }),
+            <Signed as LayoutAs<i32>>::try_read(2)
+        );
+
+        let storage = [0, 1, 0, 0, 0, 0, 2];
+        assert!(matches!(
+            layout::wide::try_read(&storage),
+            Err(WrappedFieldError::LayoutAsError(InvalidDiscriminantError {
+                value: 0x0100
+            }))
+        ));
+        assert!(matches!(
+            layout::implicit::try_read(&storage),
+            Err(WrappedFieldError::LayoutAsError(InvalidDiscriminantError {
+                value: 2
+            }))
+        ));
+    }
+
+    #[test]
+    fn catch_all_variant() {
+        let mut storage = [0x02, 0x00];
+        assert_eq!(WithUnknown::Second, unknown_layout::value::read(&storage));
+        unknown_layout::value::write(&mut storage, WithUnknown::First);
+        assert_eq!([0x00, 0x01], storage);
+        assert_eq!(WithUnknown::First, unknown_layout::value::read(&storage));
+    }
+
+    #[test]
+    fn catch_all_variant_round_trips_unknown_values() {
+        for raw in [0, 2, 0x0201, 0xFFFF] {
+            let storage = u16::to_be_bytes(raw);
+            let value = unknown_layout::value::read(&storage);
+            assert_eq!(WithUnknown::Unknown(raw), value);
+
+            let mut written = [0; 2];
+            unknown_layout::value::write(&mut written, value);
+            assert_eq!(storage, written);
+        }
+    }
+}
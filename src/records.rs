@@ -0,0 +1,105 @@
+use core::marker::PhantomData;
+
+use crate::fields::primitive::{BorrowingNestedView, NestedViewInfo};
+
+/// An iterator over records of the same layout that are stored back to back in a storage,
+/// e.g. a sequence of type-length-value (TLV) records. Each record starts right after the previous one ends,
+/// and the length of each record is the [byte_len](crate::binary_layout#struct-view) of the record,
+/// i.e. it can be computed from the record's own fields.
+///
+/// Create it by calling the `iter_records` function that the [binary_layout!](crate::binary_layout!) macro generates for each layout.
+/// Iteration stops once the storage is exhausted or the remaining storage is shorter than the next record.
+/// Records with a length of zero also end the iteration since they would never advance.
+///
+/// # Example
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(tlv, BigEndian, {
+///   tag: u8,
+///   len: u8,
+///   value: [u8; expr(|v| v.len().read() as usize)],
+/// });
+///
+/// let storage = [1, 2, 0xAA, 0xBB, 2, 1, 0xCC];
+/// let tags: Vec<(u8, &[u8])> = tlv::iter_records(&storage)
+///     .map(|record| (record.tag().read(), record.into_value().into_slice()))
+///     .collect();
+/// assert_eq!(vec![(1, &[0xAA, 0xBB][..]), (2, &[0xCC][..])], tags);
+/// ```
+///
+/// # Recursive formats
+/// Some formats nest records of the same layout within each other, e.g. a TLV record whose value is a list of TLV records again.
+/// Since a layout can't contain itself, parse these recursively at runtime by calling `iter_records` on the
+/// value of a record. Limit the recursion depth to protect against malicious input.
+///
+/// ```
+/// use binary_layout::prelude::*;
+///
+/// binary_layout!(tlv, BigEndian, {
+///   tag: u8,
+///   len: u8,
+///   value: [u8; expr(|v| v.len().read() as usize)],
+/// });
+///
+/// const CONSTRUCTED: u8 = 0x20;
+///
+/// fn count_leaves(storage: &[u8], max_depth: usize) -> Option<usize> {
+///   let mut leaves = 0;
+///   for record in tlv::iter_records(storage) {
+///     if record.tag().read() & CONSTRUCTED != 0 {
+///       let depth = max_depth.checked_sub(1)?;
+///       leaves += count_leaves(record.value(), depth)?;
+///     } else {
+///       leaves += 1;
+///     }
+///   }
+///   Some(leaves)
+/// }
+///
+/// let storage = [0x21, 6, 0x01, 0, 0x22, 2, 0x02, 0, 0x03, 0];
+/// assert_eq!(Some(3), count_leaves(&storage, 2));
+/// assert_eq!(None, count_leaves(&storage, 1));
+/// ```
+pub struct Records<'a, N> {
+    remaining: &'a [u8],
+    _p: PhantomData<N>,
+}
+
+impl<'a, N: NestedViewInfo + BorrowingNestedView<&'a [u8]>> Records<'a, N> {
+    /// Create an iterator over the records of the layout `N` (the `NestedView` marker type of the layout) in the given storage.
+    #[inline]
+    pub fn new(storage: &'a [u8]) -> Self {
+        Self {
+            remaining: storage,
+            _p: PhantomData,
+        }
+    }
+
+    /// Return the part of the storage that wasn't consumed by the iterator yet.
+    /// After iteration ended, this is empty unless the storage ended in a truncated record.
+    #[inline]
+    pub fn remainder(&self) -> &'a [u8] {
+        self.remaining
+    }
+}
+
+impl<'a, N: NestedViewInfo + BorrowingNestedView<&'a [u8]>> Iterator for Records<'a, N> {
+    type Item = N::View;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        if N::MIN_SIZE > self.remaining.len() {
+            return None;
+        }
+        let len = N::byte_len(self.remaining);
+        if len == 0 || len > self.remaining.len() {
+            return None;
+        }
+        let (record, remaining) = self.remaining.split_at(len);
+        self.remaining = remaining;
+        Some(N::view(record))
+    }
+}
@@ -34,6 +34,13 @@ impl<S> Data<S> {
         self.region.is_empty()
     }
 
+    /// Return the offset of the [Data] instance within the storage it was created from.
+    /// This is `0` unless the instance is a subregion created with [Data::into_subregion].
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        self.region.start
+    }
+
     /// Return a [Data] instance that semantically only represents a subregion of the original instance.
     /// Using any data accessors like `AsRef<[u8]>` or `AsMut<[u8]>` on the new instance will behave
     /// as if the instance only owned the subregion.
@@ -333,6 +340,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn offset() {
+        let data: Data<_> = data_region(1024, 0).into();
+        assert_eq!(0, data.offset());
+        let data = data.into_subregion(5..);
+        assert_eq!(5, data.offset());
+        let data = data.into_subregion(10..=20);
+        assert_eq!(15, data.offset());
+        let data = data.into_subregion(..3);
+        assert_eq!(15, data.offset());
+    }
+
     #[test]
     #[should_panic(
         expected = "Range end out of bounds. Tried to access subregion ..=1024 for a Data instance of length 1024"
@@ -0,0 +1,214 @@
+use binary_layout::prelude::*;
+use std::convert::TryInto;
+
+mod common;
+use common::data_region;
+
+binary_layout!(computed_length, BigEndian, {
+    header_len: u16,
+    num_words: u8,
+    header: [u8; expr(|v| v.header_len().read() as usize - 8)],
+});
+
+binary_layout!(computed_from_multiple_fields, LittleEndian, {
+    num_words: u8,
+    word_size: u8,
+    words: [u8; expr(|v| v.num_words().read() as usize * v.word_size().read() as usize)],
+});
+
+fn storage_with_header_len(header_len: u16) -> Vec<u8> {
+    let mut storage = data_region(1024, 5);
+    storage[0..2].copy_from_slice(&header_len.to_be_bytes());
+    storage
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(0, computed_length::header_len::OFFSET);
+    assert_eq!(Some(2), computed_length::header_len::SIZE);
+    assert_eq!(2, computed_length::num_words::OFFSET);
+    assert_eq!(Some(1), computed_length::num_words::SIZE);
+    assert_eq!(3, computed_length::header::OFFSET);
+    assert_eq!(None, computed_length::header::SIZE);
+    assert_eq!(None, computed_length::SIZE);
+}
+
+#[test]
+fn fields() {
+    let mut storage = storage_with_header_len(108);
+
+    // Test initial data is read correctly
+    assert_eq!(100, computed_length::header::data(&storage).len());
+    assert_eq!(100, computed_length::header::data_mut(&mut storage).len());
+    assert_eq!(
+        &data_region(1024, 5)[3..103],
+        computed_length::header::data(&storage)
+    );
+
+    // Test data can be written
+    computed_length::header::data_mut(&mut storage).copy_from_slice(&data_region(100, 6));
+    assert_eq!(
+        &data_region(100, 6),
+        computed_length::header::data(&storage)
+    );
+    assert_eq!(&data_region(1024, 5)[103..], &storage[103..]);
+
+    // Test the length is re-evaluated after the length field changed
+    computed_length::header_len::write(&mut storage, 18);
+    assert_eq!(10, computed_length::header::data(&storage).len());
+    assert_eq!(
+        &data_region(100, 6)[..10],
+        computed_length::header::data(&storage)
+    );
+}
+
+#[test]
+fn length_from_multiple_fields() {
+    let mut storage = data_region(1024, 5);
+    computed_from_multiple_fields::num_words::write(&mut storage, 10);
+    computed_from_multiple_fields::word_size::write(&mut storage, 4);
+    assert_eq!(
+        40,
+        computed_from_multiple_fields::words::data(&storage).len()
+    );
+    assert_eq!(
+        &data_region(1024, 5)[2..42],
+        computed_from_multiple_fields::words::data(&storage)
+    );
+}
+
+#[test]
+fn view_readonly() {
+    let storage = storage_with_header_len(58);
+    let view = computed_length::View::new(&storage);
+
+    assert_eq!(58, view.header_len().read());
+    assert_eq!(
+        u8::from_be_bytes((&data_region(1024, 5)[2..3]).try_into().unwrap()),
+        view.num_words().read()
+    );
+    assert_eq!(&data_region(1024, 5)[3..53], view.header());
+
+    // Test into_storage will return correct data
+    let extracted_storage: &Vec<u8> = view.into_storage();
+    assert_eq!(storage, *extracted_storage);
+}
+
+#[test]
+fn view_readwrite() {
+    let mut storage = storage_with_header_len(58);
+    let mut view = computed_length::View::new(&mut storage);
+
+    assert_eq!(&data_region(1024, 5)[3..53], view.header());
+
+    // Test data can be written
+    view.header_mut().copy_from_slice(&data_region(50, 6));
+    assert_eq!(&data_region(50, 6), view.header());
+
+    // Test the length is re-evaluated after the length field changed
+    view.header_len_mut().write(10);
+    assert_eq!(&data_region(50, 6)[..2], view.header());
+
+    // Test storage is actually changed
+    assert_eq!(&data_region(50, 6), &storage[3..53]);
+    assert_eq!(&data_region(1024, 5)[53..], &storage[53..]);
+}
+
+#[test]
+fn view_vec_into_field() {
+    let view = computed_length::View::new(storage_with_header_len(58));
+    let extracted = view.into_header();
+    assert_eq!(&data_region(1024, 5)[3..53], &*extracted);
+}
+
+#[test]
+#[should_panic]
+fn given_too_short_storage_when_accessing_then_panics() {
+    let storage = storage_with_header_len(2000);
+    computed_length::header::data(&storage);
+}
+
+#[test]
+fn byte_len() {
+    let storage = storage_with_header_len(58);
+    assert_eq!(53, computed_length::byte_len(&storage));
+
+    let mut storage = data_region(1024, 5);
+    let mut view = computed_from_multiple_fields::View::new(&mut storage);
+    view.num_words_mut().write(3);
+    view.word_size_mut().write(8);
+    assert_eq!(26, computed_from_multiple_fields::byte_len(&storage));
+    computed_from_multiple_fields::num_words::write(&mut storage, 0);
+    assert_eq!(2, computed_from_multiple_fields::byte_len(&storage));
+}
+
+#[test]
+fn required_len() {
+    assert_eq!(53, computed_length::required_len(50));
+    assert_eq!(3, computed_length::required_len(0));
+    assert_eq!(26, computed_from_multiple_fields::required_len(24));
+
+    let mut storage = vec![0; computed_from_multiple_fields::required_len(24)];
+    let mut view = computed_from_multiple_fields::View::new(&mut storage);
+    view.num_words_mut().write(3);
+    view.word_size_mut().write(8);
+    assert_eq!(
+        computed_from_multiple_fields::byte_len(&storage),
+        storage.len()
+    );
+}
+
+#[test]
+fn try_new() {
+    let storage = storage_with_header_len(58);
+    assert!(computed_length::try_new(&storage[..53]).is_ok());
+    assert_eq!(
+        Err(LayoutError::StorageTooShort {
+            expected: 53,
+            actual: 52
+        }),
+        computed_length::try_new(&storage[..52]).map(|_| ())
+    );
+    assert_eq!(
+        Err(LayoutError::StorageTooShort {
+            expected: 3,
+            actual: 2
+        }),
+        computed_length::try_new(&storage[..2]).map(|_| ())
+    );
+}
+
+#[test]
+fn try_new_exact() {
+    let storage = storage_with_header_len(58);
+    assert!(computed_length::try_new_exact(&storage[..53]).is_ok());
+    assert_eq!(
+        Err(LayoutError::StorageTooLong {
+            expected: 53,
+            actual: 58
+        }),
+        computed_length::try_new_exact(&storage[..58]).map(|_| ())
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_vec_truncating() {
+    let storage = storage_with_header_len(58);
+    let view = computed_length::from_vec_truncating(storage.clone()).unwrap();
+    assert_eq!(&storage[..53], &view.into_storage()[..]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn field_layout() {
+    let storage = storage_with_header_len(12);
+    assert_eq!(
+        vec![("header_len", 0..2), ("num_words", 2..3), ("header", 3..7)],
+        computed_length::field_layout(&storage)
+    );
+    assert_eq!(
+        vec![("num_words", 0..1), ("word_size", 1..2), ("words", 2..8)],
+        computed_from_multiple_fields::field_layout(&[2, 3, 0, 0, 0, 0, 0, 0, 0xFF])
+    );
+}
@@ -0,0 +1,79 @@
+use binary_layout::{binary_layout_family, prelude::*};
+
+mod common;
+use common::data_region;
+
+binary_layout_family!(header, LittleEndian, {
+    message_type: u8,
+    #[name(length)]
+    len: u16,
+    flags: [u8; 3],
+}, {
+    empty: {},
+    login: {
+        user_id: u32,
+        session: u64,
+    },
+    upload: {
+        checksum: u32,
+        content: [u8],
+    },
+});
+
+binary_layout_family!(compact_header, BigEndian, {
+    message_type: u8
+}, {
+    reading: {
+        value: i16,
+    },
+});
+
+#[test]
+fn metadata() {
+    assert_eq!(Some(6), header::SIZE);
+    assert_eq!(Some(6), empty::SIZE);
+    assert_eq!(Some(18), login::SIZE);
+    assert_eq!(None, upload::SIZE);
+
+    assert_eq!(0, login::message_type::OFFSET);
+    assert_eq!(1, login::length::OFFSET);
+    assert_eq!(3, login::flags::OFFSET);
+    assert_eq!(6, login::user_id::OFFSET);
+    assert_eq!(10, login::session::OFFSET);
+    assert_eq!(6, upload::checksum::OFFSET);
+    assert_eq!(10, upload::content::OFFSET);
+
+    assert_eq!(Some(1), compact_header::SIZE);
+    assert_eq!(1, reading::value::OFFSET);
+}
+
+#[test]
+fn header_view_reads_any_message() {
+    let mut storage = data_region(1024, 0);
+    let mut view = upload::View::new(&mut storage);
+    view.message_type_mut().write(3);
+    view.length_mut().write(1000);
+    view.flags_mut().copy_from_slice(&[1, 2, 3]);
+    view.checksum_mut().write(0xDEAD_BEEF);
+    view.content_mut()[..2].copy_from_slice(&[4, 5]);
+
+    assert_eq!(
+        &[3, 0xE8, 0x03, 1, 2, 3, 0xEF, 0xBE, 0xAD, 0xDE, 4, 5],
+        &storage[..12]
+    );
+
+    let header = header::View::new(&storage);
+    assert_eq!(3, header.message_type().read());
+    assert_eq!(1000, header.length().read());
+    assert_eq!(&[1, 2, 3], header.flags());
+    assert_eq!(0xDEAD_BEEF, login::user_id::read(&storage));
+}
+
+#[test]
+fn header_without_trailing_comma() {
+    let mut storage = [0; 3];
+    reading::message_type::write(&mut storage, 7);
+    reading::value::write(&mut storage, -2);
+    assert_eq!([7, 0xFF, 0xFE], storage);
+    assert_eq!(7, compact_header::View::new(&storage).message_type().read());
+}
@@ -12,6 +12,7 @@ binary_layout!(header, BigEndian, {
     field1: i16,
 });
 binary_layout!(middle, NativeEndian, {
+    #[flatten(deep_nesting::{field1})]
     deep: deep_nesting::NestedView,
     field1: u16,
 });
@@ -23,8 +24,10 @@ binary_layout!(footer, BigEndian, {
 binary_layout!(whole, LittleEndian, {
     head: header::NestedView,
     field1: u64,
+    #[flatten(middle::{deep_field1})]
     mid: middle::NestedView,
     field2: NonZeroU128,
+    #[flatten(footer::{tail})]
     foot: footer::NestedView,
 });
 
@@ -42,7 +45,73 @@ fn metadata() {
     assert_eq!(None, whole::foot::SIZE);
 }
 
-// TODO Test field API once it supports nesting (see "fields" test function in other integration tests here)
+#[test]
+fn fields() {
+    let mut storage = data_region(1024, 5);
+
+    // Test initial data is read correctly
+    assert_eq!(
+        i16::from_be_bytes((&data_region(1024, 5)[0..2]).try_into().unwrap()),
+        whole::head::view(&storage).field1().read(),
+    );
+    assert_eq!(
+        u16::from_le_bytes((&data_region(1024, 5)[10..12]).try_into().unwrap()),
+        whole::mid::view(&storage).deep().field1().read(),
+    );
+    assert_eq!(
+        u32::from_be_bytes((&data_region(1024, 5)[30..34]).try_into().unwrap()),
+        whole::foot::view(&storage).field1().read()
+    );
+    assert_eq!(
+        &data_region(1024, 5)[36..],
+        whole::foot::view(&storage).tail()
+    );
+
+    // Test data can be written
+    whole::head::view_mut(&mut storage).field1_mut().write(-40);
+    whole::mid::view_mut(&mut storage)
+        .deep_mut()
+        .field1_mut()
+        .write(10);
+    whole::foot::view_mut(&mut storage)
+        .field1_mut()
+        .write(10_u32.pow(7));
+
+    // Test reading will return changed data
+    assert_eq!(-40, whole::head::view(&storage).field1().read());
+    assert_eq!(10, whole::mid::view(&storage).deep().field1().read());
+    assert_eq!(10_u32.pow(7), whole::foot::view(&storage).field1().read());
+
+    // Test the nested views offer the same API as top level views
+    assert_eq!(
+        2,
+        header::byte_len(whole::head::view(&storage).into_storage())
+    );
+    assert_eq!(&storage[30..], whole::foot::view(&storage).into_storage());
+    assert_eq!(
+        &data_region(1024, 5)[36..],
+        &*whole::foot::view(&storage).into_tail()
+    );
+
+    // Test into_view
+    let foot = whole::foot::into_view(storage);
+    assert_eq!(10_u32.pow(7), foot.field1().read());
+    assert_eq!(&data_region(1024, 5)[36..], &*foot.into_tail());
+}
+
+fn borrow_nested(storage: &[u8]) -> footer::View<&[u8]> {
+    whole::foot::view(storage)
+}
+
+#[test]
+fn nested_view_can_be_returned_from_function() {
+    let storage = data_region(1024, 5);
+    let foot = borrow_nested(&storage);
+    assert_eq!(
+        u16::from_le_bytes((&data_region(1024, 5)[34..36]).try_into().unwrap()),
+        foot.deep().field1().read(),
+    );
+}
 
 #[test]
 fn view_readonly() {
@@ -336,3 +405,88 @@ fn view_vec_readwrite() {
     let extracted_storage_2 = view.into_foot().into_storage();
     assert_eq!(&&extracted_storage[30..], &extracted_storage_2.as_ref());
 }
+
+binary_layout!(nested_computed_tail, BigEndian, {
+    len: u8,
+    data: [u8; expr(|v| v.len().read() as usize)],
+});
+binary_layout!(with_nested_computed_tail, LittleEndian, {
+    field1: u32,
+    nested: nested_computed_tail::NestedView,
+});
+
+#[test]
+fn byte_len() {
+    let storage = data_region(1024, 5);
+    assert_eq!(1024, whole::byte_len(&storage));
+    assert_eq!(4, middle::byte_len(&storage[whole::mid::OFFSET..]));
+    assert_eq!(1024 - 30, footer::byte_len(&storage[whole::foot::OFFSET..]));
+
+    let mut storage = data_region(1024, 5);
+    let mut view = with_nested_computed_tail::View::new(&mut storage);
+    view.nested_mut().len_mut().write(20);
+    assert_eq!(21, nested_computed_tail::byte_len(&storage[4..]));
+    assert_eq!(25, with_nested_computed_tail::byte_len(&storage));
+}
+
+#[test]
+fn required_len() {
+    // The tail of a layout ending in a nested layout is the whole nested layout
+    assert_eq!(25, with_nested_computed_tail::required_len(21));
+    assert_eq!(5, with_nested_computed_tail::required_len(0));
+    assert_eq!(21, nested_computed_tail::required_len(20));
+    assert_eq!(1034, whole::required_len(1004));
+    assert_eq!(Some(4), middle::SIZE);
+    assert_eq!(4, middle::required_len(10));
+}
+
+binary_layout!(sub_header, BigEndian, {
+    version: u8,
+    flags: u16,
+});
+binary_layout!(message, BigEndian, {
+    header: sub_header::NestedView,
+    count: u16,
+    body: [u8],
+});
+
+#[test]
+fn fields_after_fixed_size_nested_layout() {
+    assert_eq!(0, message::header::OFFSET);
+    assert_eq!(Some(3), message::header::SIZE);
+    assert_eq!(3, message::count::OFFSET);
+    assert_eq!(5, message::body::OFFSET);
+    assert_eq!(None, message::SIZE);
+
+    let mut storage = data_region(1024, 5);
+    let mut view = message::View::new(&mut storage);
+    view.header_mut().version_mut().write(1);
+    view.header_mut().flags_mut().write(0x0203);
+    view.count_mut().write(0x0405);
+    view.body_mut()[0] = 6;
+    assert_eq!(&[1, 2, 3, 4, 5, 6], &storage[..6]);
+    assert_eq!(&data_region(1024, 5)[6..], &storage[6..]);
+}
+
+#[test]
+fn flattened_fields() {
+    use whole::{foot_tail, mid_deep_field1};
+
+    assert_eq!(10, mid_deep_field1::OFFSET);
+    assert_eq!(Some(2), mid_deep_field1::SIZE);
+    assert_eq!(36, foot_tail::OFFSET);
+    assert_eq!(None, foot_tail::SIZE);
+
+    let mut storage = data_region(1024, 5);
+    assert_eq!(
+        u16::from_le_bytes((&data_region(1024, 5)[10..12]).try_into().unwrap()),
+        mid_deep_field1::read(&storage)
+    );
+    assert_eq!(&data_region(1024, 5)[36..], foot_tail::data(&storage));
+
+    mid_deep_field1::write(&mut storage, 1000);
+    foot_tail::data_mut(&mut storage).copy_from_slice(&data_region(1024, 6)[36..]);
+    let view = whole::View::new(&storage);
+    assert_eq!(1000, view.mid().deep().field1().read());
+    assert_eq!(&data_region(1024, 6)[36..], view.foot().tail());
+}
@@ -233,3 +233,133 @@ fn view_vec_readwrite() {
         u16::from_le_bytes((&extracted_storage[13..15]).try_into().unwrap())
     );
 }
+
+#[test]
+fn byte_len() {
+    let storage = data_region(1024, 5);
+    assert_eq!(15, noslice::byte_len(&storage));
+}
+
+#[test]
+fn required_len() {
+    assert_eq!(15, noslice::required_len(0));
+    assert_eq!(15, noslice::required_len(100));
+}
+
+#[test]
+fn try_new() {
+    let storage = data_region(1024, 5);
+    assert!(noslice::try_new(&storage[..15]).is_ok());
+    assert_eq!(
+        Err(LayoutError::StorageTooShort {
+            expected: 15,
+            actual: 14
+        }),
+        noslice::try_new(&storage[..14]).map(|_| ())
+    );
+}
+
+#[test]
+fn try_new_exact() {
+    let storage = data_region(1024, 5);
+    assert!(noslice::try_new_exact(&storage[..15]).is_ok());
+    assert!(noslice::try_new(&storage[..16]).is_ok());
+    assert_eq!(
+        Err(LayoutError::StorageTooLong {
+            expected: 15,
+            actual: 16
+        }),
+        noslice::try_new_exact(&storage[..16]).map(|_| ())
+    );
+    assert_eq!(
+        Err(LayoutError::StorageTooShort {
+            expected: 15,
+            actual: 14
+        }),
+        noslice::try_new_exact(&storage[..14]).map(|_| ())
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_vec_truncating() {
+    let mut storage = data_region(1024, 5);
+    storage.truncate(20);
+    let capacity = storage.capacity();
+    let view = noslice::from_vec_truncating(storage).unwrap();
+    let storage = view.into_storage();
+    assert_eq!(15, noslice::byte_len(&storage));
+    assert_eq!(&data_region(1024, 5)[..15], &storage[..]);
+    assert_eq!(capacity, storage.capacity());
+
+    assert_eq!(
+        Err(LayoutError::StorageTooShort {
+            expected: 15,
+            actual: 14
+        }),
+        noslice::from_vec_truncating(data_region(14, 5)).map(|_| ())
+    );
+}
+
+#[test]
+fn try_from() {
+    fn parse<'a, V>(storage: &'a [u8]) -> Result<V, LayoutError>
+    where
+        V: TryFrom<&'a [u8], Error = LayoutError>,
+    {
+        V::try_from(storage)
+    }
+
+    let mut storage = data_region(1024, 5);
+    let view: noslice::View<&[u8]> = parse(&storage[..15]).unwrap();
+    assert_eq!(noslice::second::read(&storage), view.second().read());
+    let error = parse::<noslice::View<&[u8]>>(&storage[..14])
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(
+        LayoutError::StorageTooShort {
+            expected: 15,
+            actual: 14
+        },
+        error
+    );
+
+    let mut view = noslice::View::try_from(&mut storage[..]).unwrap();
+    view.fourth_mut().write(1000);
+    assert_eq!(1000, noslice::fourth::read(&storage));
+    assert!(noslice::View::try_from(&mut storage[..3]).is_err());
+}
+
+#[test]
+fn update() {
+    let mut storage = data_region(1024, 5);
+    let mut view = noslice::View::new(&mut storage);
+    view.second_mut().write(10i64.pow(15));
+    view.fourth_mut().write(1000);
+
+    view.second_mut().update(|v| v + 1);
+    view.fourth_mut().update(|v| v * 2);
+
+    assert_eq!(10i64.pow(15) + 1, view.second().read());
+    assert_eq!(2000, view.fourth().read());
+    assert_eq!(
+        10i64.pow(15) + 1,
+        i64::from_le_bytes((&storage[1..9]).try_into().unwrap())
+    );
+}
+
+#[test]
+fn read_as() {
+    let mut storage = data_region(1024, 5);
+    noslice::first::write(&mut storage, -5);
+    noslice::fourth::write(&mut storage, 60000);
+
+    assert_eq!(-5i64, noslice::first::read_as::<i64>(&storage));
+    assert_eq!(60000u64, noslice::fourth::read_as::<u64>(&storage));
+    assert_eq!(60000i32, noslice::fourth::read_as::<i32>(&storage));
+    assert_eq!(60000.0f64, noslice::fourth::read_as::<f64>(&storage));
+
+    let view = noslice::View::new(&storage);
+    assert_eq!(-5i16, view.first().read_as::<i16>());
+    assert_eq!(60000u128, view.fourth().read_as::<u128>());
+}
@@ -0,0 +1,143 @@
+use binary_layout::prelude::*;
+
+binary_layout!(tlv, LittleEndian, {
+    tag: u8,
+    len: u16,
+    value: [u8; expr(|v| v.len().read() as usize)],
+});
+
+binary_layout!(fixed, BigEndian, {
+    first: u8,
+    second: u16,
+});
+
+binary_layout!(#[pad_to(4)] #[assert_pow2_size] padded, BigEndian, {
+    first: u8,
+    second: u16,
+});
+
+#[test]
+fn given_dynamically_sized_records_then_iterates() {
+    let storage = [1, 2, 0, 10, 11, 2, 0, 0, 3, 1, 0, 12];
+    let mut records = tlv::iter_records(&storage);
+    let record = records.next().unwrap();
+    assert_eq!(1, record.tag().read());
+    assert_eq!(&[10, 11], record.value());
+    let record = records.next().unwrap();
+    assert_eq!(2, record.tag().read());
+    assert_eq!(&[] as &[u8], record.value());
+    let record = records.next().unwrap();
+    assert_eq!(3, record.tag().read());
+    assert_eq!(&[12], record.value());
+    assert!(records.next().is_none());
+    assert_eq!(&[] as &[u8], records.remainder());
+}
+
+#[test]
+fn given_statically_sized_records_then_iterates() {
+    let storage = [1, 0, 2, 3, 0, 4];
+    let values: Vec<(u8, u16)> = fixed::iter_records(&storage)
+        .map(|record| (record.first().read(), record.second().read()))
+        .collect();
+    assert_eq!(vec![(1, 2), (3, 4)], values);
+}
+
+#[test]
+fn given_empty_storage_then_yields_nothing() {
+    assert_eq!(0, tlv::iter_records(&[]).count());
+    assert_eq!(0, fixed::iter_records(&[]).count());
+}
+
+#[test]
+fn given_truncated_header_then_stops() {
+    let storage = [1, 1, 0, 10, 2, 5];
+    let mut records = tlv::iter_records(&storage);
+    assert_eq!(&[10], records.next().unwrap().value());
+    assert!(records.next().is_none());
+    assert_eq!(&[2, 5], records.remainder());
+
+    let storage = [1, 0, 2, 3];
+    let mut records = fixed::iter_records(&storage);
+    assert_eq!(2, records.next().unwrap().second().read());
+    assert!(records.next().is_none());
+    assert_eq!(&[3], records.remainder());
+}
+
+#[test]
+fn given_truncated_value_then_stops() {
+    let storage = [1, 1, 0, 10, 2, 5, 0, 1, 2];
+    let mut records = tlv::iter_records(&storage);
+    assert_eq!(&[10], records.next().unwrap().value());
+    assert!(records.next().is_none());
+    assert_eq!(&[2, 5, 0, 1, 2], records.remainder());
+}
+
+#[test]
+fn min_size() {
+    assert_eq!(None, tlv::SIZE);
+    assert_eq!(
+        3,
+        <tlv::NestedView as binary_layout::internal::NestedViewInfo>::MIN_SIZE
+    );
+    assert_eq!(
+        3,
+        <fixed::NestedView as binary_layout::internal::NestedViewInfo>::MIN_SIZE
+    );
+}
+
+binary_layout!(#[assert_pow2_size] pow2, LittleEndian, {
+    sequence: u32,
+    value: i32,
+});
+
+#[test]
+fn given_pow2_size_assertion_then_keeps_size() {
+    assert_eq!(Some(8), pow2::SIZE);
+    assert_eq!(4, pow2::value::OFFSET);
+    assert_eq!(Some(4), padded::SIZE);
+}
+
+#[test]
+fn given_padded_records_then_skips_padding() {
+    assert_eq!(Some(4), padded::SIZE);
+    assert_eq!(3..4, padded::PADDING);
+
+    let mut storage = [1, 0, 2, 0xFF, 3, 0, 4, 0xFF];
+    let values: Vec<(u8, u16)> = padded::iter_records(&storage)
+        .map(|record| (record.first().read(), record.second().read()))
+        .collect();
+    assert_eq!(vec![(1, 2), (3, 4)], values);
+
+    padded::zero_padding(&mut storage[4..]);
+    assert_eq!([1, 0, 2, 0xFF, 3, 0, 4, 0], storage);
+    assert_eq!(4, padded::byte_len(&storage));
+    assert!(padded::try_new(&storage[..3]).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn given_pushed_records_then_iterates() {
+    let mut buf = vec![0xFF];
+    let mut record = fixed::push_record(&mut buf);
+    record.first_mut().write(1);
+    record.second_mut().write(2);
+    fixed::push_record(&mut buf).second_mut().write(3);
+    assert_eq!(vec![0xFF, 1, 0, 2, 0, 0, 3], buf);
+
+    let values: Vec<(u8, u16)> = fixed::iter_records(&buf[1..])
+        .map(|record| (record.first().read(), record.second().read()))
+        .collect();
+    assert_eq!(vec![(1, 2), (0, 3)], values);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn given_pushed_dynamically_sized_record_then_tail_is_empty() {
+    let mut buf = Vec::new();
+    let record = tlv::push_record(&mut buf);
+    assert_eq!(&[] as &[u8], record.value());
+    assert_eq!(3, buf.len());
+
+    padded::push_record(&mut buf).first_mut().write(7);
+    assert_eq!(vec![0, 0, 0, 7, 0, 0, 0], buf);
+}
@@ -297,3 +297,43 @@ fn view_vec_readwrite() {
 }
 
 // TODO Here and in other tests, add an array-based alternative to the _vec_ views
+
+#[test]
+fn byte_len() {
+    let storage = data_region(1024, 5);
+    assert_eq!(1024, withslice::byte_len(&storage));
+    assert_eq!(30, withslice::byte_len(&storage[..30]));
+}
+
+#[test]
+fn required_len() {
+    const LEN: usize = withslice::required_len(10);
+    assert_eq!(30, LEN);
+    assert_eq!(20, withslice::required_len(0));
+}
+
+#[test]
+fn at_offset() {
+    let mut storage = data_region(1024, 5);
+    let mut view = withslice::at_offset(&mut storage, 100);
+    view.second_mut().write(10i64.pow(15));
+    view.third_mut().copy_from_slice(&[1, 2, 3, 4, 5]);
+    assert_eq!(10i64.pow(15), view.second().read());
+    assert_eq!(&[1, 2, 3, 4, 5], view.third());
+    assert_eq!(1024 - 100 - 20, view.sixth().len());
+    assert_eq!(
+        10i64.pow(15),
+        i64::from_le_bytes((&storage[101..109]).try_into().unwrap())
+    );
+    assert_eq!(&[1, 2, 3, 4, 5], &storage[109..114]);
+
+    let view = withslice::at_offset(&storage, 100);
+    assert_eq!(100, view.into_storage().offset());
+}
+
+#[test]
+#[should_panic(expected = "Base offset 1025 is out of bounds for a storage of length 1024")]
+fn given_base_beyond_storage_when_creating_view_at_offset_then_panics() {
+    let storage = data_region(1024, 5);
+    withslice::at_offset(&storage, 1025);
+}